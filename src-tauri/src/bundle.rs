@@ -0,0 +1,482 @@
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct BundleInfo {
+    pub bundle_id: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub category: Option<String>,
+    pub icon_file: Option<String>,
+    pub package_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleError {
+    NotFound(String),
+    NotAnAppBundle(String),
+    MissingInfoPlist(String),
+}
+
+impl std::fmt::Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleError::NotFound(path) => write!(f, "{} does not exist", path),
+            BundleError::NotAnAppBundle(path) => {
+                write!(f, "{} is not an application bundle", path)
+            }
+            BundleError::MissingInfoPlist(path) => {
+                write!(f, "{} has no readable Contents/Info.plist", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+pub(crate) fn read_info_plist(app_path: &Path) -> Option<plist::Dictionary> {
+    let value = plist::Value::from_file(app_path.join("Contents").join("Info.plist")).ok()?;
+    value.into_dictionary()
+}
+
+fn string_value(dict: &plist::Dictionary, key: &str) -> Option<String> {
+    dict.get(key)
+        .and_then(|value| value.as_string())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub(crate) fn read_bundle_info(app_path: &Path) -> Option<BundleInfo> {
+    let dict = read_info_plist(app_path)?;
+    Some(BundleInfo {
+        bundle_id: string_value(&dict, "CFBundleIdentifier"),
+        name: string_value(&dict, "CFBundleDisplayName")
+            .or_else(|| string_value(&dict, "CFBundleName")),
+        version: string_value(&dict, "CFBundleShortVersionString")
+            .or_else(|| string_value(&dict, "CFBundleVersion")),
+        category: string_value(&dict, "LSApplicationCategoryType"),
+        icon_file: string_value(&dict, "CFBundleIconFile"),
+        package_type: string_value(&dict, "CFBundlePackageType"),
+    })
+}
+
+pub(crate) fn read_app_bundle(app_path: &Path) -> Result<BundleInfo, BundleError> {
+    let display = app_path.to_string_lossy().to_string();
+    if !app_path.exists() {
+        return Err(BundleError::NotFound(display));
+    }
+    if !app_path.is_dir() || app_path.extension().is_none_or(|ext| ext != "app") {
+        return Err(BundleError::NotAnAppBundle(display));
+    }
+    let info = read_bundle_info(app_path).ok_or(BundleError::MissingInfoPlist(display.clone()))?;
+    if info
+        .package_type
+        .as_deref()
+        .is_some_and(|kind| kind != "APPL")
+    {
+        return Err(BundleError::NotAnAppBundle(display));
+    }
+    Ok(info)
+}
+
+const LEGACY_LPROJ_NAMES: [(&str, &str); 8] = [
+    ("en", "English"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("nl", "Dutch"),
+    ("pt", "Portuguese"),
+];
+
+pub(crate) fn parse_strings_file(bytes: &[u8]) -> Option<plist::Dictionary> {
+    if bytes.starts_with(b"bplist") || bytes.starts_with(b"<?xml") {
+        return plist::Value::from_reader(std::io::Cursor::new(bytes))
+            .ok()?
+            .into_dictionary();
+    }
+
+    let text = if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).ok()?
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).ok()?
+    } else {
+        let rest = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+        String::from_utf8(rest.to_vec()).ok()?
+    };
+
+    // .strings files are an old-style plist dictionary without the outer braces.
+    let wrapped = format!("{{\n{}\n}}", text);
+    plist::Value::from_reader_ascii(wrapped.as_bytes())
+        .ok()?
+        .into_dictionary()
+}
+
+fn lproj_candidates(language: &str) -> Vec<String> {
+    let mut candidates = vec![language.to_string(), language.replace('-', "_")];
+    let base = language.split(['-', '_']).next().unwrap_or(language);
+    candidates.push(base.to_string());
+    if let Some((_, legacy)) = LEGACY_LPROJ_NAMES.iter().find(|(code, _)| *code == base) {
+        candidates.push(legacy.to_string());
+    }
+    candidates.dedup();
+    candidates
+}
+
+pub(crate) fn parse_apple_languages(output: &str) -> Vec<String> {
+    plist::Value::from_reader_ascii(output.trim().as_bytes())
+        .ok()
+        .and_then(plist::Value::into_array)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(plist::Value::into_string)
+        .map(|language| language.trim().to_string())
+        .filter(|language| !language.is_empty())
+        .collect()
+}
+
+pub(crate) fn preferred_languages() -> &'static [String] {
+    static LANGUAGES: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
+    LANGUAGES.get_or_init(|| {
+        let mut languages = std::process::Command::new("defaults")
+            .args(["read", "-g", "AppleLanguages"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| parse_apple_languages(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default();
+        if languages.is_empty() {
+            if let Ok(lang) = std::env::var("LANG") {
+                let code = lang.split('.').next().unwrap_or_default();
+                if !code.is_empty() && code != "C" && code != "POSIX" {
+                    languages.push(code.to_string());
+                }
+            }
+        }
+        languages.push("en".to_string());
+        languages
+    })
+}
+
+pub(crate) fn localized_name(app_path: &Path, languages: &[String]) -> Option<String> {
+    let resources_dir = app_path.join("Contents").join("Resources");
+    for language in languages {
+        for candidate in lproj_candidates(language) {
+            let strings_path = resources_dir
+                .join(format!("{}.lproj", candidate))
+                .join("InfoPlist.strings");
+            let Ok(bytes) = std::fs::read(&strings_path) else {
+                continue;
+            };
+            let Some(dict) = parse_strings_file(&bytes) else {
+                continue;
+            };
+            if let Some(name) = string_value(&dict, "CFBundleDisplayName")
+                .or_else(|| string_value(&dict, "CFBundleName"))
+            {
+                return Some(name);
+            }
+        }
+    }
+    None
+}
+
+pub(crate) fn bundle_mtime(app_path: &Path) -> Option<u64> {
+    let info_plist = app_path.join("Contents").join("Info.plist");
+    let modified = std::fs::metadata(&info_plist)
+        .or_else(|_| std::fs::metadata(app_path))
+        .and_then(|metadata| metadata.modified())
+        .ok()?;
+    modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
+
+pub(crate) fn bundle_file_stem(app_path: &Path) -> String {
+    app_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    pub(crate) fn temp_dir(prefix: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    pub(crate) fn make_bundle(path: &Path, entries: &[(&str, &str)]) {
+        let contents = path.join("Contents");
+        fs::create_dir_all(&contents).unwrap();
+        let mut dict = plist::Dictionary::new();
+        for (key, value) in entries {
+            dict.insert(key.to_string(), plist::Value::String(value.to_string()));
+        }
+        plist::Value::Dictionary(dict)
+            .to_file_xml(contents.join("Info.plist"))
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{make_bundle, temp_dir};
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn read_bundle_info_reads_known_keys() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Notes.app");
+        make_bundle(
+            &app,
+            &[
+                ("CFBundleIdentifier", "com.example.notes"),
+                ("CFBundleName", "Notes"),
+                ("CFBundleDisplayName", "My Notes"),
+                ("CFBundleShortVersionString", "4.2"),
+                (
+                    "LSApplicationCategoryType",
+                    "public.app-category.productivity",
+                ),
+                ("CFBundleIconFile", "AppIcon"),
+            ],
+        );
+
+        let info = read_bundle_info(&app).unwrap();
+        assert_eq!(info.bundle_id.as_deref(), Some("com.example.notes"));
+        assert_eq!(info.name.as_deref(), Some("My Notes"));
+        assert_eq!(info.version.as_deref(), Some("4.2"));
+        assert_eq!(
+            info.category.as_deref(),
+            Some("public.app-category.productivity")
+        );
+        assert_eq!(info.icon_file.as_deref(), Some("AppIcon"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_bundle_info_falls_back_to_bundle_name_and_version() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Tool.app");
+        make_bundle(
+            &app,
+            &[
+                ("CFBundleName", "Tool"),
+                ("CFBundleDisplayName", "  "),
+                ("CFBundleVersion", "17"),
+            ],
+        );
+
+        let info = read_bundle_info(&app).unwrap();
+        assert_eq!(info.name.as_deref(), Some("Tool"));
+        assert_eq!(info.version.as_deref(), Some("17"));
+        assert_eq!(info.bundle_id, None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_bundle_info_returns_none_without_plist() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Broken.app");
+        fs::create_dir_all(&app).unwrap();
+
+        assert_eq!(read_bundle_info(&app), None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_app_bundle_accepts_application_bundles() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Editor.app");
+        make_bundle(
+            &app,
+            &[
+                ("CFBundleIdentifier", "com.example.editor"),
+                ("CFBundlePackageType", "APPL"),
+            ],
+        );
+
+        let info = read_app_bundle(&app).unwrap();
+        assert_eq!(info.bundle_id.as_deref(), Some("com.example.editor"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_app_bundle_rejects_missing_paths() {
+        let path = Path::new("/Applications/DefinitelyMissing.app");
+        assert_eq!(
+            read_app_bundle(path),
+            Err(BundleError::NotFound(path.to_string_lossy().to_string()))
+        );
+    }
+
+    #[test]
+    fn read_app_bundle_rejects_non_app_paths() {
+        let root = temp_dir("dockswitcher-bundle");
+        let folder = root.join("Documents");
+        fs::create_dir_all(&folder).unwrap();
+        let file = root.join("Fake.app");
+        fs::write(&file, b"not a bundle").unwrap();
+
+        assert!(matches!(
+            read_app_bundle(&folder),
+            Err(BundleError::NotAnAppBundle(_))
+        ));
+        assert!(matches!(
+            read_app_bundle(&file),
+            Err(BundleError::NotAnAppBundle(_))
+        ));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_app_bundle_rejects_non_application_package_types() {
+        let root = temp_dir("dockswitcher-bundle");
+        let plugin = root.join("Plugin.app");
+        make_bundle(&plugin, &[("CFBundlePackageType", "BNDL")]);
+
+        assert!(matches!(
+            read_app_bundle(&plugin),
+            Err(BundleError::NotAnAppBundle(_))
+        ));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_app_bundle_requires_info_plist() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Empty.app");
+        fs::create_dir_all(&app).unwrap();
+
+        assert!(matches!(
+            read_app_bundle(&app),
+            Err(BundleError::MissingInfoPlist(_))
+        ));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    fn write_strings(app: &Path, lproj: &str, bytes: &[u8]) {
+        let dir = app.join("Contents").join("Resources").join(lproj);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("InfoPlist.strings"), bytes).unwrap();
+    }
+
+    #[test]
+    fn parse_strings_file_reads_text_format() {
+        let text = "/* Localized versions of Info.plist keys */\n\
+                    CFBundleName = \"Rechner\";\n\
+                    \"CFBundleDisplayName\" = \"Taschenrechner\";\n";
+        let dict = parse_strings_file(text.as_bytes()).unwrap();
+        assert_eq!(
+            dict.get("CFBundleDisplayName").and_then(|v| v.as_string()),
+            Some("Taschenrechner")
+        );
+        assert_eq!(
+            dict.get("CFBundleName").and_then(|v| v.as_string()),
+            Some("Rechner")
+        );
+    }
+
+    #[test]
+    fn parse_strings_file_reads_utf16_text() {
+        let text = "\"CFBundleDisplayName\" = \"Calculadora\";";
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in text.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        let dict = parse_strings_file(&bytes).unwrap();
+        assert_eq!(
+            dict.get("CFBundleDisplayName").and_then(|v| v.as_string()),
+            Some("Calculadora")
+        );
+    }
+
+    #[test]
+    fn parse_strings_file_reads_binary_plist() {
+        let mut dict = plist::Dictionary::new();
+        dict.insert(
+            "CFBundleDisplayName".to_string(),
+            plist::Value::String("Calculatrice".to_string()),
+        );
+        let mut bytes = Vec::new();
+        plist::Value::Dictionary(dict)
+            .to_writer_binary(&mut bytes)
+            .unwrap();
+
+        let parsed = parse_strings_file(&bytes).unwrap();
+        assert_eq!(
+            parsed
+                .get("CFBundleDisplayName")
+                .and_then(|v| v.as_string()),
+            Some("Calculatrice")
+        );
+    }
+
+    #[test]
+    fn parse_apple_languages_reads_defaults_array() {
+        let output = "(\n    \"es-MX\",\n    \"en-US\",\n    fr\n)\n";
+        assert_eq!(parse_apple_languages(output), vec!["es-MX", "en-US", "fr"]);
+        assert!(parse_apple_languages("").is_empty());
+    }
+
+    #[test]
+    fn localized_name_prefers_languages_in_order() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Calculator.app");
+        make_bundle(&app, &[("CFBundleName", "Calculator")]);
+        write_strings(&app, "de.lproj", b"CFBundleDisplayName = \"Rechner\";");
+        write_strings(
+            &app,
+            "es_MX.lproj",
+            b"CFBundleDisplayName = \"Calculadora\";",
+        );
+
+        let languages = vec!["es-MX".to_string(), "de".to_string()];
+        assert_eq!(
+            localized_name(&app, &languages).as_deref(),
+            Some("Calculadora")
+        );
+        let languages = vec!["de-AT".to_string()];
+        assert_eq!(localized_name(&app, &languages).as_deref(), Some("Rechner"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn localized_name_supports_legacy_lproj_names() {
+        let root = temp_dir("dockswitcher-bundle");
+        let app = root.join("Old.app");
+        make_bundle(&app, &[]);
+        write_strings(&app, "English.lproj", b"CFBundleName = \"Old Timer\";");
+
+        assert_eq!(
+            localized_name(&app, &["en-GB".to_string()]).as_deref(),
+            Some("Old Timer")
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,759 @@
+use crate::commands::dock::{apply_profile, restore_app_list, snapshot_dock};
+use crate::schedule::{parse_timezone, resolve_local};
+use crate::store::{AppEntry, CalendarRule, CalendarSession, Profile, Restore, Session, Store};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use regex::{Regex, RegexBuilder};
+use rrule::{RRule, Tz, Unvalidated};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone)]
+pub(crate) struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub start: DateTime<Tz>,
+    pub duration: Duration,
+    pub rrule: Option<String>,
+    pub exdates: Vec<DateTime<Tz>>,
+    /// Set on an event that overrides one occurrence of a recurring event.
+    pub recurrence_id: Option<DateTime<Utc>>,
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CalendarOccurrence {
+    pub uid: String,
+    pub summary: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl CalendarOccurrence {
+    fn key(&self) -> String {
+        format!("{}@{}", self.uid, self.start.to_rfc3339())
+    }
+}
+
+pub(crate) fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|_| format!("\"{}\" is not a valid regular expression", pattern))
+}
+
+pub(crate) fn validate_calendar_files(files: &[String]) -> Result<(), String> {
+    for file in files {
+        let path = Path::new(file);
+        if !path.is_absolute() {
+            return Err(format!("\"{}\" is not an absolute path", file));
+        }
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("ics"))
+        {
+            return Err(format!("{} is not an .ics calendar", file));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_calendar_rules(
+    rules: &[CalendarRule],
+    profiles: &[Profile],
+) -> Result<(), String> {
+    for rule in rules {
+        if !profiles.iter().any(|p| p.id == rule.profile_id) {
+            return Err("A calendar rule refers to a profile that no longer exists".to_string());
+        }
+        if rule.pattern.is_empty() {
+            return Err("A calendar rule needs a title pattern".to_string());
+        }
+        compile_pattern(&rule.pattern)?;
+    }
+    Ok(())
+}
+
+/// Joins folded content lines back together.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim_matches('"'))
+    }
+}
+
+fn parse_property(line: &str) -> Option<Property<'_>> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let mut head = line[..colon].split(';');
+    let name = head.next()?.trim().to_ascii_uppercase();
+    let params = head
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_uppercase(), value))
+        .collect();
+    Some(Property {
+        name,
+        params,
+        value: &line[colon + 1..],
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
+
+/// Parses a DATE or DATE-TIME value. Dates and floating times are read in the
+/// Mac's own zone, as are TZIDs that aren't IANA names.
+fn parse_date_time(value: &str, property: &Property) -> Option<(DateTime<Tz>, bool)> {
+    let value = value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        let at = resolve_local(&Tz::LOCAL, date.and_hms_opt(0, 0, 0)?)?;
+        return Some((at.with_timezone(&Tz::LOCAL), true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((Tz::UTC.from_utc_datetime(&naive), false));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let tz = property
+        .param("TZID")
+        .and_then(|tzid| parse_timezone(tzid.trim_start_matches('/')).ok())
+        .map(Tz::from)
+        .unwrap_or(Tz::LOCAL);
+    let at = resolve_local(&tz, naive)?;
+    Some((at.with_timezone(&tz), false))
+}
+
+/// Parses an ISO 8601 duration such as `PT1H30M` or `P1W`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            _ => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match (c, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    number
+        .is_empty()
+        .then_some(if negative { -total } else { total })
+}
+
+#[derive(Default)]
+struct EventFields {
+    uid: String,
+    summary: String,
+    start: Option<(DateTime<Tz>, bool)>,
+    end: Option<DateTime<Tz>>,
+    duration: Option<Duration>,
+    rrule: Option<String>,
+    exdates: Vec<DateTime<Tz>>,
+    recurrence_id: Option<DateTime<Utc>>,
+    cancelled: bool,
+}
+
+impl EventFields {
+    fn set(&mut self, property: &Property) {
+        let value = property.value;
+        match property.name.as_str() {
+            "UID" => self.uid = value.trim().to_string(),
+            "SUMMARY" => self.summary = unescape_text(value),
+            "DTSTART" => self.start = parse_date_time(value, property),
+            "DTEND" => self.end = parse_date_time(value, property).map(|(at, _)| at),
+            "DURATION" => self.duration = parse_duration(value),
+            "RRULE" => self.rrule = Some(value.trim().to_string()),
+            "EXDATE" => self.exdates.extend(
+                value
+                    .split(',')
+                    .filter_map(|date| parse_date_time(date, property))
+                    .map(|(at, _)| at),
+            ),
+            "RECURRENCE-ID" => {
+                self.recurrence_id =
+                    parse_date_time(value, property).map(|(at, _)| at.with_timezone(&Utc))
+            }
+            "STATUS" => self.cancelled = value.trim().eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+
+    fn into_event(self) -> Option<CalendarEvent> {
+        let (start, all_day) = self.start?;
+        let duration = match (self.end, self.duration) {
+            (Some(end), _) => end.signed_duration_since(start),
+            (None, Some(duration)) => duration,
+            (None, None) if all_day => Duration::days(1),
+            (None, None) => Duration::zero(),
+        };
+        Some(CalendarEvent {
+            uid: self.uid,
+            summary: self.summary,
+            start,
+            duration,
+            rrule: self.rrule,
+            exdates: self.exdates,
+            recurrence_id: self.recurrence_id,
+            cancelled: self.cancelled,
+        })
+    }
+}
+
+/// Reads the VEVENTs of an iCalendar file, skipping any it can't make sense of.
+pub(crate) fn parse_calendar(text: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<EventFields> = None;
+    let mut nested = 0;
+    for line in unfold(text) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        let is_event = property.value.trim().eq_ignore_ascii_case("VEVENT");
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", _) if is_event => {
+                current = Some(EventFields::default());
+                nested = 0;
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if is_event => {
+                events.extend(current.take().and_then(EventFields::into_event));
+            }
+            (_, Some(fields)) if nested == 0 => fields.set(&property),
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Start times of `event`'s occurrences that could still be running at `now`.
+fn occurrence_starts(event: &CalendarEvent, now: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let single = vec![event.start.with_timezone(&Utc)];
+    let Some(rrule) = event
+        .rrule
+        .as_ref()
+        .filter(|_| event.recurrence_id.is_none())
+    else {
+        return single;
+    };
+    let tz = event.start.timezone();
+    let set = match rrule
+        .parse::<RRule<Unvalidated>>()
+        .and_then(|rrule| rrule.build(event.start))
+    {
+        Ok(set) => set,
+        Err(e) => {
+            eprintln!("Ignoring recurrence of calendar event {}: {}", event.uid, e);
+            return single;
+        }
+    };
+    set.set_exdates(event.exdates.clone())
+        .after((now - event.duration).with_timezone(&tz))
+        .before(now.with_timezone(&tz))
+        .all(100)
+        .dates
+        .into_iter()
+        .map(|at| at.with_timezone(&Utc))
+        .collect()
+}
+
+/// Expands recurring events and returns the occurrences running at `now`.
+/// Occurrences that have their own RECURRENCE-ID event are replaced by it,
+/// or dropped when that event is cancelled.
+pub(crate) fn active_occurrences(
+    events: &[CalendarEvent],
+    now: DateTime<Utc>,
+) -> Vec<CalendarOccurrence> {
+    let overridden: HashSet<(&str, DateTime<Utc>)> = events
+        .iter()
+        .filter_map(|event| Some((event.uid.as_str(), event.recurrence_id?)))
+        .collect();
+    events
+        .iter()
+        .filter(|event| !event.cancelled && event.duration > Duration::zero())
+        .flat_map(|event| {
+            occurrence_starts(event, now)
+                .into_iter()
+                .filter(|start| {
+                    event.recurrence_id.is_some()
+                        || !overridden.contains(&(event.uid.as_str(), *start))
+                })
+                .map(|start| CalendarOccurrence {
+                    uid: event.uid.clone(),
+                    summary: event.summary.clone(),
+                    start,
+                    end: start + event.duration,
+                })
+        })
+        .filter(|occurrence| occurrence.start <= now && now < occurrence.end)
+        .collect()
+}
+
+/// The first enabled rule, in list order, with a running event whose title
+/// matches it. Among several matching events the one that started last wins.
+pub(crate) fn matching_event<'a>(
+    rules: &'a [CalendarRule],
+    occurrences: &'a [CalendarOccurrence],
+) -> Option<(&'a CalendarRule, &'a CalendarOccurrence)> {
+    rules.iter().filter(|rule| rule.enabled).find_map(|rule| {
+        let pattern = compile_pattern(&rule.pattern).ok()?;
+        occurrences
+            .iter()
+            .filter(|occurrence| pattern.is_match(&occurrence.summary))
+            .max_by_key(|occurrence| occurrence.start)
+            .map(|occurrence| (rule, occurrence))
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum CalendarTick {
+    Idle,
+    Changed,
+    Apply(String),
+    RestoreApps(Vec<AppEntry>),
+}
+
+/// Starts, switches or ends the calendar session in `store` for the events
+/// running now. When an event ends, the profile it replaced comes back, unless
+/// someone switched away from the calendar's profile in the meantime.
+pub(crate) fn calendar_tick(store: &mut Store, occurrences: &[CalendarOccurrence]) -> CalendarTick {
+    if store.data.automation_paused {
+        return CalendarTick::Idle;
+    }
+    let matched = matching_event(&store.data.calendar_rules, occurrences)
+        .map(|(rule, occurrence)| (rule.id.clone(), rule.profile_id.clone(), occurrence.key()));
+    let Some((rule_id, profile_id, event_key)) = matched else {
+        let Some(ended) = store.data.calendar_session.take() else {
+            return CalendarTick::Idle;
+        };
+        return match store.end_session(ended.session) {
+            Restore::Nothing => CalendarTick::Changed,
+            Restore::Profile(id) => CalendarTick::Apply(id),
+            Restore::Apps(apps) => CalendarTick::RestoreApps(apps),
+        };
+    };
+    let replacing = match store.data.calendar_session.take() {
+        Some(session) if session.event_key == event_key && session.rule_id == rule_id => {
+            store.data.calendar_session = Some(session);
+            return CalendarTick::Idle;
+        }
+        replacing => replacing.map(|replacing| replacing.session),
+    };
+    let already_active = store.data.active_profile_id.as_ref() == Some(&profile_id);
+    let session = store.start_session(Some(profile_id.clone()), replacing);
+    store.data.calendar_session = Some(CalendarSession {
+        event_key,
+        rule_id,
+        session,
+    });
+    if already_active {
+        CalendarTick::Changed
+    } else {
+        CalendarTick::Apply(profile_id)
+    }
+}
+
+/// Parsed calendars, re-read only when a file's modification time changes.
+#[derive(Default)]
+pub(crate) struct CalendarCache {
+    files: HashMap<PathBuf, (SystemTime, Vec<CalendarEvent>)>,
+}
+
+impl CalendarCache {
+    pub fn events(&mut self, files: &[String]) -> Vec<CalendarEvent> {
+        let paths: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+        self.files.retain(|path, _| paths.contains(path));
+        let mut events = Vec::new();
+        for path in paths {
+            let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                self.files.remove(&path);
+                continue;
+            };
+            let fresh = self
+                .files
+                .get(&path)
+                .is_some_and(|(cached, _)| *cached == modified);
+            if !fresh {
+                let parsed = match std::fs::read_to_string(&path) {
+                    Ok(text) => parse_calendar(&text),
+                    Err(e) => {
+                        eprintln!("Failed to read calendar {}: {}", path.display(), e);
+                        Vec::new()
+                    }
+                };
+                self.files.insert(path.clone(), (modified, parsed));
+            }
+            if let Some((_, cached)) = self.files.get(&path) {
+                events.extend(cached.iter().cloned());
+            }
+        }
+        events
+    }
+}
+
+fn calendar_session(store: &mut Store) -> Option<&mut Session> {
+    store
+        .data
+        .calendar_session
+        .as_mut()
+        .map(|calendar| &mut calendar.session)
+}
+
+/// Whether `tick` is about to replace a Dock that no profile can bring back.
+fn needs_dock_snapshot(store: &Store, tick: &CalendarTick) -> bool {
+    matches!(tick, CalendarTick::Apply(_))
+        && store
+            .data
+            .calendar_session
+            .as_ref()
+            .is_some_and(|calendar| calendar.session.needs_dock_snapshot())
+}
+
+pub(crate) fn run_calendar(app: &AppHandle, cache: &mut CalendarCache) {
+    let files = {
+        let state = app.state::<Mutex<Store>>();
+        let Ok(store) = state.lock() else {
+            return;
+        };
+        if store.data.calendar_rules.is_empty() && store.data.calendar_session.is_none() {
+            return;
+        }
+        store.data.calendar_files.clone()
+    };
+    let occurrences = active_occurrences(&cache.events(&files), Utc::now());
+    let (tick, snapshot) = {
+        let state = app.state::<Mutex<Store>>();
+        let Ok(mut store) = state.lock() else {
+            return;
+        };
+        let tick = calendar_tick(&mut store, &occurrences);
+        if tick != CalendarTick::Idle {
+            if let Err(e) = store.save() {
+                eprintln!("Failed to save calendar state: {}", e);
+            }
+        }
+        let snapshot = needs_dock_snapshot(&store, &tick);
+        (tick, snapshot)
+    };
+    if snapshot {
+        if let Err(e) = snapshot_dock(app, calendar_session) {
+            eprintln!("Failed to save the Dock before the calendar event: {}", e);
+        }
+    }
+    let result = match tick {
+        CalendarTick::Apply(id) => apply_profile(id, app.clone(), app.state::<Mutex<Store>>()),
+        CalendarTick::RestoreApps(apps) => restore_app_list(app, apps),
+        CalendarTick::Idle | CalendarTick::Changed => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to apply calendar profile: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::fixtures::{store_with_profiles, utc};
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VTIMEZONE\r\n\
+TZID:Europe/Madrid\r\n\
+END:VTIMEZONE\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+SUMMARY:Product demo\\, weekly\r\n\
+DTSTART;TZID=Europe/Madrid:20260302T100000\r\n\
+DTEND;TZID=Europe/Madrid:20260302T110000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+EXDATE;TZID=Europe/Madrid:20260309T100000\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+SUMMARY:Reminder\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+RECURRENCE-ID;TZID=Europe/Madrid:20260316T100000\r\n\
+SUMMARY:Product demo\\, weekly\r\n\
+DTSTART;TZID=Europe/Madrid:20260317T150000\r\n\
+DURATION:PT1H\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+RECURRENCE-ID;TZID=Europe/Madrid:20260323T100000\r\n\
+SUMMARY:Product demo\\, weekly\r\n\
+DTSTART;TZID=Europe/Madrid:20260323T100000\r\n\
+DURATION:PT1H\r\n\
+STATUS:CANCELLED\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:focus\r\n\
+SUMMARY:Focus time with a very long title that the calendar app folde\r\n\
+\x20d onto a second line\r\n\
+DTSTART:20260304T130000Z\r\n\
+DTEND:20260304T150000Z\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    fn running(at: &str) -> Vec<String> {
+        active_occurrences(&parse_calendar(CALENDAR), utc(at))
+            .into_iter()
+            .map(|occurrence| occurrence.key())
+            .collect()
+    }
+
+    fn calendar_rule(id: &str, pattern: &str, profile_id: &str) -> CalendarRule {
+        CalendarRule {
+            id: id.to_string(),
+            pattern: pattern.to_string(),
+            profile_id: profile_id.to_string(),
+            enabled: true,
+        }
+    }
+
+    fn occurrence(uid: &str, summary: &str, start: &str) -> CalendarOccurrence {
+        CalendarOccurrence {
+            uid: uid.to_string(),
+            summary: summary.to_string(),
+            start: utc(start),
+            end: utc(start) + Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn parse_calendar_unfolds_unescapes_and_skips_nested_components() {
+        let events = parse_calendar(CALENDAR);
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].summary, "Product demo, weekly");
+        assert_eq!(events[0].duration, Duration::hours(1));
+        assert_eq!(events[0].exdates.len(), 1);
+        assert!(events[2].cancelled);
+        assert_eq!(
+            events[3].summary,
+            "Focus time with a very long title that the calendar app folded onto a second line"
+        );
+        assert_eq!(
+            events[3].start.with_timezone(&Utc),
+            utc("2026-03-04T13:00:00Z")
+        );
+    }
+
+    #[test]
+    fn active_occurrences_follow_the_rule_across_daylight_saving() {
+        // 10:00 in Madrid is 09:00 UTC in winter and 08:00 UTC in summer time.
+        assert_eq!(
+            running("2026-03-02T09:30:00Z"),
+            vec!["standup@2026-03-02T09:00:00+00:00"]
+        );
+        assert_eq!(
+            running("2026-03-30T08:30:00Z"),
+            vec!["standup@2026-03-30T08:00:00+00:00"]
+        );
+        assert!(running("2026-03-30T09:00:00Z").is_empty());
+    }
+
+    #[test]
+    fn active_occurrences_honour_exdates_and_overrides() {
+        // Excluded by EXDATE.
+        assert!(running("2026-03-09T09:30:00Z").is_empty());
+        // Moved from Monday to Tuesday afternoon.
+        assert!(running("2026-03-16T09:30:00Z").is_empty());
+        assert_eq!(
+            running("2026-03-17T14:30:00Z"),
+            vec!["standup@2026-03-17T14:00:00+00:00"]
+        );
+        // Cancelled.
+        assert!(running("2026-03-23T09:30:00Z").is_empty());
+    }
+
+    #[test]
+    fn parse_duration_reads_weeks_days_and_times() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1W"), Some(Duration::weeks(1)));
+        assert_eq!(parse_duration("P1DT12H"), Some(Duration::hours(36)));
+        assert_eq!(parse_duration("-PT15M"), Some(Duration::minutes(-15)));
+        assert_eq!(parse_duration("PT1"), None);
+        assert_eq!(parse_duration("1H"), None);
+    }
+
+    #[test]
+    fn calendar_tick_applies_for_the_event_then_restores() {
+        let mut store = store_with_profiles(&["home", "presenting"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.calendar_rules = vec![calendar_rule("r1", "demo|presentation", "presenting")];
+        let demo = [occurrence("demo", "Quarterly DEMO", "2026-03-02T09:00:00Z")];
+
+        assert_eq!(
+            calendar_tick(&mut store, &demo),
+            CalendarTick::Apply("presenting".to_string())
+        );
+        store.data.active_profile_id = Some("presenting".to_string());
+        assert_eq!(calendar_tick(&mut store, &demo), CalendarTick::Idle);
+        assert_eq!(
+            store
+                .data
+                .calendar_session
+                .as_ref()
+                .and_then(|session| session.session.previous_profile_id.as_deref()),
+            Some("home")
+        );
+
+        assert_eq!(
+            calendar_tick(&mut store, &[]),
+            CalendarTick::Apply("home".to_string())
+        );
+        assert_eq!(store.data.calendar_session, None);
+        assert_eq!(calendar_tick(&mut store, &[]), CalendarTick::Idle);
+    }
+
+    #[test]
+    fn calendar_tick_restores_the_dock_snapshot_when_no_profile_was_active() {
+        let mut store = store_with_profiles(&["presenting"]);
+        store.data.calendar_rules = vec![calendar_rule("r1", "demo", "presenting")];
+        let demo = [occurrence("demo", "Demo", "2026-03-02T09:00:00Z")];
+
+        let tick = calendar_tick(&mut store, &demo);
+        assert_eq!(tick, CalendarTick::Apply("presenting".to_string()));
+        assert!(needs_dock_snapshot(&store, &tick));
+        let dock = vec![AppEntry {
+            name: "Finder".to_string(),
+            path: "/System/Library/CoreServices/Finder.app".to_string(),
+            icon: None,
+            bundle_id: None,
+            version: None,
+            kind: crate::store::DockItemKind::App,
+        }];
+        calendar_session(&mut store).unwrap().previous_apps = dock.clone();
+        store.data.active_profile_id = Some("presenting".to_string());
+
+        assert_eq!(
+            calendar_tick(&mut store, &[]),
+            CalendarTick::RestoreApps(dock)
+        );
+    }
+
+    #[test]
+    fn calendar_tick_leaves_a_manual_switch_alone() {
+        let mut store = store_with_profiles(&["home", "presenting", "writing"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.calendar_rules = vec![calendar_rule("r1", "demo", "presenting")];
+        let demo = [occurrence("demo", "Demo", "2026-03-02T09:00:00Z")];
+
+        calendar_tick(&mut store, &demo);
+        // Switched by hand during the event: neither reapplied nor restored.
+        store.data.active_profile_id = Some("writing".to_string());
+        assert_eq!(calendar_tick(&mut store, &demo), CalendarTick::Idle);
+        assert_eq!(calendar_tick(&mut store, &[]), CalendarTick::Changed);
+        assert_eq!(store.data.active_profile_id.as_deref(), Some("writing"));
+    }
+
+    #[test]
+    fn calendar_tick_keeps_the_original_profile_across_back_to_back_events() {
+        let mut store = store_with_profiles(&["home", "presenting", "focus"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.calendar_rules = vec![
+            calendar_rule("r1", "demo", "presenting"),
+            calendar_rule("r2", "focus", "focus"),
+        ];
+
+        calendar_tick(
+            &mut store,
+            &[occurrence("demo", "Demo", "2026-03-02T09:00:00Z")],
+        );
+        store.data.active_profile_id = Some("presenting".to_string());
+        assert_eq!(
+            calendar_tick(
+                &mut store,
+                &[occurrence("focus", "Focus", "2026-03-02T10:00:00Z")]
+            ),
+            CalendarTick::Apply("focus".to_string())
+        );
+        store.data.active_profile_id = Some("focus".to_string());
+        assert_eq!(
+            calendar_tick(&mut store, &[]),
+            CalendarTick::Apply("home".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_calendar_rules_checks_profiles_and_patterns() {
+        let store = store_with_profiles(&["presenting"]);
+        let valid = calendar_rule("r1", "demo|presentation", "presenting");
+        assert!(
+            validate_calendar_rules(std::slice::from_ref(&valid), &store.data.profiles).is_ok()
+        );
+
+        let gone = calendar_rule("r1", "demo", "gone");
+        assert!(validate_calendar_rules(&[gone], &store.data.profiles).is_err());
+
+        let broken = calendar_rule("r1", "demo(", "presenting");
+        assert!(validate_calendar_rules(&[broken], &store.data.profiles)
+            .unwrap_err()
+            .contains("regular expression"));
+
+        assert!(validate_calendar_files(&["/Users/me/work.ics".to_string()]).is_ok());
+        assert!(validate_calendar_files(&["work.ics".to_string()]).is_err());
+        assert!(validate_calendar_files(&["/Users/me/work.json".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,426 @@
+use crate::bundle::{
+    bundle_file_stem, bundle_mtime, read_app_bundle, read_bundle_info, BundleError, BundleInfo,
+};
+use crate::icon::icon_sources;
+use crate::resolver::{default_app_roots, find_app_bundles, AppResolver};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long a crawl of the app folders is reused before searches crawl again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogApp {
+    pub name: String,
+    pub path: String,
+    pub bundle_id: Option<String>,
+    pub version: Option<String>,
+    pub category: Option<String>,
+    pub icon_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct CachedApp {
+    mtime: u64,
+    app: CatalogApp,
+}
+
+pub struct AppCatalog {
+    roots: Vec<PathBuf>,
+    entries: HashMap<String, CachedApp>,
+    cache_path: Option<PathBuf>,
+    refreshed_at: Option<Instant>,
+}
+
+pub(crate) fn read_catalog_app(app_path: &Path) -> CatalogApp {
+    catalog_app(app_path, read_bundle_info(app_path).unwrap_or_default())
+}
+
+fn catalog_app(app_path: &Path, info: BundleInfo) -> CatalogApp {
+    CatalogApp {
+        name: info.name.unwrap_or_else(|| bundle_file_stem(app_path)),
+        path: app_path.to_string_lossy().to_string(),
+        icon_file: icon_sources(app_path)
+            .first()
+            .map(|path| path.to_string_lossy().to_string()),
+        bundle_id: info.bundle_id,
+        version: info.version,
+        category: info.category,
+    }
+}
+
+pub(crate) fn catalog_roots(extra_roots: &[String]) -> Vec<PathBuf> {
+    let mut roots = default_app_roots();
+    roots.extend(extra_roots.iter().map(PathBuf::from));
+    roots
+}
+
+fn search_rank(app: &CatalogApp, query: &str) -> Option<u8> {
+    let name = app.name.to_lowercase();
+    let bundle_id = app.bundle_id.as_deref().unwrap_or_default().to_lowercase();
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else if bundle_id.contains(query) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+impl AppCatalog {
+    pub fn new(roots: Vec<PathBuf>, cache_path: Option<PathBuf>) -> Self {
+        let entries = cache_path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            roots,
+            entries,
+            cache_path,
+            refreshed_at: None,
+        }
+    }
+
+    pub fn load(extra_roots: &[String]) -> Self {
+        Self::new(
+            catalog_roots(extra_roots),
+            Some(crate::store::Store::data_dir().join("catalog.json")),
+        )
+    }
+
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>) {
+        self.roots = roots;
+        self.refreshed_at = None;
+    }
+
+    pub fn refresh_if_stale(&mut self) {
+        if self
+            .refreshed_at
+            .is_none_or(|refreshed_at| refreshed_at.elapsed() >= REFRESH_INTERVAL)
+        {
+            self.refresh();
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        let bundles = find_app_bundles(&self.roots);
+        let mut entries = HashMap::with_capacity(bundles.len());
+        let mut changed = false;
+
+        for bundle in bundles {
+            let key = bundle.to_string_lossy().to_string();
+            if entries.contains_key(&key) {
+                continue;
+            }
+            let mtime = bundle_mtime(&bundle).unwrap_or(0);
+            let cached = match self.entries.remove(&key) {
+                Some(cached) if cached.mtime == mtime => cached,
+                _ => {
+                    changed = true;
+                    CachedApp {
+                        mtime,
+                        app: read_catalog_app(&bundle),
+                    }
+                }
+            };
+            entries.insert(key, cached);
+        }
+
+        changed |= !self.entries.is_empty();
+        self.entries = entries;
+        self.refreshed_at = Some(Instant::now());
+        if changed {
+            self.save();
+        }
+    }
+
+    /// Describes one app bundle, reading its Info.plist only when the cached
+    /// entry is missing or older than the bundle. Bundles outside the catalog
+    /// roots are read but not added to the catalog.
+    pub fn app(&mut self, app_path: &Path) -> Result<CatalogApp, BundleError> {
+        let key = app_path.to_string_lossy().to_string();
+        let mtime = bundle_mtime(app_path).unwrap_or(0);
+        if let Some(cached) = self
+            .entries
+            .get(&key)
+            .filter(|cached| cached.mtime == mtime)
+        {
+            return Ok(cached.app.clone());
+        }
+        let app = catalog_app(app_path, read_app_bundle(app_path)?);
+        if self.roots.iter().any(|root| app_path.starts_with(root)) {
+            self.entries.insert(
+                key,
+                CachedApp {
+                    mtime,
+                    app: app.clone(),
+                },
+            );
+            self.save();
+        }
+        Ok(app)
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.cache_path else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string(&self.entries) {
+            let _ = fs::write(path, json);
+        }
+    }
+
+    pub fn apps(&self) -> Vec<CatalogApp> {
+        let mut apps: Vec<CatalogApp> = self
+            .entries
+            .values()
+            .map(|cached| cached.app.clone())
+            .collect();
+        apps.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        apps
+    }
+
+    pub fn search(&self, query: &str) -> Vec<CatalogApp> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return self.apps();
+        }
+        let mut ranked: Vec<(u8, CatalogApp)> = self
+            .apps()
+            .into_iter()
+            .filter_map(|app| search_rank(&app, &query).map(|rank| (rank, app)))
+            .collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, app)| app).collect()
+    }
+
+    pub fn resolver(&self) -> AppResolver {
+        let by_root = |path: &str| {
+            self.roots
+                .iter()
+                .position(|root| Path::new(path).starts_with(root))
+                .unwrap_or(usize::MAX)
+        };
+        let mut apps = self.apps();
+        apps.sort_by_key(|app| by_root(&app.path));
+        AppResolver::from_entries(apps.into_iter().filter_map(|app| {
+            app.bundle_id
+                .map(|bundle_id| (bundle_id, PathBuf::from(app.path)))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::{make_bundle, temp_dir};
+
+    fn catalog_for(root: &Path) -> AppCatalog {
+        let mut catalog = AppCatalog::new(vec![root.to_path_buf()], None);
+        catalog.refresh();
+        catalog
+    }
+
+    #[test]
+    fn refresh_records_bundle_metadata() {
+        let root = temp_dir("dockswitcher-catalog");
+        let app = root.join("Notes.app");
+        make_bundle(
+            &app,
+            &[
+                ("CFBundleIdentifier", "com.example.notes"),
+                ("CFBundleName", "Notes"),
+                ("CFBundleShortVersionString", "1.0"),
+                (
+                    "LSApplicationCategoryType",
+                    "public.app-category.productivity",
+                ),
+            ],
+        );
+
+        let catalog = catalog_for(&root);
+        let apps = catalog.apps();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].name, "Notes");
+        assert_eq!(apps[0].bundle_id.as_deref(), Some("com.example.notes"));
+        assert_eq!(apps[0].version.as_deref(), Some("1.0"));
+        assert_eq!(
+            apps[0].category.as_deref(),
+            Some("public.app-category.productivity")
+        );
+        assert_eq!(apps[0].icon_file, None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn refresh_falls_back_to_file_stem_for_name() {
+        let root = temp_dir("dockswitcher-catalog");
+        make_bundle(&root.join("Nameless.app"), &[]);
+
+        let catalog = catalog_for(&root);
+        assert_eq!(catalog.apps()[0].name, "Nameless");
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn refresh_resolves_icon_file_reference() {
+        let root = temp_dir("dockswitcher-catalog");
+        let app = root.join("Iconic.app");
+        make_bundle(&app, &[("CFBundleIconFile", "Iconic")]);
+        let resources = app.join("Contents").join("Resources");
+        fs::create_dir_all(&resources).unwrap();
+        fs::write(resources.join("Iconic.icns"), b"icns").unwrap();
+
+        let catalog = catalog_for(&root);
+        assert_eq!(
+            catalog.apps()[0].icon_file,
+            Some(resources.join("Iconic.icns").to_string_lossy().to_string())
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn refresh_drops_removed_bundles() {
+        let root = temp_dir("dockswitcher-catalog");
+        make_bundle(&root.join("Gone.app"), &[("CFBundleName", "Gone")]);
+
+        let mut catalog = catalog_for(&root);
+        assert_eq!(catalog.apps().len(), 1);
+
+        fs::remove_dir_all(root.join("Gone.app")).unwrap();
+        catalog.refresh();
+        assert!(catalog.apps().is_empty());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn refresh_reuses_cached_entries_with_same_mtime() {
+        let root = temp_dir("dockswitcher-catalog");
+        let cache_path = root.join("catalog.json");
+        let apps_root = root.join("Applications");
+        let app = apps_root.join("Cached.app");
+        make_bundle(&app, &[("CFBundleName", "Cached")]);
+
+        let mut catalog = AppCatalog::new(vec![apps_root.clone()], Some(cache_path.clone()));
+        catalog.refresh();
+        assert!(cache_path.exists());
+
+        let key = app.to_string_lossy().to_string();
+        let mut cached: HashMap<String, CachedApp> =
+            serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
+        cached.get_mut(&key).unwrap().app.name = "From Cache".to_string();
+        fs::write(&cache_path, serde_json::to_string(&cached).unwrap()).unwrap();
+
+        let mut reloaded = AppCatalog::new(vec![apps_root], Some(cache_path));
+        reloaded.refresh();
+        assert_eq!(reloaded.apps()[0].name, "From Cache");
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn app_caches_bundles_under_roots_only() {
+        let root = temp_dir("dockswitcher-catalog");
+        let apps_root = root.join("Applications");
+        let inside = apps_root.join("Inside.app");
+        let outside = root.join("Downloads").join("Outside.app");
+        make_bundle(&inside, &[("CFBundleName", "Inside")]);
+        make_bundle(&outside, &[("CFBundleIdentifier", "com.example.outside")]);
+
+        let mut catalog = AppCatalog::new(vec![apps_root], None);
+        assert_eq!(catalog.app(&inside).unwrap().name, "Inside");
+        let app = catalog.app(&outside).unwrap();
+        assert_eq!(app.bundle_id.as_deref(), Some("com.example.outside"));
+        assert_eq!(app.name, "Outside");
+        let listed: Vec<String> = catalog.apps().into_iter().map(|app| app.name).collect();
+        assert_eq!(listed, vec!["Inside"]);
+        assert!(matches!(
+            catalog.app(&root.join("Missing.app")),
+            Err(BundleError::NotFound(_))
+        ));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn search_ranks_name_matches_before_bundle_id_matches() {
+        let root = temp_dir("dockswitcher-catalog");
+        make_bundle(
+            &root.join("Mailer.app"),
+            &[
+                ("CFBundleName", "Mailer"),
+                ("CFBundleIdentifier", "com.example.mailer"),
+            ],
+        );
+        make_bundle(
+            &root.join("Mail.app"),
+            &[
+                ("CFBundleName", "Mail"),
+                ("CFBundleIdentifier", "com.apple.mail"),
+            ],
+        );
+        make_bundle(
+            &root.join("Post.app"),
+            &[
+                ("CFBundleName", "Post"),
+                ("CFBundleIdentifier", "org.mail.post"),
+            ],
+        );
+        make_bundle(&root.join("Notes.app"), &[("CFBundleName", "Notes")]);
+
+        let catalog = catalog_for(&root);
+        let names: Vec<String> = catalog
+            .search("Mail")
+            .into_iter()
+            .map(|app| app.name)
+            .collect();
+        assert_eq!(names, vec!["Mail", "Mailer", "Post"]);
+        assert_eq!(catalog.search("  ").len(), 4);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn resolver_prefers_earlier_roots() {
+        let first = temp_dir("dockswitcher-catalog");
+        let second = temp_dir("dockswitcher-catalog");
+        make_bundle(
+            &first.join("App.app"),
+            &[("CFBundleIdentifier", "com.example.app")],
+        );
+        make_bundle(
+            &second.join("App.app"),
+            &[("CFBundleIdentifier", "com.example.app")],
+        );
+
+        let mut catalog = AppCatalog::new(vec![second.clone(), first.clone()], None);
+        catalog.refresh();
+        assert_eq!(
+            catalog.resolver().resolve("com.example.app"),
+            Some(second.join("App.app").as_path())
+        );
+
+        fs::remove_dir_all(first).ok();
+        fs::remove_dir_all(second).ok();
+    }
+}
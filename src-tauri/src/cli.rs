@@ -0,0 +1,1074 @@
+use crate::catalog::AppCatalog;
+use crate::commands::dock::PREVIOUS_DOCK_NAME;
+use crate::control::{control_socket_path, send_notification, send_request};
+use crate::dock::{self, app_entry_from_bundle, find_duplicate_app, locate_dockutil};
+use crate::dockprofile::{
+    app_list_profile, bash_hook, claim_latest_report, directory_tick, fish_hook,
+    needs_dock_snapshot, read_dockprofile, zsh_hook, DirectoryTick, DIRECTORY_DEBOUNCE,
+    DOCKPROFILE_FILE,
+};
+use crate::icon_cache::{attach_cached_icons, IconCache};
+use crate::launcher::{alfred_script_filter, launcher_icon_dir, launcher_profiles, raycast_list};
+use crate::preview::{self, PreviewOptions};
+use crate::signals::{evaluate_rule, winning_rule, SignalProvider, SystemSignals};
+use crate::store::{AppEntry, ApplyResult, Profile, Store};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitCode {
+    Success = 0,
+    Failure = 1,
+    Usage = 2,
+    NotFound = 3,
+    ApplyFailed = 4,
+}
+
+#[derive(Debug)]
+struct CliError {
+    code: ExitCode,
+    message: String,
+}
+
+impl CliError {
+    fn new(code: ExitCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn failure(message: impl Into<String>) -> Self {
+        Self::new(ExitCode::Failure, message)
+    }
+}
+
+struct CliOutput {
+    json: serde_json::Value,
+    text: String,
+}
+
+impl CliOutput {
+    fn new(value: impl Serialize, text: impl Into<String>) -> Self {
+        Self {
+            json: serde_json::to_value(value).unwrap_or_default(),
+            text: text.into(),
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "dockswitcher",
+    version,
+    about = "Switch macOS Dock profiles from the command line"
+)]
+struct Cli {
+    /// Print machine-readable JSON
+    #[arg(long, global = true)]
+    json: bool,
+    /// Read and write this config.json instead of the app's
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List all profiles
+    List {
+        /// Print a launcher list instead: Alfred script filter or Raycast items
+        #[arg(long, value_enum)]
+        format: Option<LauncherFormat>,
+    },
+    /// Show the apps in a profile
+    Show { profile: String },
+    /// Replace the Dock with a profile
+    Apply { profile: String },
+    /// Save the current Dock to a profile, creating it if needed
+    Capture { name: String },
+    /// Add apps to a profile
+    AddApp {
+        profile: String,
+        #[arg(required = true)]
+        apps: Vec<PathBuf>,
+    },
+    /// Remove an app from a profile by name, path, bundle id or position
+    RemoveApp { profile: String, app: String },
+    /// Export a profile as JSON with a PNG preview next to it
+    Export { profile: String, path: PathBuf },
+    /// Import a profile exported from DockSwitcher
+    Import {
+        path: PathBuf,
+        /// Name for the imported profile
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Show the active profile
+    Current,
+    /// Explain which automation rules match right now, and why
+    Rules,
+    /// Print a shell hook that switches profiles from .dockprofile files
+    Hook { shell: HookShell },
+    /// Switch to the profile of the nearest .dockprofile above a folder
+    ReportDir { path: PathBuf },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LauncherFormat {
+    Alfred,
+    Raycast,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum HookShell {
+    Zsh,
+    Bash,
+    Fish,
+}
+
+#[derive(Serialize)]
+struct ProfileSummary<'a> {
+    id: &'a str,
+    name: &'a str,
+    app_count: usize,
+    active: bool,
+}
+
+/// Returns true when `args` (including the program name) ask for the CLI
+/// rather than the menu bar app.
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    args.get(1).is_some_and(|arg| {
+        Cli::command()
+            .get_subcommands()
+            .any(|subcommand| subcommand.get_name() == arg)
+            || matches!(
+                arg.as_str(),
+                "help" | "-h" | "--help" | "-V" | "--version" | "--json" | "--config"
+            )
+    })
+}
+
+pub fn run<I>(args: I, out: &mut dyn Write, err: &mut dyn Write) -> i32
+where
+    I: IntoIterator<Item = String>,
+{
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            let rendered = e.render().to_string();
+            if e.use_stderr() {
+                let _ = write!(err, "{}", rendered);
+                return ExitCode::Usage as i32;
+            }
+            let _ = write!(out, "{}", rendered);
+            return ExitCode::Success as i32;
+        }
+    };
+
+    let json = cli.json;
+    match execute(cli) {
+        Ok(output) => {
+            let _ = if json {
+                writeln!(
+                    out,
+                    "{}",
+                    serde_json::to_string_pretty(&output.json).unwrap_or_default()
+                )
+            } else if output.text.is_empty() {
+                Ok(())
+            } else {
+                writeln!(out, "{}", output.text)
+            };
+            ExitCode::Success as i32
+        }
+        Err(e) => {
+            let _ = if json {
+                writeln!(
+                    err,
+                    "{}",
+                    json!({ "error": e.message, "code": e.code as i32 })
+                )
+            } else {
+                writeln!(err, "dockswitcher: {}", e.message)
+            };
+            e.code as i32
+        }
+    }
+}
+
+/// Returns the control socket when the menu bar app is running. The app keeps
+/// its own copy of config.json, so changes go through it instead of the file.
+fn running_app(custom_config: bool) -> Option<PathBuf> {
+    let socket = control_socket_path();
+    (!custom_config && UnixStream::connect(&socket).is_ok()).then_some(socket)
+}
+
+fn request<T: DeserializeOwned>(
+    socket: &Path,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T, String> {
+    let result = send_request(socket, method, params)
+        .map_err(|e| format!("Failed to reach DockSwitcher: {}", e))??;
+    serde_json::from_value(result).map_err(|e| e.to_string())
+}
+
+fn execute(cli: Cli) -> Result<CliOutput, CliError> {
+    let custom_config = cli.config.is_some();
+    let app = running_app(custom_config);
+    let app = app.as_deref();
+    let config = cli.config.unwrap_or_else(Store::config_path);
+    let mut store = Store::load_from(config.clone())
+        .map_err(|e| CliError::failure(format!("Failed to read config: {}", e)))?;
+
+    match cli.command {
+        Command::List { format: None } => Ok(list_profiles(&store)),
+        Command::List {
+            format: Some(format),
+        } => launcher_list(&store, &launcher_icon_dir(), format),
+        Command::Show { profile } => {
+            let profile = &store.data.profiles[find_profile(&store, &profile)?];
+            Ok(CliOutput::new(profile, describe_profile(profile)))
+        }
+        Command::Apply { profile } => apply(&mut store, &profile, app),
+        Command::Capture { name } => capture(&mut store, &name, app),
+        Command::AddApp { profile, apps } => add_apps(&mut store, &profile, &apps, app),
+        Command::RemoveApp {
+            profile,
+            app: entry,
+        } => remove_app(&mut store, &profile, &entry, app),
+        Command::Export { profile, path } => {
+            let profile = &store.data.profiles[find_profile(&store, &profile)?];
+            let preview_path = preview::export_profile(profile, &path, &PreviewOptions::default())
+                .map_err(CliError::failure)?;
+            Ok(CliOutput::new(
+                json!({ "path": path, "preview_path": preview_path }),
+                format!(
+                    "Exported \"{}\" to {} and {}",
+                    profile.name,
+                    path.display(),
+                    preview_path.display()
+                ),
+            ))
+        }
+        Command::Import { path, name } => import(&mut store, &path, name, app),
+        Command::Rules => Ok(explain_rules(&store, &SystemSignals)),
+        Command::Hook { shell } => {
+            let program = std::env::current_exe().map_err(|e| CliError::failure(e.to_string()))?;
+            let script = match shell {
+                HookShell::Zsh => zsh_hook(&program),
+                HookShell::Bash => bash_hook(&program),
+                HookShell::Fish => fish_hook(&program),
+            };
+            Ok(CliOutput::new(
+                json!({ "script": script }),
+                script.trim_end().to_string(),
+            ))
+        }
+        Command::ReportDir { path } => {
+            let path = std::path::absolute(&path).map_err(|e| CliError::failure(e.to_string()))?;
+            // A running app debounces and applies the change itself.
+            if let Some(socket) = app {
+                if send_notification(socket, "report_directory", json!({ "path": path })).is_ok() {
+                    return Ok(CliOutput::new(json!({ "reported": true }), String::new()));
+                }
+            }
+            // Otherwise only the newest of several quick reports switches,
+            // against a config.json reread once it holds the lock.
+            let state_dir = config.parent().unwrap_or(Path::new("."));
+            let Some(_lock) =
+                claim_latest_report(state_dir, DIRECTORY_DEBOUNCE).map_err(CliError::failure)?
+            else {
+                return Ok(CliOutput::new(json!({ "changed": false }), String::new()));
+            };
+            let mut store = Store::load_from(config.clone())
+                .map_err(|e| CliError::failure(format!("Failed to read config: {}", e)))?;
+            report_dir(&mut store, &path)
+        }
+        Command::Current => {
+            let profile = store
+                .active_profile()
+                .ok_or_else(|| CliError::new(ExitCode::NotFound, "No profile is active"))?;
+            Ok(CliOutput::new(
+                json!({ "id": profile.id, "name": profile.name }),
+                profile.name.clone(),
+            ))
+        }
+    }
+}
+
+fn save(store: &Store) -> Result<(), CliError> {
+    store
+        .save()
+        .map_err(|e| CliError::failure(format!("Failed to save config: {}", e)))
+}
+
+fn find_profile(store: &Store, query: &str) -> Result<usize, CliError> {
+    store
+        .find_profile_index(query)
+        .map_err(|e| CliError::new(ExitCode::NotFound, e))
+}
+
+fn launcher_list(
+    store: &Store,
+    icon_dir: &Path,
+    format: LauncherFormat,
+) -> Result<CliOutput, CliError> {
+    let profiles = launcher_profiles(store, &IconCache::shared(), icon_dir);
+    let value = match format {
+        LauncherFormat::Alfred => serde_json::to_value(alfred_script_filter(&profiles)),
+        LauncherFormat::Raycast => {
+            let program = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("dockswitcher"));
+            serde_json::to_value(raycast_list(&profiles, &program))
+        }
+    }
+    .map_err(|e| CliError::failure(e.to_string()))?;
+    // Launchers read stdout as JSON, so the output is the same with or without --json.
+    let text = value.to_string();
+    Ok(CliOutput::new(value, text))
+}
+
+fn list_profiles(store: &Store) -> CliOutput {
+    let active_id = store.data.active_profile_id.as_deref();
+    let summaries: Vec<ProfileSummary> = store
+        .data
+        .profiles
+        .iter()
+        .map(|p| ProfileSummary {
+            id: &p.id,
+            name: &p.name,
+            app_count: p.apps.iter().filter(|entry| entry.kind.is_app()).count(),
+            active: Some(p.id.as_str()) == active_id,
+        })
+        .collect();
+    let text = summaries
+        .iter()
+        .map(|s| {
+            format!(
+                "{} {} ({} app{})",
+                if s.active { "*" } else { " " },
+                s.name,
+                s.app_count,
+                if s.app_count == 1 { "" } else { "s" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    CliOutput::new(&summaries, text)
+}
+
+fn explain_rules(store: &Store, provider: &dyn SignalProvider) -> CliOutput {
+    let evaluations: Vec<_> = store
+        .data
+        .signal_rules
+        .iter()
+        .map(|rule| evaluate_rule(rule, provider))
+        .collect();
+    let winner = winning_rule(&store.data.signal_rules, provider).map(|(rule, _)| rule.id.clone());
+    let mut text = Vec::new();
+    for (rule, evaluation) in store.data.signal_rules.iter().zip(&evaluations) {
+        let profile = store
+            .data
+            .profiles
+            .iter()
+            .find(|p| p.id == rule.profile_id)
+            .map_or(rule.profile_id.as_str(), |p| p.name.as_str());
+        text.push(format!(
+            "{} {} -> {} (priority {}){}",
+            if winner.as_ref() == Some(&rule.id) {
+                "*"
+            } else {
+                " "
+            },
+            rule.name,
+            profile,
+            rule.priority,
+            if rule.enabled { "" } else { ", disabled" }
+        ));
+        for condition in &evaluation.conditions {
+            text.push(format!(
+                "    [{}] {}",
+                if condition.met { "x" } else { " " },
+                condition.description
+            ));
+        }
+    }
+    if text.is_empty() {
+        text.push("No rules".to_string());
+    }
+    if let Some(firing) = &store.data.last_signal_firing {
+        text.push(format!(
+            "Last fired: {} at {} because {}",
+            firing.rule_name,
+            firing.at,
+            firing.reasons.join(" and ")
+        ));
+    }
+    CliOutput::new(
+        json!({
+            "rules": evaluations,
+            "winner": winner,
+            "last_firing": store.data.last_signal_firing,
+        }),
+        text.join("\n"),
+    )
+}
+
+fn describe_entry(entry: &AppEntry) -> String {
+    match dock::dockutil_spacer_type(entry.kind) {
+        Some(tile_type) => format!("({})", tile_type),
+        None => format!("{}  {}", entry.name, entry.path),
+    }
+}
+
+fn describe_profile(profile: &Profile) -> String {
+    let mut lines = vec![format!("{} ({})", profile.name, profile.id)];
+    lines.extend(
+        profile
+            .apps
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| format!("{:>3}. {}", index + 1, describe_entry(entry))),
+    );
+    lines.join("\n")
+}
+
+fn apply(store: &mut Store, query: &str, app: Option<&Path>) -> Result<CliOutput, CliError> {
+    let index = find_profile(store, query)?;
+    let profile = store.data.profiles[index].clone();
+    if let Some(socket) = app {
+        request::<serde_json::Value>(socket, "apply_profile", json!({ "id": profile.id }))
+            .map_err(|e| CliError::new(ExitCode::ApplyFailed, e))?;
+        let warnings = Store::load_from(store.path().to_path_buf())
+            .ok()
+            .and_then(|store| store.data.last_apply)
+            .filter(|result| result.profile_id == profile.id)
+            .map(|result| result.messages)
+            .unwrap_or_default();
+        return Ok(applied(&profile, &warnings));
+    }
+    let dockutil = locate_dockutil().map_err(|e| CliError::new(ExitCode::ApplyFailed, e))?;
+
+    let mut resolver = None;
+    let result = dock::apply_dock_profile(
+        &dockutil,
+        &profile,
+        |bundle_id| {
+            resolver
+                .get_or_insert_with(|| {
+                    let mut catalog = AppCatalog::load(&store.data.settings.app_roots);
+                    catalog.refresh();
+                    catalog.resolver()
+                })
+                .resolve(bundle_id)
+                .map(|path| path.to_string_lossy().to_string())
+        },
+        |_| {},
+    );
+
+    let warnings = match &result {
+        Ok(outcome) => {
+            if store.data.settings.update_moved_app_paths {
+                dock::update_relocated_paths(&mut store.data.profiles[index], &outcome.relocated);
+            }
+            Ok(outcome.warnings.clone())
+        }
+        Err(e) => Err(e.clone()),
+    };
+    store.record_apply(ApplyResult::new(
+        profile.id.clone(),
+        profile.name.clone(),
+        &warnings,
+    ));
+    save(store)?;
+
+    let warnings = warnings.map_err(|e| CliError::new(ExitCode::ApplyFailed, e))?;
+    Ok(applied(&profile, &warnings))
+}
+
+fn applied(profile: &Profile, warnings: &[String]) -> CliOutput {
+    let mut text = vec![format!("Applied \"{}\"", profile.name)];
+    text.extend(warnings.iter().map(|w| format!("warning: {}", w)));
+    CliOutput::new(
+        json!({ "id": profile.id, "name": profile.name, "warnings": warnings }),
+        text.join("\n"),
+    )
+}
+
+fn report_dir(store: &mut Store, dir: &Path) -> Result<CliOutput, CliError> {
+    let found = read_dockprofile(dir)
+        .transpose()
+        .map_err(CliError::failure)?;
+    let tick = directory_tick(store, found).map_err(|e| CliError::new(ExitCode::NotFound, e))?;
+    if needs_dock_snapshot(store, &tick) {
+        let dockutil = locate_dockutil().map_err(CliError::failure)?;
+        let mut catalog = AppCatalog::load(&store.data.settings.app_roots);
+        let apps = dock::list_dock_apps(&dockutil, &mut catalog).map_err(CliError::failure)?;
+        if let Some(directory) = store.data.directory_session.as_mut() {
+            directory.session.previous_apps = apps;
+        }
+    }
+    match tick {
+        DirectoryTick::Idle => Ok(CliOutput::new(json!({ "changed": false }), String::new())),
+        DirectoryTick::Changed => {
+            save(store)?;
+            Ok(CliOutput::new(json!({ "changed": true }), String::new()))
+        }
+        DirectoryTick::Apply(id) => {
+            save(store)?;
+            apply(store, &id, None)
+        }
+        DirectoryTick::ApplyApps(root, lines) => {
+            save(store)?;
+            let mut catalog = AppCatalog::load(&store.data.settings.app_roots);
+            catalog.refresh();
+            let profile = app_list_profile(&root, &lines, &mut catalog)
+                .map_err(|e| CliError::new(ExitCode::NotFound, e))?;
+            let warnings = apply_app_list(store, &profile, &catalog)?;
+
+            let mut text = vec![format!(
+                "Applied the apps in {}",
+                root.join(DOCKPROFILE_FILE).display()
+            )];
+            text.extend(warnings.iter().map(|w| format!("warning: {}", w)));
+            Ok(CliOutput::new(
+                json!({ "root": root, "warnings": warnings }),
+                text.join("\n"),
+            ))
+        }
+        DirectoryTick::RestoreApps(apps) => {
+            save(store)?;
+            let mut catalog = AppCatalog::load(&store.data.settings.app_roots);
+            catalog.refresh();
+            let mut profile = Profile::new(PREVIOUS_DOCK_NAME.to_string());
+            profile.apps = apps;
+            let warnings = apply_app_list(store, &profile, &catalog)?;
+
+            let mut text = vec!["Restored the previous Dock".to_string()];
+            text.extend(warnings.iter().map(|w| format!("warning: {}", w)));
+            Ok(CliOutput::new(
+                json!({ "warnings": warnings }),
+                text.join("\n"),
+            ))
+        }
+    }
+}
+
+/// Replaces the Dock with apps that don't belong to a saved profile, leaving
+/// no profile active.
+fn apply_app_list(
+    store: &mut Store,
+    profile: &Profile,
+    catalog: &AppCatalog,
+) -> Result<Vec<String>, CliError> {
+    let dockutil = locate_dockutil().map_err(|e| CliError::new(ExitCode::ApplyFailed, e))?;
+    let resolver = catalog.resolver();
+    let outcome = dock::apply_dock_profile(
+        &dockutil,
+        profile,
+        |bundle_id| {
+            resolver
+                .resolve(bundle_id)
+                .map(|path| path.to_string_lossy().to_string())
+        },
+        |_| {},
+    )
+    .map_err(|e| CliError::new(ExitCode::ApplyFailed, e))?;
+    store.data.active_profile_id = None;
+    save(store)?;
+    Ok(outcome.warnings)
+}
+
+fn capture(store: &mut Store, name: &str, app: Option<&Path>) -> Result<CliOutput, CliError> {
+    let existing = find_profile(store, name).ok();
+    let (id, name, count, created) = match app {
+        Some(socket) => {
+            let (id, name, created) = match existing {
+                Some(index) => {
+                    let profile = &store.data.profiles[index];
+                    (profile.id.clone(), profile.name.clone(), false)
+                }
+                None => {
+                    let profile: Profile =
+                        request(socket, "create_profile", json!({ "name": name }))
+                            .map_err(CliError::failure)?;
+                    (profile.id, profile.name, true)
+                }
+            };
+            let apps: Vec<AppEntry> =
+                request(socket, "save_dock_to_profile", json!({ "profile_id": id }))
+                    .map_err(CliError::failure)?;
+            (id, name, apps.len(), created)
+        }
+        None => {
+            let dockutil = locate_dockutil().map_err(CliError::failure)?;
+            let mut catalog = AppCatalog::load(&store.data.settings.app_roots);
+            let mut apps =
+                dock::list_dock_apps(&dockutil, &mut catalog).map_err(CliError::failure)?;
+            attach_cached_icons(&mut apps, &IconCache::shared());
+
+            let (index, created) = match existing {
+                Some(index) => (index, false),
+                None => {
+                    store.data.profiles.push(Profile::new(name.to_string()));
+                    (store.data.profiles.len() - 1, true)
+                }
+            };
+            let profile = &mut store.data.profiles[index];
+            profile.apps = apps;
+            profile.updated_at = chrono::Utc::now().to_rfc3339();
+            let captured = (
+                profile.id.clone(),
+                profile.name.clone(),
+                profile.apps.len(),
+                created,
+            );
+            save(store)?;
+            captured
+        }
+    };
+
+    Ok(CliOutput::new(
+        json!({ "id": id, "name": name, "app_count": count, "created": created }),
+        format!(
+            "{} \"{}\" with {} Dock item{}",
+            if created { "Created" } else { "Updated" },
+            name,
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+    ))
+}
+
+fn add_apps(
+    store: &mut Store,
+    query: &str,
+    paths: &[PathBuf],
+    app: Option<&Path>,
+) -> Result<CliOutput, CliError> {
+    let index = find_profile(store, query)?;
+    let mut catalog = AppCatalog::load(&store.data.settings.app_roots);
+    let entries = paths
+        .iter()
+        .map(|path| app_entry_from_bundle(&mut catalog, &path.to_string_lossy()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CliError::failure(e.to_string()))?;
+
+    let profile = &mut store.data.profiles[index];
+    let mut added = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in entries {
+        match find_duplicate_app(&profile.apps, &entry) {
+            Some(existing) => skipped.push(existing.name.clone()),
+            None => {
+                profile.apps.push(entry.clone());
+                added.push(entry);
+            }
+        }
+    }
+    if !added.is_empty() {
+        profile.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+    let (id, name) = (profile.id.clone(), profile.name.clone());
+    match app {
+        Some(socket) => {
+            for entry in &added {
+                request::<AppEntry>(
+                    socket,
+                    "add_app_to_profile",
+                    json!({ "profile_id": id, "app_path": entry.path }),
+                )
+                .map_err(CliError::failure)?;
+            }
+        }
+        None => save(store)?,
+    }
+
+    let mut text: Vec<String> = added
+        .iter()
+        .map(|entry| format!("Added {} to \"{}\"", entry.name, name))
+        .collect();
+    text.extend(
+        skipped
+            .iter()
+            .map(|app| format!("{} is already in \"{}\"", app, name)),
+    );
+    Ok(CliOutput::new(
+        json!({ "added": added, "skipped": skipped }),
+        text.join("\n"),
+    ))
+}
+
+fn find_app(apps: &[AppEntry], query: &str) -> Option<usize> {
+    if let Ok(position) = query.parse::<usize>() {
+        return (1..=apps.len()).contains(&position).then(|| position - 1);
+    }
+    apps.iter()
+        .position(|entry| entry.path == query || entry.bundle_id.as_deref() == Some(query))
+        .or_else(|| {
+            apps.iter()
+                .position(|entry| entry.kind.is_app() && entry.name.eq_ignore_ascii_case(query))
+        })
+}
+
+fn remove_app(
+    store: &mut Store,
+    query: &str,
+    entry: &str,
+    app: Option<&Path>,
+) -> Result<CliOutput, CliError> {
+    let index = find_profile(store, query)?;
+    let profile = &mut store.data.profiles[index];
+    let position = find_app(&profile.apps, entry).ok_or_else(|| {
+        CliError::new(
+            ExitCode::NotFound,
+            format!("\"{}\" is not in \"{}\"", entry, profile.name),
+        )
+    })?;
+    let removed = profile.apps.remove(position);
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    let text = format!(
+        "Removed {} from \"{}\"",
+        describe_entry(&removed),
+        profile.name
+    );
+    match app {
+        Some(socket) => request::<()>(
+            socket,
+            "remove_app_from_profile",
+            json!({ "profile_id": profile.id, "app_index": position, "expected": removed }),
+        )
+        .map_err(CliError::failure)?,
+        None => save(store)?,
+    }
+    Ok(CliOutput::new(&removed, text))
+}
+
+fn import(
+    store: &mut Store,
+    path: &Path,
+    name: Option<String>,
+    app: Option<&Path>,
+) -> Result<CliOutput, CliError> {
+    let (id, name, warning) = match app {
+        Some(socket) => {
+            let path = std::path::absolute(path).map_err(|e| CliError::failure(e.to_string()))?;
+            let imported: serde_json::Value = request(
+                socket,
+                "import_profile",
+                json!({ "path": path, "name": name }),
+            )
+            .map_err(CliError::failure)?;
+            let field = |key: &str| imported[key].as_str().map(str::to_string);
+            (
+                field("id").unwrap_or_default(),
+                field("name").unwrap_or_default(),
+                field("warning"),
+            )
+        }
+        None => {
+            let (profile, warning) = store
+                .import_profile(path, name)
+                .map_err(CliError::failure)?;
+            let imported = (profile.id.clone(), profile.name.clone(), warning);
+            store.data.profiles.push(profile);
+            save(store)?;
+            imported
+        }
+    };
+
+    let mut text = vec![format!("Imported \"{}\"", name)];
+    if let Some(warning) = warning {
+        text.push(format!("warning: {}", warning));
+    }
+    Ok(CliOutput::new(
+        json!({ "id": id, "name": name }),
+        text.join("\n"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::temp_dir;
+    use crate::store::{DockItemKind, Signal, SignalCondition, SignalRule};
+
+    fn entry(name: &str, bundle_id: &str) -> AppEntry {
+        AppEntry {
+            name: name.to_string(),
+            path: format!("/Applications/{}.app", name),
+            icon: None,
+            bundle_id: Some(bundle_id.to_string()),
+            version: None,
+            kind: DockItemKind::App,
+        }
+    }
+
+    fn config_with_profiles() -> PathBuf {
+        let config = temp_dir("dockswitcher-cli").join("config.json");
+        let mut store = Store::with_path(config.clone());
+        let mut work = Profile::new("Work".to_string());
+        work.id = "work-id".to_string();
+        work.apps = vec![
+            entry("Safari", "com.apple.Safari"),
+            entry("Slack", "com.tinyspeck.slackmacgap"),
+        ];
+        let mut home = Profile::new("Home".to_string());
+        home.id = "home-id".to_string();
+        home.apps = vec![entry("Music", "com.apple.Music")];
+        store.data.profiles = vec![work, home];
+        store.data.active_profile_id = Some("work-id".to_string());
+        store.save().unwrap();
+        config
+    }
+
+    fn run_cli(config: &Path, args: &[&str]) -> (i32, String, String) {
+        let mut argv = vec!["dockswitcher".to_string()];
+        argv.extend(args.iter().map(|arg| arg.to_string()));
+        argv.push("--config".to_string());
+        argv.push(config.to_string_lossy().to_string());
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let code = run(argv, &mut out, &mut err);
+        (
+            code,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+        )
+    }
+
+    #[test]
+    fn is_cli_invocation_only_for_known_subcommands_and_flags() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert!(is_cli_invocation(&args(&["dockswitcher", "list"])));
+        assert!(is_cli_invocation(&args(&[
+            "dockswitcher",
+            "--json",
+            "current"
+        ])));
+        assert!(is_cli_invocation(&args(&["dockswitcher", "--help"])));
+        assert!(!is_cli_invocation(&args(&["dockswitcher"])));
+        assert!(!is_cli_invocation(&args(&["dockswitcher", "-psn_0_12345"])));
+    }
+
+    #[test]
+    fn every_subcommand_is_routed_to_the_cli() {
+        for name in ["report-dir", "add-app", "help"] {
+            let args = vec!["dockswitcher".to_string(), name.to_string()];
+            assert!(
+                is_cli_invocation(&args),
+                "{} is not routed to the CLI",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn list_prints_profiles_as_text_and_json() {
+        let config = config_with_profiles();
+
+        let (code, out, _) = run_cli(&config, &["list"]);
+        assert_eq!(code, 0);
+        assert_eq!(out, "* Work (2 apps)\n  Home (1 app)\n");
+
+        let (code, out, _) = run_cli(&config, &["list", "--json"]);
+        assert_eq!(code, 0);
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed[1]["name"], "Home");
+        assert_eq!(parsed[0]["active"], true);
+    }
+
+    #[test]
+    fn list_prints_launcher_formats_as_json() {
+        let config = config_with_profiles();
+
+        let (code, out, _) = run_cli(&config, &["list", "--format", "alfred"]);
+        assert_eq!(code, 0);
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["items"][0]["arg"], "work-id");
+        assert_eq!(parsed["items"][0]["subtitle"], "Active · 2 apps");
+
+        let (code, out, _) = run_cli(&config, &["--json", "list", "--format", "raycast"]);
+        assert_eq!(code, 0);
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["items"][1]["actions"][0]["command"][1], "apply");
+        assert_eq!(parsed["items"][1]["actions"][0]["command"][2], "home-id");
+
+        let (code, _, _) = run_cli(&config, &["list", "--format", "spotlight"]);
+        assert_eq!(code, ExitCode::Usage as i32);
+    }
+
+    #[test]
+    fn show_and_current_find_profiles_by_name_or_id() {
+        let config = config_with_profiles();
+
+        let (code, out, _) = run_cli(&config, &["show", "home"]);
+        assert_eq!(code, 0);
+        assert!(out.starts_with("Home (home-id)\n"));
+        assert!(out.contains("1. Music  /Applications/Music.app"));
+
+        let (code, out, _) = run_cli(&config, &["current", "--json"]);
+        assert_eq!(code, 0);
+        assert!(out.contains("\"work-id\""));
+
+        let (code, _, err) = run_cli(&config, &["show", "Travel"]);
+        assert_eq!(code, ExitCode::NotFound as i32);
+        assert!(err.contains("No profile named \"Travel\""));
+    }
+
+    #[test]
+    fn remove_app_accepts_position_bundle_id_and_name() {
+        let config = config_with_profiles();
+
+        let (code, out, _) = run_cli(
+            &config,
+            &["remove-app", "Work", "com.tinyspeck.slackmacgap"],
+        );
+        assert_eq!(code, 0);
+        assert!(out.contains("Removed Slack"));
+
+        let (code, _, _) = run_cli(&config, &["remove-app", "Work", "5"]);
+        assert_eq!(code, ExitCode::NotFound as i32);
+
+        let (code, _, _) = run_cli(&config, &["remove-app", "Work", "safari"]);
+        assert_eq!(code, 0);
+        let store = Store::load_from(config).unwrap();
+        assert!(store.data.profiles[0].apps.is_empty());
+    }
+
+    #[test]
+    fn export_then_import_creates_a_renamed_copy() {
+        let config = config_with_profiles();
+        let export = config.with_file_name("work.json");
+
+        let (code, _, _) = run_cli(&config, &["export", "Work", &export.to_string_lossy()]);
+        assert_eq!(code, 0);
+        assert!(export.with_extension("png").exists());
+
+        let (code, out, _) = run_cli(&config, &["import", &export.to_string_lossy()]);
+        assert_eq!(code, 0);
+        assert_eq!(out, "Imported \"Work 2\"\n");
+
+        let store = Store::load_from(config).unwrap();
+        let imported = &store.data.profiles[2];
+        assert_ne!(imported.id, "work-id");
+        assert_eq!(imported.apps.len(), 2);
+    }
+
+    #[test]
+    fn import_drops_conflicting_shortcuts() {
+        let config = config_with_profiles();
+        let mut store = Store::load_from(config.clone()).unwrap();
+        store.data.profiles[0].shortcut = Some("Ctrl+Alt+1".to_string());
+        store.save().unwrap();
+        let mut exported = store.data.profiles[0].clone();
+        exported.name = "Other".to_string();
+        let path = config.with_file_name("other.json");
+        std::fs::write(&path, serde_json::to_string(&exported).unwrap()).unwrap();
+
+        let (code, out, _) = run_cli(&config, &["import", &path.to_string_lossy()]);
+        assert_eq!(code, 0);
+        assert!(out.contains("dropped keyboard shortcut"));
+        let store = Store::load_from(config).unwrap();
+        assert_eq!(store.data.profiles[2].shortcut, None);
+    }
+
+    #[test]
+    fn usage_errors_exit_with_code_two() {
+        let config = config_with_profiles();
+        let (code, _, err) = run_cli(&config, &["add-app", "Work"]);
+        assert_eq!(code, ExitCode::Usage as i32);
+        assert!(!err.is_empty());
+    }
+
+    #[test]
+    fn rules_explains_each_condition_and_the_winner() {
+        let config = config_with_profiles();
+        let mounted = config.parent().unwrap().join("Volumes").join("Work");
+        std::fs::create_dir_all(&mounted).unwrap();
+        let mut store = Store::load_from(config.clone()).unwrap();
+        let exists = |path: &Path| SignalCondition {
+            signal: Signal::PathExists {
+                path: path.to_string_lossy().to_string(),
+            },
+            negate: false,
+        };
+        store.data.signal_rules = vec![
+            SignalRule {
+                id: "office".to_string(),
+                name: "Office".to_string(),
+                profile_id: "work-id".to_string(),
+                conditions: vec![exists(&mounted)],
+                priority: 0,
+                enabled: true,
+            },
+            SignalRule {
+                id: "home".to_string(),
+                name: "Home".to_string(),
+                profile_id: "home-id".to_string(),
+                conditions: vec![exists(&mounted.with_file_name("Home"))],
+                priority: 1,
+                enabled: true,
+            },
+        ];
+        store.save().unwrap();
+
+        let (code, out, _) = run_cli(&config, &["rules"]);
+        assert_eq!(code, 0);
+        assert_eq!(
+            out,
+            format!(
+                "* Office -> Work (priority 0)\n    [x] {} exists\n  Home -> Home (priority 1)\n    [ ] {} does not exist\n",
+                mounted.display(),
+                mounted.with_file_name("Home").display()
+            )
+        );
+
+        let (_, out, _) = run_cli(&config, &["--json", "rules"]);
+        let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(json["winner"], "office");
+        assert_eq!(json["rules"][1]["matched"], false);
+    }
+
+    #[test]
+    fn report_dir_tracks_the_project_without_reapplying_the_active_profile() {
+        let config = config_with_profiles();
+        let repo = config.parent().unwrap().join("repo");
+        let nested = repo.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(repo.join(".dockprofile"), "# Dock for this repo\nWork\n").unwrap();
+
+        let (code, out, _) = run_cli(&config, &["report-dir", nested.to_str().unwrap()]);
+        assert_eq!((code, out.as_str()), (0, ""));
+        let session = Store::load_from(config.clone())
+            .unwrap()
+            .data
+            .directory_session
+            .unwrap();
+        assert_eq!(session.root, repo.to_string_lossy());
+        assert_eq!(session.session.profile_id.as_deref(), Some("work-id"));
+
+        let outside = config.parent().unwrap();
+        let (code, _, _) = run_cli(&config, &["report-dir", outside.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        let store = Store::load_from(config.clone()).unwrap();
+        assert_eq!(store.data.directory_session, None);
+        assert_eq!(store.data.active_profile_id.as_deref(), Some("work-id"));
+    }
+
+    #[test]
+    fn hook_prints_a_snippet_for_each_shell() {
+        let config = config_with_profiles();
+        for shell in ["zsh", "bash", "fish"] {
+            let (code, out, _) = run_cli(&config, &["hook", shell]);
+            assert_eq!(code, 0);
+            assert!(out.contains("report-dir \"$PWD\""), "{}", out);
+        }
+    }
+}
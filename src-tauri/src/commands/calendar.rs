@@ -0,0 +1,61 @@
+use crate::calendar::{validate_calendar_files, validate_calendar_rules};
+use crate::store::{CalendarRule, Store};
+use crate::tray::refresh_tray;
+
+#[tauri::command]
+pub fn get_calendar_files(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<String>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store.data.calendar_files.clone())
+}
+
+#[tauri::command]
+pub fn update_calendar_files(
+    files: Vec<String>,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<String>, String> {
+    let mut seen = std::collections::HashSet::new();
+    let files: Vec<String> = files
+        .into_iter()
+        .map(|file| file.trim().to_string())
+        .filter(|file| !file.is_empty() && seen.insert(file.clone()))
+        .collect();
+    validate_calendar_files(&files)?;
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    store.data.calendar_files = files;
+    store.save().map_err(|e| e.to_string())?;
+    Ok(store.data.calendar_files.clone())
+}
+
+#[tauri::command]
+pub fn get_calendar_rules(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<CalendarRule>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store.data.calendar_rules.clone())
+}
+
+#[tauri::command]
+pub fn update_calendar_rules(
+    rules: Vec<CalendarRule>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<CalendarRule>, String> {
+    let rules: Vec<CalendarRule> = rules
+        .into_iter()
+        .map(|mut rule| {
+            if rule.id.is_empty() {
+                rule.id = uuid::Uuid::new_v4().to_string();
+            }
+            rule.pattern = rule.pattern.trim().to_string();
+            rule
+        })
+        .collect();
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    validate_calendar_rules(&rules, &store.data.profiles)?;
+    store.data.calendar_rules = rules;
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    Ok(store.data.calendar_rules.clone())
+}
@@ -0,0 +1,20 @@
+use crate::catalog::{AppCatalog, CatalogApp};
+
+#[tauri::command]
+pub fn list_installed_apps(
+    catalog: tauri::State<'_, std::sync::Mutex<AppCatalog>>,
+) -> Result<Vec<CatalogApp>, String> {
+    let mut catalog = catalog.lock().map_err(|e| e.to_string())?;
+    catalog.refresh_if_stale();
+    Ok(catalog.apps())
+}
+
+#[tauri::command]
+pub fn search_apps(
+    query: String,
+    catalog: tauri::State<'_, std::sync::Mutex<AppCatalog>>,
+) -> Result<Vec<CatalogApp>, String> {
+    let mut catalog = catalog.lock().map_err(|e| e.to_string())?;
+    catalog.refresh_if_stale();
+    Ok(catalog.search(&query))
+}
@@ -1,29 +1,131 @@
-use crate::dock::{get_dockutil_path, parse_dockutil_output};
-use crate::store::{AppEntry, Store};
-use crate::tray::build_tray_menu;
+use crate::bundle::BundleError;
+use crate::catalog::AppCatalog;
+use crate::control::{notify_subscribers, APPLY_PROGRESS_NOTIFICATION};
+use crate::dock::{
+    self, app_entry_from_bundle, find_duplicate_app, get_dockutil_path, restart_dock,
+    ApplyProgress, ApplyStage,
+};
+use crate::icon_cache::{attach_cached_icons, icon_worker_count, load_icons_parallel, IconCache};
+use crate::lock::{read_dock_locks, unlock_dock as lift_dock_locks};
+use crate::resolver::AppResolver;
+use crate::store::{AppEntry, ApplyResult, DockLocks, Profile, Session, Store};
+use crate::switch::{plan_switch, SwitchPlan};
+use crate::tray::refresh_tray;
+use tauri::{Emitter, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 
 #[tauri::command]
 pub fn check_dockutil(app: tauri::AppHandle) -> Result<bool, String> {
     Ok(get_dockutil_path(&app).is_ok())
 }
 
+pub(crate) const APP_ICON_LOADED_EVENT: &str = "app-icon-loaded";
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct AppIconLoaded {
+    profile_id: Option<String>,
+    path: String,
+    icon: String,
+}
+
+pub(crate) fn list_dock_apps(app: &tauri::AppHandle) -> Result<Vec<AppEntry>, String> {
+    let dockutil = get_dockutil_path(app)?;
+    let catalog = app.state::<std::sync::Mutex<AppCatalog>>();
+    let mut catalog = catalog.lock().map_err(|e| e.to_string())?;
+    dock::list_dock_apps(&dockutil, &mut catalog)
+}
+
+fn spawn_icon_loading(app: tauri::AppHandle, profile_id: Option<String>, paths: Vec<String>) {
+    if paths.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || {
+        let cache = IconCache::shared();
+        load_icons_parallel(&cache, &paths, icon_worker_count(), |path, icon| {
+            let Some(icon) = icon else {
+                return;
+            };
+            if let Some(profile_id) = &profile_id {
+                let state = app.state::<std::sync::Mutex<Store>>();
+                if let Ok(mut store) = state.lock() {
+                    if let Some(profile) =
+                        store.data.profiles.iter_mut().find(|p| &p.id == profile_id)
+                    {
+                        for entry in profile.apps.iter_mut().filter(|entry| entry.path == path) {
+                            entry.icon = Some(icon.clone());
+                        }
+                    }
+                };
+            }
+            let _ = app.emit(
+                APP_ICON_LOADED_EVENT,
+                AppIconLoaded {
+                    profile_id: profile_id.clone(),
+                    path: path.to_string(),
+                    icon,
+                },
+            );
+        });
+
+        if profile_id.is_some() {
+            let state = app.state::<std::sync::Mutex<Store>>();
+            if let Ok(store) = state.lock() {
+                if let Err(e) = store.save() {
+                    eprintln!("Failed to save loaded icons: {}", e);
+                }
+                refresh_tray(&app, &store);
+            };
+        }
+    });
+}
+
 #[tauri::command]
 pub fn get_current_dock_apps(app: tauri::AppHandle) -> Result<Vec<AppEntry>, String> {
-    let dockutil = get_dockutil_path(&app)?;
-    let output = std::process::Command::new(&dockutil)
-        .arg("--list")
-        .output()
-        .map_err(|e| format!("Failed to run dockutil: {}", e))?;
-
-    if !output.status.success() {
-        return Err(format!(
-            "dockutil --list failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
-    }
+    let mut apps = list_dock_apps(&app)?;
+    let missing = attach_cached_icons(&mut apps, &IconCache::shared());
+    spawn_icon_loading(app, None, missing);
+    Ok(apps)
+}
+
+fn catalog_resolver(app: &tauri::AppHandle) -> AppResolver {
+    let catalog = app.state::<std::sync::Mutex<AppCatalog>>();
+    let Ok(mut catalog) = catalog.lock() else {
+        return AppResolver::default();
+    };
+    catalog.refresh_if_stale();
+    catalog.resolver()
+}
+
+#[tauri::command]
+pub fn get_dock_locks() -> Result<DockLocks, String> {
+    Ok(read_dock_locks())
+}
+
+#[tauri::command]
+pub fn unlock_dock() -> Result<(), String> {
+    lift_dock_locks()?;
+    restart_dock()
+}
+
+pub(crate) const PROFILES_CHANGED_EVENT: &str = "profiles-changed";
+pub(crate) const APPLY_PROGRESS_EVENT: &str = "apply-progress";
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_dockutil_output(&stdout))
+fn report_apply_progress(app: &tauri::AppHandle, progress: ApplyProgress) {
+    let _ = app.emit(APPLY_PROGRESS_EVENT, &progress);
+    notify_subscribers(app, APPLY_PROGRESS_NOTIFICATION, &progress);
+}
+
+/// Held for a whole Dock switch, so applies from the window, tray, shortcuts,
+/// automation and the control socket run one after another.
+#[derive(Default)]
+pub(crate) struct ApplyLock(std::sync::Mutex<()>);
+
+fn lock_applies(app: &tauri::AppHandle) -> Result<std::sync::MutexGuard<'_, ()>, String> {
+    app.state::<ApplyLock>()
+        .inner()
+        .0
+        .lock()
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -32,7 +134,93 @@ pub fn apply_profile(
     app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<(), String> {
-    let dockutil = get_dockutil_path(&app)?;
+    let _applying = lock_applies(&app)?;
+    let result = switch_to_profile(&id, &app, &state);
+
+    {
+        let mut store = state.lock().map_err(|e| e.to_string())?;
+        let (profile_name, total) = store
+            .data
+            .profiles
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| (p.name.clone(), p.apps.len()))
+            .unwrap_or_default();
+        report_apply_progress(
+            &app,
+            ApplyProgress {
+                profile_id: id.clone(),
+                stage: if result.is_ok() {
+                    ApplyStage::Finished
+                } else {
+                    ApplyStage::Failed
+                },
+                step: total,
+                total,
+                detail: result.as_ref().err().cloned(),
+            },
+        );
+        store.record_apply(ApplyResult::new(id, profile_name, &result));
+        store.save().map_err(|e| e.to_string())?;
+        refresh_tray(&app, &store);
+    }
+    let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+
+    let warnings = result?;
+    if !warnings.is_empty() {
+        eprintln!("Warnings during profile apply: {:?}", warnings);
+    }
+    Ok(())
+}
+
+fn apply_profile_target(
+    target: impl FnOnce(&Store) -> Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Option<String>, String> {
+    let id = {
+        let store = state.lock().map_err(|e| e.to_string())?;
+        target(&store)
+    };
+    match id {
+        Some(id) => {
+            apply_profile(id.clone(), app, state)?;
+            Ok(Some(id))
+        }
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub fn apply_next_profile(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Option<String>, String> {
+    apply_profile_target(|store| store.adjacent_profile_id(1), app, state)
+}
+
+#[tauri::command]
+pub fn apply_previous_profile(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Option<String>, String> {
+    apply_profile_target(|store| store.adjacent_profile_id(-1), app, state)
+}
+
+#[tauri::command]
+pub fn toggle_last_profile(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Option<String>, String> {
+    apply_profile_target(Store::last_profile_id, app, state)
+}
+
+fn switch_to_profile(
+    id: &str,
+    app: &tauri::AppHandle,
+    state: &tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<String>, String> {
+    let dockutil = get_dockutil_path(app)?;
 
     let profile = {
         let store = state.lock().map_err(|e| e.to_string())?;
@@ -45,61 +233,215 @@ pub fn apply_profile(
             .ok_or_else(|| "Profile not found".to_string())?
     };
 
-    let remove_output = std::process::Command::new(&dockutil)
-        .args(["--remove", "all", "--no-restart"])
-        .output()
-        .map_err(|e| format!("Failed to remove dock items: {}", e))?;
-
-    if !remove_output.status.success() {
-        return Err(format!(
-            "dockutil --remove failed: {}",
-            String::from_utf8_lossy(&remove_output.stderr)
-        ));
-    }
+    let mut resolver: Option<AppResolver> = None;
+    let outcome = dock::apply_dock_profile(
+        &dockutil,
+        &profile,
+        |bundle_id| {
+            resolver
+                .get_or_insert_with(|| catalog_resolver(app))
+                .resolve(bundle_id)
+                .map(|path| path.to_string_lossy().to_string())
+        },
+        |progress| report_apply_progress(app, progress),
+    )?;
 
-    let mut warnings = Vec::new();
-    for entry in &profile.apps {
-        if !std::path::Path::new(&entry.path).exists() {
-            warnings.push(format!("{} not found at {}", entry.name, entry.path));
-            continue;
-        }
-        let add_output = std::process::Command::new(&dockutil)
-            .args(["--add", &entry.path, "--no-restart"])
-            .output()
-            .map_err(|e| format!("Failed to add {}: {}", entry.name, e))?;
-
-        if !add_output.status.success() {
-            warnings.push(format!(
-                "Failed to add {}: {}",
-                entry.name,
-                String::from_utf8_lossy(&add_output.stderr)
-            ));
+    if !outcome.relocated.is_empty() {
+        let mut store = state.lock().map_err(|e| e.to_string())?;
+        if store.data.settings.update_moved_app_paths {
+            if let Some(stored) = store.data.profiles.iter_mut().find(|p| p.id == id) {
+                dock::update_relocated_paths(stored, &outcome.relocated);
+            }
         }
     }
 
-    std::process::Command::new("killall")
-        .arg("Dock")
-        .output()
-        .map_err(|e| format!("Failed to restart Dock: {}", e))?;
+    Ok(outcome.warnings)
+}
 
+/// Replaces the Dock with apps that don't belong to a saved profile, such as
+/// the inline list of a `.dockprofile`, leaving no profile active.
+pub(crate) fn apply_app_list(app: &tauri::AppHandle, profile: &Profile) -> Result<(), String> {
+    let _applying = lock_applies(app)?;
+    let dockutil = get_dockutil_path(app)?;
+    let mut resolver: Option<AppResolver> = None;
+    let outcome = dock::apply_dock_profile(
+        &dockutil,
+        profile,
+        |bundle_id| {
+            resolver
+                .get_or_insert_with(|| catalog_resolver(app))
+                .resolve(bundle_id)
+                .map(|path| path.to_string_lossy().to_string())
+        },
+        |_| {},
+    )?;
     {
+        let state = app.state::<std::sync::Mutex<Store>>();
         let mut store = state.lock().map_err(|e| e.to_string())?;
-        store.data.active_profile_id = Some(id);
+        store.data.active_profile_id = None;
         store.save().map_err(|e| e.to_string())?;
+        refresh_tray(app, &store);
+    }
+    let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+    if !outcome.warnings.is_empty() {
+        eprintln!("Warnings while applying app list: {:?}", outcome.warnings);
     }
+    Ok(())
+}
+
+pub(crate) const PREVIOUS_DOCK_NAME: &str = "Previous Dock";
+
+/// Saves the current Dock into the session `session` picks out, when that
+/// session started with no profile active, so ending it can put the apps back.
+pub(crate) fn snapshot_dock(
+    app: &tauri::AppHandle,
+    session: fn(&mut Store) -> Option<&mut Session>,
+) -> Result<(), String> {
+    let apps = list_dock_apps(app)?;
+    let state = app.state::<std::sync::Mutex<Store>>();
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    let Some(session) = session(&mut store).filter(|session| session.needs_dock_snapshot()) else {
+        return Ok(());
+    };
+    session.previous_apps = apps;
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Puts back a Dock that had no profile active, as saved by a session.
+pub(crate) fn restore_app_list(app: &tauri::AppHandle, apps: Vec<AppEntry>) -> Result<(), String> {
+    let mut profile = Profile::new(PREVIOUS_DOCK_NAME.to_string());
+    profile.apps = apps;
+    apply_app_list(app, &profile)
+}
 
-    if let Some(tray) = app.tray_by_id("main-tray") {
+fn build_switch_plan(app: &tauri::AppHandle, id: &str) -> Result<SwitchPlan, String> {
+    let profile = {
+        let state = app.state::<std::sync::Mutex<Store>>();
         let store = state.lock().map_err(|e| e.to_string())?;
-        if let Ok(menu) = build_tray_menu(&app, &store) {
-            let _ = tray.set_menu(Some(menu));
+        store
+            .data
+            .profiles
+            .iter()
+            .find(|p| p.id == id)
+            .cloned()
+            .ok_or_else(|| "Profile not found".to_string())?
+    };
+    let current = list_dock_apps(app).unwrap_or_default();
+    Ok(plan_switch(&current, &profile, &catalog_resolver(app)))
+}
+
+fn confirm_before_switch(app: &tauri::AppHandle) -> bool {
+    let state = app.state::<std::sync::Mutex<Store>>();
+    let confirm = state
+        .lock()
+        .map(|store| store.data.settings.confirm_before_switch)
+        .unwrap_or(true);
+    confirm
+}
+
+#[tauri::command]
+pub fn get_switch_confirmation(
+    id: String,
+    app: tauri::AppHandle,
+) -> Result<Option<SwitchPlan>, String> {
+    if !confirm_before_switch(&app) {
+        return Ok(None);
+    }
+    build_switch_plan(&app, &id).map(Some)
+}
+
+fn spawn_apply(app: &tauri::AppHandle, id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<std::sync::Mutex<Store>>();
+        if let Err(e) = apply_profile(id, app.clone(), state) {
+            eprintln!("Failed to apply profile: {}", e);
         }
+    });
+}
+
+pub(crate) fn request_profile_switch(app: &tauri::AppHandle, id: String) {
+    if confirm_before_switch(app) {
+        confirm_profile_switch(app, id);
+    } else {
+        spawn_apply(app, id);
     }
+}
 
-    if !warnings.is_empty() {
-        eprintln!("Warnings during profile apply: {:?}", warnings);
+/// Asks before switching regardless of the confirmation setting. Listing the
+/// Dock for the summary runs dockutil, so it happens off the calling thread.
+pub(crate) fn confirm_profile_switch(app: &tauri::AppHandle, id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let plan = match build_switch_plan(&app, &id) {
+            Ok(plan) => plan,
+            Err(e) => {
+                eprintln!("Failed to plan profile switch: {}", e);
+                return;
+            }
+        };
+        let app_handle = app.clone();
+        app.dialog()
+            .message(plan.summary)
+            .title(format!("Switch Dock to \"{}\"?", plan.profile_name))
+            .kind(MessageDialogKind::Info)
+            .buttons(MessageDialogButtons::OkCancelCustom(
+                "Switch".to_string(),
+                "Cancel".to_string(),
+            ))
+            .show(move |confirmed| {
+                if confirmed {
+                    spawn_apply(&app_handle, id);
+                }
+            });
+    });
+}
+
+#[derive(Debug)]
+pub enum AddAppError {
+    InvalidBundle(BundleError),
+    ProfileNotFound,
+    AlreadyInProfile(String),
+    LockPoisoned(String),
+    Store(String),
+}
+
+impl AddAppError {
+    fn kind(&self) -> &'static str {
+        match self {
+            AddAppError::InvalidBundle(_) => "invalid_bundle",
+            AddAppError::ProfileNotFound => "profile_not_found",
+            AddAppError::AlreadyInProfile(_) => "already_in_profile",
+            AddAppError::LockPoisoned(_) => "lock_poisoned",
+            AddAppError::Store(_) => "store",
+        }
     }
+}
 
-    Ok(())
+impl std::fmt::Display for AddAppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddAppError::InvalidBundle(error) => write!(f, "{}", error),
+            AddAppError::ProfileNotFound => write!(f, "Profile not found"),
+            AddAppError::AlreadyInProfile(name) => {
+                write!(f, "{} is already in this profile", name)
+            }
+            AddAppError::LockPoisoned(error) => write!(f, "{}", error),
+            AddAppError::Store(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for AddAppError {}
+
+impl serde::Serialize for AddAppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut error = serializer.serialize_struct("AddAppError", 2)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
 }
 
 #[tauri::command]
@@ -107,27 +449,32 @@ pub fn add_app_to_profile(
     profile_id: String,
     app_path: String,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
-) -> Result<AppEntry, String> {
-    let name = std::path::Path::new(&app_path)
-        .file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| "Unknown".to_string());
-
-    let entry = AppEntry {
-        name,
-        path: app_path.clone(),
-        icon: crate::icon::extract_app_icon(&app_path),
-        bundle_id: None,
+    catalog: tauri::State<'_, std::sync::Mutex<AppCatalog>>,
+) -> Result<AppEntry, AddAppError> {
+    let entry = {
+        let mut catalog = catalog
+            .lock()
+            .map_err(|e| AddAppError::LockPoisoned(e.to_string()))?;
+        app_entry_from_bundle(&mut catalog, &app_path).map_err(AddAppError::InvalidBundle)?
     };
 
-    let mut store = state.lock().map_err(|e| e.to_string())?;
-    if let Some(profile) = store.data.profiles.iter_mut().find(|p| p.id == profile_id) {
-        profile.apps.push(entry.clone());
-        profile.updated_at = chrono::Utc::now().to_rfc3339();
-    } else {
-        return Err("Profile not found".to_string());
+    let mut store = state
+        .lock()
+        .map_err(|e| AddAppError::LockPoisoned(e.to_string()))?;
+    let profile = store
+        .data
+        .profiles
+        .iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or(AddAppError::ProfileNotFound)?;
+    if let Some(existing) = find_duplicate_app(&profile.apps, &entry) {
+        return Err(AddAppError::AlreadyInProfile(existing.name.clone()));
     }
-    store.save().map_err(|e| e.to_string())?;
+    profile.apps.push(entry.clone());
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    store
+        .save()
+        .map_err(|e| AddAppError::Store(e.to_string()))?;
     Ok(entry)
 }
 
@@ -135,10 +482,25 @@ pub fn add_app_to_profile(
 pub fn remove_app_from_profile(
     profile_id: String,
     app_index: usize,
+    expected: Option<AppEntry>,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<(), String> {
     let mut store = state.lock().map_err(|e| e.to_string())?;
     if let Some(profile) = store.data.profiles.iter_mut().find(|p| p.id == profile_id) {
+        // Callers working from an older copy of the profile say which entry they
+        // mean, so a profile changed since then never loses the wrong app.
+        if let Some(expected) = &expected {
+            let matches = profile
+                .apps
+                .get(app_index)
+                .is_some_and(|entry| entry.path == expected.path && entry.kind == expected.kind);
+            if !matches {
+                return Err(format!(
+                    "\"{}\" changed before {} could be removed; try again",
+                    profile.name, expected.name
+                ));
+            }
+        }
         if app_index < profile.apps.len() {
             profile.apps.remove(app_index);
             profile.updated_at = chrono::Utc::now().to_rfc3339();
@@ -148,20 +510,31 @@ pub fn remove_app_from_profile(
     Ok(())
 }
 
+pub(crate) fn capture_dock_to_profile(
+    app: &tauri::AppHandle,
+    profile_id: &str,
+) -> Result<Vec<AppEntry>, String> {
+    let mut apps = list_dock_apps(app)?;
+    let missing = attach_cached_icons(&mut apps, &IconCache::shared());
+    {
+        let state = app.state::<std::sync::Mutex<Store>>();
+        let mut store = state.lock().map_err(|e| e.to_string())?;
+        if let Some(profile) = store.data.profiles.iter_mut().find(|p| p.id == profile_id) {
+            profile.apps = apps.clone();
+            profile.updated_at = chrono::Utc::now().to_rfc3339();
+        } else {
+            return Err("Profile not found".to_string());
+        }
+        store.save().map_err(|e| e.to_string())?;
+    }
+    spawn_icon_loading(app.clone(), Some(profile_id.to_string()), missing);
+    Ok(apps)
+}
+
 #[tauri::command]
 pub fn save_dock_to_profile(
     profile_id: String,
     app: tauri::AppHandle,
-    state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<Vec<AppEntry>, String> {
-    let apps = get_current_dock_apps(app)?;
-    let mut store = state.lock().map_err(|e| e.to_string())?;
-    if let Some(profile) = store.data.profiles.iter_mut().find(|p| p.id == profile_id) {
-        profile.apps = apps.clone();
-        profile.updated_at = chrono::Utc::now().to_rfc3339();
-    } else {
-        return Err("Profile not found".to_string());
-    }
-    store.save().map_err(|e| e.to_string())?;
-    Ok(apps)
+    capture_dock_to_profile(&app, &profile_id)
 }
@@ -1,3 +1,8 @@
+pub mod calendar;
+pub mod catalog;
 pub mod dock;
 pub mod profiles;
+pub mod schedules;
 pub mod settings;
+pub mod signals;
+pub mod temporary;
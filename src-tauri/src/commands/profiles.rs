@@ -1,4 +1,10 @@
+use crate::preview::{self, encode_png, PreviewOptions};
+use crate::shortcuts::{register_shortcuts, shortcut_bindings};
 use crate::store::{Profile, Store};
+use crate::tray::refresh_tray;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tauri::Manager;
 
 #[tauri::command]
 pub fn get_profiles(
@@ -19,53 +25,81 @@ pub fn get_active_profile_id(
 #[tauri::command]
 pub fn create_profile(
     name: String,
+    app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<Profile, String> {
     let mut store = state.lock().map_err(|e| e.to_string())?;
-    let profile = Profile {
-        id: uuid::Uuid::new_v4().to_string(),
-        name,
-        apps: Vec::new(),
-        created_at: chrono::Utc::now().to_rfc3339(),
-        updated_at: chrono::Utc::now().to_rfc3339(),
-    };
+    let profile = Profile::new(name);
     store.data.profiles.push(profile.clone());
     store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
     Ok(profile)
 }
 
 #[tauri::command]
 pub fn update_profile(
     profile: Profile,
+    app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<(), String> {
     let mut store = state.lock().map_err(|e| e.to_string())?;
+    let mut candidates = store.data.profiles.clone();
+    if let Some(p) = candidates.iter_mut().find(|p| p.id == profile.id) {
+        p.shortcut = profile.shortcut.clone();
+    }
+    shortcut_bindings(&candidates, &store.data.settings)?;
+
     if let Some(p) = store.data.profiles.iter_mut().find(|p| p.id == profile.id) {
         p.name = profile.name;
         p.apps = profile.apps;
+        p.locks = profile.locks;
+        p.symbol = profile.symbol;
+        p.shortcut = profile.shortcut;
         p.updated_at = chrono::Utc::now().to_rfc3339();
     }
     store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    register_shortcuts(&app, &store);
     Ok(())
 }
 
 #[tauri::command]
 pub fn delete_profile(
     id: String,
+    app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<(), String> {
     let mut store = state.lock().map_err(|e| e.to_string())?;
     store.data.profiles.retain(|p| p.id != id);
+    store.data.recent_profile_ids.retain(|recent| recent != &id);
+    store.data.schedules.retain(|rule| rule.profile_id != id);
+    store
+        .data
+        .calendar_rules
+        .retain(|rule| rule.profile_id != id);
+    store.data.signal_rules.retain(|rule| rule.profile_id != id);
+    if store.data.signal_rule_id.as_ref().is_some_and(|rule_id| {
+        !store
+            .data
+            .signal_rules
+            .iter()
+            .any(|rule| &rule.id == rule_id)
+    }) {
+        store.data.signal_rule_id = None;
+    }
     if store.data.active_profile_id.as_ref() == Some(&id) {
         store.data.active_profile_id = None;
     }
     store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    register_shortcuts(&app, &store);
     Ok(())
 }
 
 #[tauri::command]
 pub fn reorder_profiles(
     ids: Vec<String>,
+    app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
 ) -> Result<(), String> {
     let mut store = state.lock().map_err(|e| e.to_string())?;
@@ -77,5 +111,64 @@ pub fn reorder_profiles(
     }
     store.data.profiles = reordered;
     store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
     Ok(())
 }
+
+fn find_profile(
+    id: &str,
+    state: &tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Profile, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    store
+        .data
+        .profiles
+        .iter()
+        .find(|p| p.id == id)
+        .cloned()
+        .ok_or_else(|| "Profile not found".to_string())
+}
+
+#[tauri::command]
+pub fn render_profile_preview(
+    id: String,
+    options: Option<PreviewOptions>,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<String, String> {
+    let profile = find_profile(&id, &state)?;
+    let image = preview::render_profile_preview(&profile, &options.unwrap_or_default());
+    Ok(STANDARD.encode(encode_png(&image)?))
+}
+
+#[tauri::command]
+pub fn export_profile(
+    id: String,
+    path: String,
+    options: Option<PreviewOptions>,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<String, String> {
+    let profile = find_profile(&id, &state)?;
+    let preview_path = preview::export_profile(
+        &profile,
+        std::path::Path::new(&path),
+        &options.unwrap_or_default(),
+    )?;
+    Ok(preview_path.to_string_lossy().to_string())
+}
+
+/// Adds the profile exported to `file`, returning it along with a warning
+/// about anything that couldn't be kept.
+pub(crate) fn import_profile_file(
+    app: &tauri::AppHandle,
+    file: &std::path::Path,
+    name: Option<String>,
+) -> Result<(Profile, Option<String>), String> {
+    let state = app.state::<std::sync::Mutex<Store>>();
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    let (profile, warning) = store.import_profile(file, name)?;
+    store.data.profiles.push(profile.clone());
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(app, &store);
+    register_shortcuts(app, &store);
+    Ok((profile, warning))
+}
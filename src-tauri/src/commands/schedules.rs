@@ -0,0 +1,60 @@
+use crate::schedule::validate_schedules;
+use crate::store::{ScheduleRule, Store};
+use crate::tray::refresh_tray;
+
+#[tauri::command]
+pub fn get_schedules(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<ScheduleRule>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store.data.schedules.clone())
+}
+
+#[tauri::command]
+pub fn update_schedules(
+    schedules: Vec<ScheduleRule>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<ScheduleRule>, String> {
+    let schedules: Vec<ScheduleRule> = schedules
+        .into_iter()
+        .map(|mut rule| {
+            if rule.id.is_empty() {
+                rule.id = uuid::Uuid::new_v4().to_string();
+            }
+            rule.time = rule.time.trim().to_string();
+            rule.timezone = rule
+                .timezone
+                .map(|timezone| timezone.trim().to_string())
+                .filter(|timezone| !timezone.is_empty());
+            rule
+        })
+        .collect();
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    validate_schedules(&schedules, &store.data.profiles)?;
+    store.data.schedules = schedules;
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    Ok(store.data.schedules.clone())
+}
+
+#[tauri::command]
+pub fn get_automation_paused(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<bool, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store.data.automation_paused)
+}
+
+#[tauri::command]
+pub fn set_automation_paused(
+    paused: bool,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<(), String> {
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    store.data.automation_paused = paused;
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    Ok(())
+}
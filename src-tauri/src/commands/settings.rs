@@ -1,4 +1,7 @@
+use crate::catalog::{catalog_roots, AppCatalog};
+use crate::shortcuts::{register_shortcuts, shortcut_bindings};
 use crate::store::{Settings, Store};
+use crate::tray::refresh_tray;
 use tauri_plugin_autostart::ManagerExt;
 
 #[tauri::command]
@@ -12,10 +15,15 @@ pub fn update_settings(
     settings: Settings,
     app: tauri::AppHandle,
     state: tauri::State<'_, std::sync::Mutex<Store>>,
+    catalog: tauri::State<'_, std::sync::Mutex<AppCatalog>>,
 ) -> Result<(), String> {
-    let previous_launch_at_login = {
+    let (previous_launch_at_login, previous_app_roots) = {
         let store = state.lock().map_err(|e| e.to_string())?;
-        store.data.settings.launch_at_login
+        shortcut_bindings(&store.data.profiles, &settings)?;
+        (
+            store.data.settings.launch_at_login,
+            store.data.settings.app_roots.clone(),
+        )
     };
 
     if settings.launch_at_login != previous_launch_at_login {
@@ -27,8 +35,15 @@ pub fn update_settings(
         }
     }
 
+    if settings.app_roots != previous_app_roots {
+        let mut catalog = catalog.lock().map_err(|e| e.to_string())?;
+        catalog.set_roots(catalog_roots(&settings.app_roots));
+    }
+
     let mut store = state.lock().map_err(|e| e.to_string())?;
     store.data.settings = settings;
     store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    register_shortcuts(&app, &store);
     Ok(())
 }
@@ -0,0 +1,63 @@
+use crate::signals::{
+    evaluate_rule, validate_signal_rules, RuleEvaluation, SignalEngine, SystemSignals,
+};
+use crate::store::{SignalFiring, SignalRule, Store};
+use crate::tray::refresh_tray;
+use tauri::Manager;
+
+#[tauri::command]
+pub fn get_signal_rules(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<SignalRule>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store.data.signal_rules.clone())
+}
+
+#[tauri::command]
+pub fn update_signal_rules(
+    rules: Vec<SignalRule>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<SignalRule>, String> {
+    let rules: Vec<SignalRule> = rules
+        .into_iter()
+        .map(|mut rule| {
+            if rule.id.is_empty() {
+                rule.id = uuid::Uuid::new_v4().to_string();
+            }
+            rule.name = rule.name.trim().to_string();
+            rule
+        })
+        .collect();
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    validate_signal_rules(&rules, &store.data.profiles)?;
+    store.data.signal_rules = rules;
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    if let Some(engine) = app.try_state::<SignalEngine>() {
+        engine.wake();
+    }
+    Ok(store.data.signal_rules.clone())
+}
+
+/// Checks every rule against the Mac right now, condition by condition.
+#[tauri::command]
+pub fn explain_signal_rules(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Vec<RuleEvaluation>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store
+        .data
+        .signal_rules
+        .iter()
+        .map(|rule| evaluate_rule(rule, &SystemSignals))
+        .collect())
+}
+
+#[tauri::command]
+pub fn get_last_signal_firing(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Option<SignalFiring>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(store.data.last_signal_firing.clone())
+}
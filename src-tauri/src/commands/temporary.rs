@@ -0,0 +1,102 @@
+use crate::commands::dock::{apply_profile, list_dock_apps, PROFILES_CHANGED_EVENT};
+use crate::store::{Store, TemporarySession};
+use crate::temporary::{
+    extend_session, new_session, run_temporary, running_session, TemporaryTimer,
+};
+use crate::tray::refresh_tray;
+use chrono::Utc;
+use tauri::{Emitter, Manager};
+
+fn wake_timer(app: &tauri::AppHandle) {
+    if let Some(timer) = app.try_state::<TemporaryTimer>() {
+        timer.wake();
+    }
+}
+
+fn duration_from_secs(duration: u64) -> Result<chrono::Duration, String> {
+    if duration == 0 {
+        return Err("Duration must be at least one second".to_string());
+    }
+    i64::try_from(duration)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .ok_or_else(|| "Duration is too long".to_string())
+}
+
+#[tauri::command]
+pub fn get_temporary_session(
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<Option<TemporarySession>, String> {
+    let store = state.lock().map_err(|e| e.to_string())?;
+    Ok(running_session(&store).cloned())
+}
+
+/// Applies a profile for `duration` seconds, then goes back to the profile or
+/// Dock that was there before.
+#[tauri::command]
+pub fn apply_profile_temporarily(
+    id: String,
+    duration: u64,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<TemporarySession, String> {
+    let duration = duration_from_secs(duration)?;
+    let mut session = {
+        let store = state.lock().map_err(|e| e.to_string())?;
+        if !store.data.profiles.iter().any(|p| p.id == id) {
+            return Err("Profile not found".to_string());
+        }
+        new_session(&store, &id, Utc::now() + duration)
+    };
+    if session.session.needs_dock_snapshot() {
+        session.session.previous_apps = list_dock_apps(&app)?;
+    }
+
+    apply_profile(id, app.clone(), app.state::<std::sync::Mutex<Store>>())?;
+
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    if let Some(replaced) = store.data.temporary_session.take() {
+        store.hand_off(&replaced.session);
+    }
+    store.data.temporary_session = Some(session.clone());
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    wake_timer(&app);
+    let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+    Ok(session)
+}
+
+#[tauri::command]
+pub fn extend_temporary_profile(
+    duration: u64,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<TemporarySession, String> {
+    let duration = duration_from_secs(duration)?;
+    let mut store = state.lock().map_err(|e| e.to_string())?;
+    let session = extend_session(&mut store, duration, Utc::now())?;
+    store.save().map_err(|e| e.to_string())?;
+    refresh_tray(&app, &store);
+    wake_timer(&app);
+    let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+    Ok(session)
+}
+
+/// Goes back to the previous profile or Dock right away.
+#[tauri::command]
+pub fn end_temporary_profile(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, std::sync::Mutex<Store>>,
+) -> Result<(), String> {
+    {
+        let mut store = state.lock().map_err(|e| e.to_string())?;
+        let now = Utc::now().to_rfc3339();
+        match store.data.temporary_session.as_mut() {
+            Some(session) => session.ends_at = now,
+            None => return Err("No temporary profile is running".to_string()),
+        }
+    }
+    run_temporary(&app);
+    wake_timer(&app);
+    Ok(())
+}
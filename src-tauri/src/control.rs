@@ -0,0 +1,464 @@
+use crate::commands;
+use crate::commands::dock::PROFILES_CHANGED_EVENT;
+use crate::dockprofile;
+use crate::store::Store;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const COMMAND_FAILED: i64 = -32000;
+
+pub(crate) const APPLY_PROGRESS_NOTIFICATION: &str = "apply_progress";
+
+pub(crate) fn control_socket_path() -> PathBuf {
+    Store::data_dir().join("control.sock")
+}
+
+#[derive(Default)]
+pub(crate) struct ControlHub {
+    subscribers: Mutex<Vec<Sender<String>>>,
+}
+
+impl ControlHub {
+    fn subscribe(&self, sender: Sender<String>) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(sender);
+        }
+    }
+
+    pub(crate) fn notify(&self, method: &str, params: impl Serialize) {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params }).to_string();
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|subscriber| subscriber.send(message.clone()).is_ok());
+        }
+    }
+}
+
+/// Sends a notification to every connection that called `subscribe`.
+pub(crate) fn notify_subscribers(app: &AppHandle, method: &str, params: impl Serialize) {
+    if let Some(hub) = app.try_state::<ControlHub>() {
+        hub.notify(method, params);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        Self::new(COMMAND_FAILED, message)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RpcRequest {
+    id: Option<Value>,
+    method: String,
+    params: Value,
+}
+
+pub(crate) fn parse_request(line: &str) -> Result<RpcRequest, (Value, RpcError)> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| (Value::Null, RpcError::new(PARSE_ERROR, e.to_string())))?;
+    let id = value.get("id").cloned();
+    let invalid = |message: &str| {
+        (
+            id.clone().unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, message),
+        )
+    };
+    if value.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(invalid("jsonrpc must be \"2.0\""));
+    }
+    let method = value
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid("method must be a string"))?
+        .to_string();
+    let params = value.get("params").cloned().unwrap_or(Value::Null);
+    if !(params.is_null() || params.is_object()) {
+        return Err(invalid("params must be an object"));
+    }
+    Ok(RpcRequest { id, method, params })
+}
+
+pub(crate) fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+    .to_string()
+}
+
+fn param<T: serde::de::DeserializeOwned>(params: &Value, name: &str) -> Result<T, RpcError> {
+    let value = params.get(name).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| RpcError::new(INVALID_PARAMS, format!("Invalid \"{}\": {}", name, e)))
+}
+
+fn to_value(result: Result<impl Serialize, String>) -> Result<Value, RpcError> {
+    let result = result?;
+    serde_json::to_value(result).map_err(|e| RpcError::new(COMMAND_FAILED, e.to_string()))
+}
+
+/// Lets open windows reload after a change made through the socket.
+fn changed(app: &AppHandle, result: Result<impl Serialize, String>) -> Result<Value, RpcError> {
+    let value = to_value(result)?;
+    let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+    Ok(value)
+}
+
+fn dispatch(app: &AppHandle, method: &str, params: &Value) -> Result<Value, RpcError> {
+    let state = || app.state::<Mutex<Store>>();
+    match method {
+        "get_profiles" => to_value(commands::profiles::get_profiles(state())),
+        "get_active_profile_id" => to_value(commands::profiles::get_active_profile_id(state())),
+        "get_settings" => to_value(commands::settings::get_settings(state())),
+        "create_profile" => changed(
+            app,
+            commands::profiles::create_profile(param(params, "name")?, app.clone(), state()),
+        ),
+        "delete_profile" => changed(
+            app,
+            commands::profiles::delete_profile(param(params, "id")?, app.clone(), state()),
+        ),
+        "import_profile" => changed(
+            app,
+            commands::profiles::import_profile_file(
+                app,
+                &param::<PathBuf>(params, "path")?,
+                param(params, "name")?,
+            )
+            .map(|(profile, warning)| {
+                json!({ "id": profile.id, "name": profile.name, "warning": warning })
+            }),
+        ),
+        "apply_profile" => to_value(commands::dock::apply_profile(
+            param(params, "id")?,
+            app.clone(),
+            state(),
+        )),
+        "apply_next_profile" => to_value(commands::dock::apply_next_profile(app.clone(), state())),
+        "apply_previous_profile" => {
+            to_value(commands::dock::apply_previous_profile(app.clone(), state()))
+        }
+        "toggle_last_profile" => {
+            to_value(commands::dock::toggle_last_profile(app.clone(), state()))
+        }
+        "get_current_dock_apps" => to_value(commands::dock::get_current_dock_apps(app.clone())),
+        "save_dock_to_profile" => changed(
+            app,
+            commands::dock::save_dock_to_profile(param(params, "profile_id")?, app.clone()),
+        ),
+        "add_app_to_profile" => changed(
+            app,
+            commands::dock::add_app_to_profile(
+                param(params, "profile_id")?,
+                param(params, "app_path")?,
+                state(),
+                app.state(),
+            )
+            .map_err(|e| e.to_string()),
+        ),
+        "remove_app_from_profile" => changed(
+            app,
+            commands::dock::remove_app_from_profile(
+                param(params, "profile_id")?,
+                param(params, "app_index")?,
+                param(params, "expected")?,
+                state(),
+            ),
+        ),
+        "get_dock_locks" => to_value(commands::dock::get_dock_locks()),
+        "apply_profile_temporarily" => to_value(commands::temporary::apply_profile_temporarily(
+            param(params, "id")?,
+            param(params, "duration")?,
+            app.clone(),
+            state(),
+        )),
+        "extend_temporary_profile" => to_value(commands::temporary::extend_temporary_profile(
+            param(params, "duration")?,
+            app.clone(),
+            state(),
+        )),
+        "end_temporary_profile" => to_value(commands::temporary::end_temporary_profile(
+            app.clone(),
+            state(),
+        )),
+        "report_directory" => to_value(dockprofile::report_directory(app, param(params, "path")?)),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method \"{}\"", method),
+        )),
+    }
+}
+
+fn handle_connection(app: AppHandle, stream: UnixStream) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let (sender, receiver) = channel::<String>();
+    std::thread::spawn(move || {
+        for message in receiver {
+            if writeln!(writer, "{}", message).is_err() {
+                break;
+            }
+        }
+    });
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match parse_request(&line) {
+            Ok(request) if request.method == "subscribe" => {
+                app.state::<ControlHub>().subscribe(sender.clone());
+                request.id.map(|id| response(id, Ok(json!(true))))
+            }
+            // Requests without an id are notifications and get no reply.
+            Ok(request) => {
+                let result = dispatch(&app, &request.method, &request.params);
+                request.id.map(|id| response(id, result))
+            }
+            Err((id, error)) => Some(response(id, Err(error))),
+        };
+        if let Some(reply) = reply {
+            if sender.send(reply).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// Binds the control socket, replacing a stale file left by a previous run but
+/// refusing to take over one that another process is still serving.
+pub(crate) fn bind_control_socket(path: &Path) -> std::io::Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                format!("{} is already in use", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+pub fn start_control_server(app: &AppHandle) {
+    let path = control_socket_path();
+    let listener = match bind_control_socket(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Control socket unavailable: {}", e);
+            return;
+        }
+    };
+    app.manage(ControlHub::default());
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    std::thread::spawn(move || handle_connection(app, stream));
+                }
+                Err(e) => eprintln!("Control socket connection failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Sends a request to a running app and waits for its reply. The outer error
+/// means the app couldn't be reached, the inner one that the request failed.
+pub(crate) fn send_request(
+    path: &Path,
+    method: &str,
+    params: Value,
+) -> std::io::Result<Result<Value, String>> {
+    let mut stream = UnixStream::connect(path)?;
+    writeln!(
+        stream,
+        "{}",
+        json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+    )?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    let reply: Value = serde_json::from_str(&line)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(match reply.get("error") {
+        Some(error) => Err(error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Request failed")
+            .to_string()),
+        None => Ok(reply.get("result").cloned().unwrap_or(Value::Null)),
+    })
+}
+
+/// Sends a notification to a running app, without waiting for it to be handled.
+pub(crate) fn send_notification(path: &Path, method: &str, params: Value) -> std::io::Result<()> {
+    let mut stream = UnixStream::connect(path)?;
+    writeln!(
+        stream,
+        "{}",
+        json!({ "jsonrpc": "2.0", "method": method, "params": params })
+    )
+}
+
+pub fn remove_control_socket() {
+    let _ = std::fs::remove_file(control_socket_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::temp_dir;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn parse_request_validates_envelope() {
+        let request = parse_request(
+            r#"{"jsonrpc":"2.0","id":7,"method":"apply_profile","params":{"id":"a"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.method, "apply_profile");
+        assert_eq!(request.params["id"], "a");
+
+        let (id, error) = parse_request("{not json").unwrap_err();
+        assert_eq!((id, error.code), (Value::Null, PARSE_ERROR));
+
+        let (id, error) = parse_request(r#"{"jsonrpc":"1.0","id":"x","method":"m"}"#).unwrap_err();
+        assert_eq!((id, error.code), (json!("x"), INVALID_REQUEST));
+
+        let (_, error) =
+            parse_request(r#"{"jsonrpc":"2.0","id":1,"method":"m","params":[1]}"#).unwrap_err();
+        assert_eq!(error.code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn response_wraps_results_and_errors() {
+        let ok: Value = serde_json::from_str(&response(json!(1), Ok(json!(["a"])))).unwrap();
+        assert_eq!(ok, json!({ "jsonrpc": "2.0", "id": 1, "result": ["a"] }));
+
+        let err: Value = serde_json::from_str(&response(
+            json!(2),
+            Err(RpcError::from("Profile not found".to_string())),
+        ))
+        .unwrap();
+        assert_eq!(err["error"]["code"], COMMAND_FAILED);
+        assert_eq!(err["error"]["message"], "Profile not found");
+    }
+
+    #[test]
+    fn param_reports_missing_or_mistyped_values() {
+        let params = json!({ "id": "a", "count": "two" });
+        assert_eq!(param::<String>(&params, "id").unwrap(), "a");
+        assert_eq!(
+            param::<u32>(&params, "count").unwrap_err().code,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            param::<String>(&params, "name").unwrap_err().code,
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn hub_notifies_live_subscribers_and_drops_closed_ones() {
+        let hub = ControlHub::default();
+        let (live, received) = channel();
+        let (closed, dropped) = channel();
+        hub.subscribe(live);
+        hub.subscribe(closed);
+        drop(dropped);
+
+        hub.notify(APPLY_PROGRESS_NOTIFICATION, json!({ "step": 1 }));
+
+        let message: Value = serde_json::from_str(&received.recv().unwrap()).unwrap();
+        assert_eq!(message["method"], APPLY_PROGRESS_NOTIFICATION);
+        assert_eq!(message["params"]["step"], 1);
+        assert!(message.get("id").is_none());
+        assert_eq!(hub.subscribers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn bind_control_socket_restricts_permissions_and_replaces_stale_files() {
+        let path = temp_dir("dockswitcher-control").join("control.sock");
+        std::fs::write(&path, "stale").unwrap();
+
+        let listener = bind_control_socket(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let in_use = bind_control_socket(&path).unwrap_err();
+        assert_eq!(in_use.kind(), std::io::ErrorKind::AddrInUse);
+        drop(listener);
+    }
+
+    #[test]
+    fn send_request_returns_the_reply_result_or_error() {
+        let path = temp_dir("dockswitcher-control").join("control.sock");
+        let listener = bind_control_socket(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            for reply in [
+                Ok(json!({ "ok": true })),
+                Err("Profile not found".to_string()),
+            ] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut line = String::new();
+                BufReader::new(&stream).read_line(&mut line).unwrap();
+                let request = parse_request(&line).unwrap();
+                assert_eq!(request.method, "apply_profile");
+                let reply = response(request.id.unwrap(), reply.map_err(RpcError::from));
+                writeln!(&stream, "{}", reply).unwrap();
+            }
+        });
+
+        let params = json!({ "id": "a" });
+        let ok = send_request(&path, "apply_profile", params.clone()).unwrap();
+        assert_eq!(ok, Ok(json!({ "ok": true })));
+        let err = send_request(&path, "apply_profile", params).unwrap();
+        assert_eq!(err, Err("Profile not found".to_string()));
+        server.join().unwrap();
+
+        let missing = send_request(&path.with_file_name("missing.sock"), "m", Value::Null);
+        assert_eq!(missing.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,460 @@
+use crate::commands::dock::{
+    confirm_profile_switch, request_profile_switch, PROFILES_CHANGED_EVENT,
+};
+use crate::commands::profiles::import_profile_file;
+use crate::store::Store;
+use crate::tray::{show_window, spawn_dock_capture, TrayAction, PROFILE_CREATED_EVENT};
+use percent_encoding::percent_decode_str;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+pub(crate) const DEEP_LINK_SCHEME: &str = "dockswitcher";
+pub(crate) const OPEN_PROFILE_EVENT: &str = "open-profile";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLinkAction {
+    Apply { profile: String },
+    Capture { profile: Option<String> },
+    OpenProfile { profile: Option<String> },
+    Import { file: PathBuf },
+}
+
+/// Where an action came from: a `dockswitcher://` link, which any web page
+/// can open, or an argument the app was launched with from Finder or a shell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Origin {
+    Link,
+    Launch,
+}
+
+fn decode(part: &str) -> String {
+    percent_decode_str(part).decode_utf8_lossy().into_owned()
+}
+
+fn import_path(value: &str) -> Result<PathBuf, String> {
+    let path = match Url::parse(value) {
+        Ok(url) if url.scheme() == "file" => url
+            .to_file_path()
+            .map_err(|_| format!("\"{}\" is not a local file", value))?,
+        Ok(_) => return Err("Only local files can be imported".to_string()),
+        Err(_) => PathBuf::from(value),
+    };
+    validate_import_path(path)
+}
+
+fn validate_import_path(path: PathBuf) -> Result<PathBuf, String> {
+    if !path.is_absolute() {
+        return Err(format!("\"{}\" is not an absolute path", path.display()));
+    }
+    if !path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        return Err(format!("{} is not a .json profile export", path.display()));
+    }
+    Ok(path)
+}
+
+/// Accepts `dockswitcher://<action>/<profile>` or `dockswitcher://<action>?profile=<profile>`,
+/// where the profile is an id or name, and `dockswitcher://import?file=<path>`.
+pub(crate) fn parse_deep_link(url: &Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Unsupported link scheme \"{}\"", url.scheme()));
+    }
+    let parts: Vec<String> = url
+        .host_str()
+        .into_iter()
+        .chain(url.path().split('/'))
+        .filter(|part| !part.is_empty())
+        .map(decode)
+        .collect();
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    let Some((action, rest)) = parts.split_first() else {
+        return Err("The link does not name an action".to_string());
+    };
+    if rest.len() > 1 {
+        return Err(format!("Unexpected \"{}\" in link", rest[1..].join("/")));
+    }
+    let profile = rest
+        .first()
+        .cloned()
+        .or_else(|| query("profile"))
+        .map(|profile| profile.trim().to_string())
+        .filter(|profile| !profile.is_empty());
+
+    match action.to_ascii_lowercase().as_str() {
+        "apply" => profile
+            .map(|profile| DeepLinkAction::Apply { profile })
+            .ok_or_else(|| "Apply links need a profile".to_string()),
+        "capture" => Ok(DeepLinkAction::Capture { profile }),
+        "open" => Ok(DeepLinkAction::OpenProfile { profile }),
+        "import" => {
+            if profile.is_some() {
+                return Err("Import links take only a file".to_string());
+            }
+            let file = query("file").ok_or_else(|| "Import links need a file".to_string())?;
+            Ok(DeepLinkAction::Import {
+                file: import_path(&file)?,
+            })
+        }
+        other => Err(format!("Unknown link action \"{}\"", other)),
+    }
+}
+
+fn resolve_profile(app: &AppHandle, query: Option<&str>) -> Result<(String, String), String> {
+    let state = app.state::<Mutex<Store>>();
+    let store = state.lock().map_err(|e| e.to_string())?;
+    let profile = match query {
+        Some(query) => &store.data.profiles[store.find_profile_index(query)?],
+        None => store
+            .active_profile()
+            .ok_or_else(|| "No profile is active".to_string())?,
+    };
+    Ok((profile.id.clone(), profile.name.clone()))
+}
+
+fn confirm_link(
+    app: &AppHandle,
+    title: String,
+    message: String,
+    action_label: &str,
+    on_confirm: impl FnOnce(&AppHandle) + Send + 'static,
+) {
+    let app_handle = app.clone();
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            action_label.to_string(),
+            "Cancel".to_string(),
+        ))
+        .show(move |confirmed| {
+            if confirmed {
+                on_confirm(&app_handle);
+            }
+        });
+}
+
+fn open_profile(app: &AppHandle, id: Option<String>) {
+    if let Some(window) = app.get_webview_window("main") {
+        show_window(&window);
+    }
+    if let Some(id) = id {
+        let _ = app.emit(OPEN_PROFILE_EVENT, id);
+    }
+}
+
+fn finish_import(app: &AppHandle, file: &Path) {
+    match import_profile_file(app, file, None) {
+        Ok((profile, warning)) => {
+            if let Some(warning) = warning {
+                eprintln!("Imported \"{}\" but {}", profile.name, warning);
+            }
+            let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+            if let Some(window) = app.get_webview_window("main") {
+                show_window(&window);
+            }
+            let _ = app.emit(PROFILE_CREATED_EVENT, profile.id);
+        }
+        Err(e) => eprintln!("Failed to import profile from {}: {}", file.display(), e),
+    }
+}
+
+/// Links can be opened by any web page, so everything except showing a
+/// profile asks first, even when switch confirmation is turned off. Launch
+/// arguments come from the user and follow the confirmation setting.
+fn run_deep_link(app: &AppHandle, action: DeepLinkAction, origin: Origin) -> Result<(), String> {
+    match action {
+        DeepLinkAction::OpenProfile { profile } => {
+            let id = match profile {
+                Some(query) => Some(resolve_profile(app, Some(&query))?.0),
+                None => None,
+            };
+            open_profile(app, id);
+        }
+        DeepLinkAction::Apply { profile } => {
+            let (id, _) = resolve_profile(app, Some(&profile))?;
+            match origin {
+                Origin::Link => confirm_profile_switch(app, id),
+                Origin::Launch => request_profile_switch(app, id),
+            }
+        }
+        DeepLinkAction::Capture { profile } => {
+            let (id, name) = resolve_profile(app, profile.as_deref())?;
+            if origin == Origin::Launch {
+                spawn_dock_capture(app, TrayAction::SaveToProfile(id));
+                return Ok(());
+            }
+            confirm_link(
+                app,
+                format!("Replace \"{}\" with the current Dock?", name),
+                "A link asked DockSwitcher to overwrite the apps saved in this profile with the apps in your Dock right now.".to_string(),
+                "Replace",
+                move |app| spawn_dock_capture(app, TrayAction::SaveToProfile(id)),
+            );
+        }
+        DeepLinkAction::Import { file } => {
+            if origin == Origin::Launch {
+                finish_import(app, &file);
+                return Ok(());
+            }
+            let (profile, _) = {
+                let state = app.state::<Mutex<Store>>();
+                let store = state.lock().map_err(|e| e.to_string())?;
+                store.import_profile(&file, None)?
+            };
+            let apps = profile.apps.len();
+            confirm_link(
+                app,
+                format!("Import \"{}\"?", profile.name),
+                format!(
+                    "A link asked DockSwitcher to add a profile with {} app{} from {}.",
+                    apps,
+                    if apps == 1 { "" } else { "s" },
+                    file.display()
+                ),
+                "Import",
+                move |app| finish_import(app, &file),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn report_link_error(app: &AppHandle, error: String) {
+    app.dialog()
+        .message(error)
+        .title("DockSwitcher can't open this link")
+        .kind(MessageDialogKind::Error)
+        .show(|_| {});
+}
+
+pub(crate) fn handle_deep_link(app: &AppHandle, url: &Url) {
+    if let Err(e) = parse_deep_link(url).and_then(|action| run_deep_link(app, action, Origin::Link))
+    {
+        report_link_error(app, e);
+    }
+}
+
+fn apply_action(profile: &str) -> Result<DeepLinkAction, String> {
+    let profile = profile.trim();
+    if profile.is_empty() {
+        return Err("--apply needs a profile".to_string());
+    }
+    Ok(DeepLinkAction::Apply {
+        profile: profile.to_string(),
+    })
+}
+
+/// Maps launch arguments (including the program name) to link actions:
+/// `dockswitcher://` links, `--apply <profile>` and exported `.json` files,
+/// resolved against `cwd`. Anything else, such as the `-psn_` argument macOS
+/// adds, is skipped. Links keep their `Link` origin so they still ask first.
+pub(crate) fn launch_actions(
+    args: &[String],
+    cwd: &Path,
+) -> Vec<Result<(DeepLinkAction, Origin), String>> {
+    let mut actions = Vec::new();
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(profile) = arg.strip_prefix("--apply=") {
+            actions.push(apply_action(profile).map(|action| (action, Origin::Launch)));
+        } else if arg == "--apply" {
+            actions.push(
+                apply_action(args.next().map_or("", String::as_str))
+                    .map(|action| (action, Origin::Launch)),
+            );
+        } else if arg.starts_with(&format!("{}:", DEEP_LINK_SCHEME)) {
+            actions.push(
+                Url::parse(arg)
+                    .map_err(|e| format!("\"{}\" is not a valid link: {}", arg, e))
+                    .and_then(|url| parse_deep_link(&url))
+                    .map(|action| (action, Origin::Link)),
+            );
+        } else if arg.to_ascii_lowercase().ends_with(".json") {
+            actions.push(
+                validate_import_path(cwd.join(arg))
+                    .map(|file| (DeepLinkAction::Import { file }, Origin::Launch)),
+            );
+        }
+    }
+    actions
+}
+
+/// Runs the actions in `args` and returns whether there were any, so a bare
+/// relaunch can just bring the window forward.
+pub(crate) fn handle_launch_arguments(app: &AppHandle, args: &[String], cwd: &Path) -> bool {
+    let actions = launch_actions(args, cwd);
+    let found = !actions.is_empty();
+    for action in actions {
+        if let Err(e) = action.and_then(|(action, origin)| run_deep_link(app, action, origin)) {
+            report_link_error(app, e);
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(link: &str) -> Result<DeepLinkAction, String> {
+        parse_deep_link(&Url::parse(link).unwrap())
+    }
+
+    #[test]
+    fn parse_deep_link_reads_profile_from_path_or_query() {
+        let apply = |profile: &str| DeepLinkAction::Apply {
+            profile: profile.to_string(),
+        };
+        assert_eq!(parse("dockswitcher://apply/Work"), Ok(apply("Work")));
+        assert_eq!(
+            parse("dockswitcher://Apply/Deep%20Work/"),
+            Ok(apply("Deep Work"))
+        );
+        assert_eq!(
+            parse("dockswitcher://apply?profile=Deep+Work"),
+            Ok(apply("Deep Work"))
+        );
+        assert_eq!(parse("dockswitcher:apply/Work"), Ok(apply("Work")));
+        assert_eq!(
+            parse("dockswitcher://capture"),
+            Ok(DeepLinkAction::Capture { profile: None })
+        );
+        assert_eq!(
+            parse("dockswitcher://open/p1"),
+            Ok(DeepLinkAction::OpenProfile {
+                profile: Some("p1".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn parse_deep_link_rejects_malformed_links() {
+        assert!(parse("dockswitcher://apply")
+            .unwrap_err()
+            .contains("need a profile"));
+        assert!(parse("dockswitcher://apply/%20").is_err());
+        assert!(parse("dockswitcher://apply/Work/extra")
+            .unwrap_err()
+            .contains("extra"));
+        assert!(parse("dockswitcher://delete/Work")
+            .unwrap_err()
+            .contains("Unknown link action"));
+        assert!(parse("dockswitcher://").is_err());
+        assert!(parse("https://apply/Work").unwrap_err().contains("scheme"));
+    }
+
+    #[test]
+    fn parse_deep_link_accepts_only_local_json_imports() {
+        let import = |file: &str| DeepLinkAction::Import {
+            file: PathBuf::from(file),
+        };
+        assert_eq!(
+            parse("dockswitcher://import?file=/Users/me/Work.json"),
+            Ok(import("/Users/me/Work.json"))
+        );
+        assert_eq!(
+            parse("dockswitcher://import?file=file:///Users/me/Deep%20Work.json"),
+            Ok(import("/Users/me/Deep Work.json"))
+        );
+        assert!(parse("dockswitcher://import").is_err());
+        assert!(parse("dockswitcher://import?file=Work.json")
+            .unwrap_err()
+            .contains("absolute"));
+        assert!(
+            parse("dockswitcher://import?file=https://example.com/Work.json")
+                .unwrap_err()
+                .contains("local files")
+        );
+        assert!(parse("dockswitcher://import?file=/etc/passwd").is_err());
+        assert!(parse("dockswitcher://import/Work?file=/tmp/Work.json").is_err());
+    }
+
+    #[test]
+    fn launch_actions_forward_links_profiles_and_files() {
+        let args: Vec<String> = [
+            "/Applications/DockSwitcher.app/Contents/MacOS/DockSwitcher",
+            "-psn_0_12345",
+            "--apply",
+            "Work",
+            "--apply=Home",
+            "dockswitcher://open/Work",
+            "exports/Focus.json",
+            "/Users/me/Deep Work.JSON",
+            "--verbose",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+
+        let actions = launch_actions(&args, Path::new("/Users/me"));
+
+        assert_eq!(
+            actions,
+            vec![
+                Ok((
+                    DeepLinkAction::Apply {
+                        profile: "Work".to_string()
+                    },
+                    Origin::Launch
+                )),
+                Ok((
+                    DeepLinkAction::Apply {
+                        profile: "Home".to_string()
+                    },
+                    Origin::Launch
+                )),
+                Ok((
+                    DeepLinkAction::OpenProfile {
+                        profile: Some("Work".to_string())
+                    },
+                    Origin::Link
+                )),
+                Ok((
+                    DeepLinkAction::Import {
+                        file: PathBuf::from("/Users/me/exports/Focus.json")
+                    },
+                    Origin::Launch
+                )),
+                Ok((
+                    DeepLinkAction::Import {
+                        file: PathBuf::from("/Users/me/Deep Work.JSON")
+                    },
+                    Origin::Launch
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn launch_actions_report_incomplete_arguments() {
+        let args: Vec<String> = ["DockSwitcher", "dockswitcher://delete", "--apply"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        let actions = launch_actions(&args, Path::new("/"));
+
+        assert_eq!(actions.len(), 2);
+        assert!(actions[0]
+            .as_ref()
+            .unwrap_err()
+            .contains("Unknown link action"));
+        assert_eq!(actions[1], Err("--apply needs a profile".to_string()));
+    }
+
+    #[test]
+    fn launch_actions_ignore_a_bare_launch() {
+        let args = vec!["DockSwitcher".to_string()];
+        assert!(launch_actions(&args, Path::new("/")).is_empty());
+    }
+}
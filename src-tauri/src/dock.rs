@@ -1,4 +1,9 @@
-use crate::store::AppEntry;
+use crate::bundle::{localized_name, preferred_languages, BundleError};
+use crate::catalog::AppCatalog;
+use crate::icon_cache::IconCache;
+use crate::lock::{unlock_dock, write_dock_locks, RestoreLocks};
+use crate::store::{AppEntry, DockItemKind, Profile};
+use std::path::Path;
 use tauri::Manager;
 
 pub(crate) fn percent_decode(input: &str) -> String {
@@ -36,9 +41,11 @@ pub(crate) fn parse_dockutil_output(output: &str) -> Vec<AppEntry> {
                 };
                 apps.push(AppEntry {
                     name,
-                    path: clean_path.clone(),
-                    icon: crate::icon::extract_app_icon(&clean_path),
+                    path: clean_path,
+                    icon: None,
                     bundle_id,
+                    version: None,
+                    kind: DockItemKind::App,
                 });
             }
         }
@@ -46,6 +53,40 @@ pub(crate) fn parse_dockutil_output(output: &str) -> Vec<AppEntry> {
     apps
 }
 
+pub(crate) fn app_entry_from_bundle(
+    catalog: &mut AppCatalog,
+    app_path: &str,
+) -> Result<AppEntry, BundleError> {
+    let path = Path::new(app_path.trim_end_matches('/'));
+    let app = catalog.app(path)?;
+    Ok(AppEntry {
+        name: localized_name(path, preferred_languages()).unwrap_or(app.name),
+        icon: IconCache::shared().app_icon(&app.path),
+        path: app.path,
+        bundle_id: app.bundle_id,
+        version: app.version,
+        kind: DockItemKind::App,
+    })
+}
+
+pub(crate) fn find_duplicate_app<'a>(
+    apps: &'a [AppEntry],
+    entry: &AppEntry,
+) -> Option<&'a AppEntry> {
+    apps.iter().find(|existing| {
+        existing.path == entry.path
+            || (existing.bundle_id.is_some() && existing.bundle_id == entry.bundle_id)
+    })
+}
+
+pub(crate) fn dockutil_spacer_type(kind: DockItemKind) -> Option<&'static str> {
+    match kind {
+        DockItemKind::App => None,
+        DockItemKind::Spacer => Some("spacer"),
+        DockItemKind::SmallSpacer => Some("small-spacer"),
+    }
+}
+
 pub(crate) fn find_dockutil_path() -> Option<String> {
     let candidates = ["/opt/homebrew/bin/dockutil", "/usr/local/bin/dockutil"];
     for path in &candidates {
@@ -56,21 +97,295 @@ pub(crate) fn find_dockutil_path() -> Option<String> {
     None
 }
 
+const DOCKUTIL_MISSING: &str = "dockutil not found. Install it with: brew install dockutil";
+
+fn bundled_dockutil_path(resource_dir: &Path) -> Option<String> {
+    let bundled_path = resource_dir.join("resources").join("dockutil");
+    bundled_path
+        .exists()
+        .then(|| bundled_path.to_string_lossy().to_string())
+}
+
 pub(crate) fn get_dockutil_path(app: &tauri::AppHandle) -> Result<String, String> {
-    if let Ok(resource_dir) = app.path().resource_dir() {
-        let bundled_path = resource_dir.join("resources").join("dockutil");
-        if bundled_path.exists() {
-            return Ok(bundled_path.to_string_lossy().to_string());
+    app.path()
+        .resource_dir()
+        .ok()
+        .and_then(|resource_dir| bundled_dockutil_path(&resource_dir))
+        .or_else(find_dockutil_path)
+        .ok_or_else(|| DOCKUTIL_MISSING.to_string())
+}
+
+/// Finds dockutil without an app handle, looking next to the running executable
+/// inside the app bundle before falling back to Homebrew.
+pub(crate) fn locate_dockutil() -> Result<String, String> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.parent()?.join("Resources")))
+        .and_then(|resource_dir| bundled_dockutil_path(&resource_dir))
+        .or_else(find_dockutil_path)
+        .ok_or_else(|| DOCKUTIL_MISSING.to_string())
+}
+
+pub(crate) fn list_dock_apps(
+    dockutil: &str,
+    catalog: &mut AppCatalog,
+) -> Result<Vec<AppEntry>, String> {
+    let output = std::process::Command::new(dockutil)
+        .arg("--list")
+        .output()
+        .map_err(|e| format!("Failed to run dockutil: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "dockutil --list failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut apps = parse_dockutil_output(&stdout);
+    // dockutil reports the Dock's label, so prefer the name the app shows in Finder.
+    for entry in &mut apps {
+        let path = Path::new(&entry.path);
+        let Ok(app) = catalog.app(path) else {
+            continue;
+        };
+        entry.name = localized_name(path, preferred_languages()).unwrap_or(app.name);
+        entry.bundle_id = entry.bundle_id.take().or(app.bundle_id);
+        entry.version = app.version;
+    }
+    Ok(apps)
+}
+
+pub(crate) fn restart_dock() -> Result<(), String> {
+    std::process::Command::new("killall")
+        .arg("Dock")
+        .output()
+        .map_err(|e| format!("Failed to restart Dock: {}", e))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ApplyStage {
+    Clearing,
+    Adding,
+    Restarting,
+    Finished,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub(crate) struct ApplyProgress {
+    pub profile_id: String,
+    pub stage: ApplyStage,
+    pub step: usize,
+    pub total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DockApplyOutcome {
+    pub warnings: Vec<String>,
+    pub relocated: Vec<(String, String)>,
+}
+
+/// Replaces the Dock with `profile`, asking `resolve` for a new location when an
+/// app is missing from its saved path and reporting each step to `progress`.
+pub(crate) fn apply_dock_profile(
+    dockutil: &str,
+    profile: &Profile,
+    mut resolve: impl FnMut(&str) -> Option<String>,
+    mut progress: impl FnMut(ApplyProgress),
+) -> Result<DockApplyOutcome, String> {
+    let total = profile.apps.len();
+    let mut report = |stage, step, detail: Option<&str>| {
+        progress(ApplyProgress {
+            profile_id: profile.id.clone(),
+            stage,
+            step,
+            total,
+            detail: detail.map(str::to_string),
+        })
+    };
+
+    report(ApplyStage::Clearing, 0, None);
+    let previous_locks = RestoreLocks::current();
+    unlock_dock()?;
+
+    let remove_output = std::process::Command::new(dockutil)
+        .args(["--remove", "all", "--no-restart"])
+        .output()
+        .map_err(|e| format!("Failed to remove dock items: {}", e))?;
+
+    if !remove_output.status.success() {
+        return Err(format!(
+            "dockutil --remove failed: {}",
+            String::from_utf8_lossy(&remove_output.stderr)
+        ));
+    }
+
+    let mut outcome = DockApplyOutcome::default();
+    for (index, entry) in profile.apps.iter().enumerate() {
+        if let Some(tile_type) = dockutil_spacer_type(entry.kind) {
+            report(ApplyStage::Adding, index + 1, Some(tile_type));
+            let add_output = std::process::Command::new(dockutil)
+                .args([
+                    "--add",
+                    "",
+                    "--type",
+                    tile_type,
+                    "--section",
+                    "apps",
+                    "--no-restart",
+                ])
+                .output()
+                .map_err(|e| format!("Failed to add {}: {}", tile_type, e))?;
+            if !add_output.status.success() {
+                outcome.warnings.push(format!(
+                    "Failed to add {}: {}",
+                    tile_type,
+                    String::from_utf8_lossy(&add_output.stderr)
+                ));
+            }
+            continue;
+        }
+        let path = if Path::new(&entry.path).exists() {
+            entry.path.clone()
+        } else {
+            match entry.bundle_id.as_deref().and_then(&mut resolve) {
+                Some(path) => {
+                    outcome.relocated.push((entry.path.clone(), path.clone()));
+                    path
+                }
+                None => {
+                    outcome
+                        .warnings
+                        .push(format!("{} not found at {}", entry.name, entry.path));
+                    continue;
+                }
+            }
+        };
+        report(ApplyStage::Adding, index + 1, Some(&entry.name));
+        let add_output = std::process::Command::new(dockutil)
+            .args(["--add", &path, "--no-restart"])
+            .output()
+            .map_err(|e| format!("Failed to add {}: {}", entry.name, e))?;
+
+        if !add_output.status.success() {
+            outcome.warnings.push(format!(
+                "Failed to add {}: {}",
+                entry.name,
+                String::from_utf8_lossy(&add_output.stderr)
+            ));
         }
     }
 
-    find_dockutil_path()
-        .ok_or_else(|| "dockutil not found. Install it with: brew install dockutil".to_string())
+    if profile.locks.any() {
+        write_dock_locks(&profile.locks)?;
+    }
+    report(ApplyStage::Restarting, total, None);
+    restart_dock()?;
+    previous_locks.disarm();
+    Ok(outcome)
+}
+
+pub(crate) fn update_relocated_paths(profile: &mut Profile, relocated: &[(String, String)]) {
+    if relocated.is_empty() {
+        return;
+    }
+    for entry in profile.apps.iter_mut() {
+        if let Some((_, new_path)) = relocated.iter().find(|(old, _)| old == &entry.path) {
+            entry.path = new_path.clone();
+        }
+    }
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bundle::fixtures::{make_bundle, temp_dir};
+    use std::fs;
+
+    fn entry(path: &str, bundle_id: Option<&str>) -> AppEntry {
+        AppEntry {
+            name: "App".to_string(),
+            path: path.to_string(),
+            icon: None,
+            bundle_id: bundle_id.map(str::to_string),
+            version: None,
+            kind: DockItemKind::App,
+        }
+    }
+
+    fn catalog() -> AppCatalog {
+        AppCatalog::new(Vec::new(), None)
+    }
+
+    #[test]
+    fn app_entry_from_bundle_reads_metadata() {
+        let root = temp_dir("dockswitcher-dock");
+        let app = root.join("code.app");
+        make_bundle(
+            &app,
+            &[
+                ("CFBundleIdentifier", "com.microsoft.VSCode"),
+                ("CFBundleName", "Code"),
+                ("CFBundleDisplayName", "Visual Studio Code"),
+                ("CFBundleShortVersionString", "1.96.0"),
+                ("CFBundlePackageType", "APPL"),
+            ],
+        );
+
+        let entry =
+            app_entry_from_bundle(&mut catalog(), &format!("{}/", app.to_string_lossy())).unwrap();
+        assert_eq!(entry.name, "Visual Studio Code");
+        assert_eq!(entry.path, app.to_string_lossy());
+        assert_eq!(entry.bundle_id.as_deref(), Some("com.microsoft.VSCode"));
+        assert_eq!(entry.version.as_deref(), Some("1.96.0"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn app_entry_from_bundle_falls_back_to_file_stem() {
+        let root = temp_dir("dockswitcher-dock");
+        let app = root.join("Unnamed.app");
+        make_bundle(&app, &[]);
+
+        let entry = app_entry_from_bundle(&mut catalog(), &app.to_string_lossy()).unwrap();
+        assert_eq!(entry.name, "Unnamed");
+        assert_eq!(entry.bundle_id, None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn app_entry_from_bundle_rejects_non_bundles() {
+        let result = app_entry_from_bundle(&mut catalog(), "/Applications/DefinitelyMissing.app");
+        assert!(matches!(result, Err(BundleError::NotFound(_))));
+    }
+
+    #[test]
+    fn find_duplicate_app_matches_path_or_bundle_id() {
+        let apps = vec![
+            entry("/Applications/Safari.app", Some("com.apple.Safari")),
+            entry("/Applications/Tool.app", None),
+        ];
+
+        assert!(find_duplicate_app(&apps, &entry("/Applications/Safari.app", None)).is_some());
+        assert!(find_duplicate_app(
+            &apps,
+            &entry(
+                "/Users/test/Applications/Safari.app",
+                Some("com.apple.Safari")
+            )
+        )
+        .is_some());
+        assert!(find_duplicate_app(&apps, &entry("/Applications/Other.app", None)).is_none());
+    }
 
     #[test]
     fn parse_dockutil_standard_output() {
@@ -175,4 +490,14 @@ mod tests {
     fn percent_decode_invalid_hex() {
         assert_eq!(percent_decode("test%ZZ"), "test%ZZ");
     }
+
+    #[test]
+    fn dockutil_spacer_type_maps_spacer_kinds() {
+        assert_eq!(dockutil_spacer_type(DockItemKind::App), None);
+        assert_eq!(dockutil_spacer_type(DockItemKind::Spacer), Some("spacer"));
+        assert_eq!(
+            dockutil_spacer_type(DockItemKind::SmallSpacer),
+            Some("small-spacer")
+        );
+    }
 }
@@ -0,0 +1,566 @@
+use crate::catalog::{AppCatalog, CatalogApp};
+use crate::commands::dock::{apply_app_list, apply_profile, restore_app_list, snapshot_dock};
+use crate::dock::app_entry_from_bundle;
+use crate::signals::expand_path;
+use crate::store::{AppEntry, DirectorySession, Profile, Restore, Session, Store};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+pub(crate) const DOCKPROFILE_FILE: &str = ".dockprofile";
+/// Waits for the shell to settle, so `cd`-ing through folders only switches once.
+pub(crate) const DIRECTORY_DEBOUNCE: Duration = Duration::from_millis(750);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DockProfileSpec {
+    /// A profile name or id.
+    Profile(String),
+    /// App names, bundle ids or paths.
+    Apps(Vec<String>),
+}
+
+/// A single line names a profile, unless it looks like an app (`Xcode.app`
+/// or a path). Otherwise every line is an app. Blank lines and `#` comments
+/// are skipped.
+pub(crate) fn parse_dockprofile(text: &str) -> Result<DockProfileSpec, String> {
+    let lines: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    match lines.as_slice() {
+        [] => Err(format!("{} is empty", DOCKPROFILE_FILE)),
+        [line] if !line.ends_with(".app") && !line.contains('/') => {
+            Ok(DockProfileSpec::Profile(line.clone()))
+        }
+        _ => Ok(DockProfileSpec::Apps(lines)),
+    }
+}
+
+/// The nearest `.dockprofile` in `dir` or one of its parents.
+pub(crate) fn find_dockprofile(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join(DOCKPROFILE_FILE))
+        .find(|file| file.is_file())
+}
+
+/// The folder holding the nearest `.dockprofile` and what it asks for.
+pub(crate) fn read_dockprofile(dir: &Path) -> Option<Result<(PathBuf, DockProfileSpec), String>> {
+    let file = find_dockprofile(dir)?;
+    let root = file.parent()?.to_path_buf();
+    Some(
+        std::fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))
+            .and_then(|text| parse_dockprofile(&text))
+            .map(|spec| (root, spec)),
+    )
+}
+
+/// A path, relative to the `.dockprofile` folder, or the bundle id or name of
+/// an installed app.
+pub(crate) fn resolve_inline_app(line: &str, root: &Path, apps: &[CatalogApp]) -> Option<String> {
+    if line.contains('/') {
+        return Some(root.join(expand_path(line)).to_string_lossy().to_string());
+    }
+    let name = line.strip_suffix(".app").unwrap_or(line);
+    apps.iter()
+        .find(|app| {
+            app.bundle_id
+                .as_deref()
+                .is_some_and(|bundle_id| bundle_id.eq_ignore_ascii_case(line))
+        })
+        .or_else(|| apps.iter().find(|app| app.name.eq_ignore_ascii_case(name)))
+        .map(|app| app.path.clone())
+}
+
+/// An unsaved profile, named after the project folder, for an inline app list.
+pub(crate) fn app_list_profile(
+    root: &Path,
+    lines: &[String],
+    catalog: &mut AppCatalog,
+) -> Result<Profile, String> {
+    let apps = catalog.apps();
+    let name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| DOCKPROFILE_FILE.to_string());
+    let mut profile = Profile::new(name);
+    for line in lines {
+        let path = resolve_inline_app(line, root, &apps)
+            .ok_or_else(|| format!("No installed app matches \"{}\"", line))?;
+        profile
+            .apps
+            .push(app_entry_from_bundle(catalog, &path).map_err(|e| e.to_string())?);
+    }
+    Ok(profile)
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum DirectoryTick {
+    Idle,
+    Changed,
+    Apply(String),
+    ApplyApps(PathBuf, Vec<String>),
+    RestoreApps(Vec<AppEntry>),
+}
+
+/// Enters, switches or leaves the project in `store` for a shell's new
+/// working directory. Moving around inside a project does nothing. Leaving it
+/// brings back the profile that was active before, unless someone switched
+/// profiles in the meantime.
+pub(crate) fn directory_tick(
+    store: &mut Store,
+    found: Option<(PathBuf, DockProfileSpec)>,
+) -> Result<DirectoryTick, String> {
+    if store.data.automation_paused {
+        return Ok(DirectoryTick::Idle);
+    }
+    let Some((root, spec)) = found else {
+        let Some(ended) = store.data.directory_session.take() else {
+            return Ok(DirectoryTick::Idle);
+        };
+        return Ok(match store.end_session(ended.session) {
+            Restore::Nothing => DirectoryTick::Changed,
+            Restore::Profile(id) => DirectoryTick::Apply(id),
+            Restore::Apps(apps) => DirectoryTick::RestoreApps(apps),
+        });
+    };
+    let root_key = root.to_string_lossy().to_string();
+    if store
+        .data
+        .directory_session
+        .as_ref()
+        .is_some_and(|session| session.root == root_key)
+    {
+        return Ok(DirectoryTick::Idle);
+    }
+    let (profile_id, tick) = match spec {
+        DockProfileSpec::Profile(query) => {
+            let id = store.data.profiles[store.find_profile_index(&query)?]
+                .id
+                .clone();
+            let tick = if store.data.active_profile_id.as_ref() == Some(&id) {
+                DirectoryTick::Changed
+            } else {
+                DirectoryTick::Apply(id.clone())
+            };
+            (Some(id), tick)
+        }
+        DockProfileSpec::Apps(apps) => (None, DirectoryTick::ApplyApps(root, apps)),
+    };
+    let replacing = store.data.directory_session.take().map(|s| s.session);
+    let session = store.start_session(profile_id, replacing);
+    store.data.directory_session = Some(DirectorySession {
+        root: root_key,
+        session,
+    });
+    Ok(tick)
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+const ZSH_HOOK: &str = r#"_dockswitcher_hook() {
+  {program} report-dir "$PWD" >/dev/null 2>&1 &!
+}
+typeset -ag chpwd_functions
+if (( ! ${chpwd_functions[(I)_dockswitcher_hook]} )); then
+  chpwd_functions=(_dockswitcher_hook $chpwd_functions)
+fi
+_dockswitcher_hook
+"#;
+
+const BASH_HOOK: &str = r#"_dockswitcher_hook() {
+  if [[ "$PWD" != "${_dockswitcher_pwd:-}" ]]; then
+    _dockswitcher_pwd="$PWD"
+    ({program} report-dir "$PWD" >/dev/null 2>&1 &)
+  fi
+}
+if [[ ";${PROMPT_COMMAND:-};" != *";_dockswitcher_hook;"* ]]; then
+  PROMPT_COMMAND="_dockswitcher_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+fi
+"#;
+
+const FISH_HOOK: &str = r#"function _dockswitcher_hook --on-variable PWD
+    {program} report-dir "$PWD" >/dev/null 2>&1 &
+    disown 2>/dev/null
+end
+_dockswitcher_hook
+"#;
+
+/// Shell code that reports every change of directory to `program` in the
+/// background, so the prompt never waits on the Dock.
+pub(crate) fn zsh_hook(program: &Path) -> String {
+    ZSH_HOOK.replace("{program}", &shell_quote(&program.to_string_lossy()))
+}
+
+pub(crate) fn bash_hook(program: &Path) -> String {
+    BASH_HOOK.replace("{program}", &shell_quote(&program.to_string_lossy()))
+}
+
+pub(crate) fn fish_hook(program: &Path) -> String {
+    FISH_HOOK.replace("{program}", &shell_quote(&program.to_string_lossy()))
+}
+
+#[derive(Default)]
+pub(crate) struct DirectoryReports {
+    latest: AtomicU64,
+    running: Mutex<()>,
+}
+
+fn directory_session(store: &mut Store) -> Option<&mut Session> {
+    store
+        .data
+        .directory_session
+        .as_mut()
+        .map(|directory| &mut directory.session)
+}
+
+/// Whether `tick` is about to replace a Dock that no profile can bring back.
+pub(crate) fn needs_dock_snapshot(store: &Store, tick: &DirectoryTick) -> bool {
+    matches!(tick, DirectoryTick::Apply(_) | DirectoryTick::ApplyApps(..))
+        && store
+            .data
+            .directory_session
+            .as_ref()
+            .is_some_and(|directory| directory.session.needs_dock_snapshot())
+}
+
+fn handle_directory(app: &AppHandle, dir: &Path) {
+    let found = match read_dockprofile(dir).transpose() {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    let (tick, snapshot) = {
+        let state = app.state::<Mutex<Store>>();
+        let Ok(mut store) = state.lock() else {
+            return;
+        };
+        let tick = match directory_tick(&mut store, found) {
+            Ok(tick) => tick,
+            Err(e) => {
+                eprintln!("Failed to switch for {}: {}", dir.display(), e);
+                return;
+            }
+        };
+        if tick != DirectoryTick::Idle {
+            if let Err(e) = store.save() {
+                eprintln!("Failed to save directory state: {}", e);
+            }
+        }
+        let snapshot = needs_dock_snapshot(&store, &tick);
+        (tick, snapshot)
+    };
+    if snapshot {
+        if let Err(e) = snapshot_dock(app, directory_session) {
+            eprintln!("Failed to save the Dock before {}: {}", dir.display(), e);
+        }
+    }
+    let result = match tick {
+        DirectoryTick::Apply(id) => apply_profile(id, app.clone(), app.state::<Mutex<Store>>()),
+        DirectoryTick::ApplyApps(root, lines) => {
+            let profile = {
+                let catalog = app.state::<Mutex<AppCatalog>>();
+                let Ok(mut catalog) = catalog.lock() else {
+                    return;
+                };
+                catalog.refresh_if_stale();
+                app_list_profile(&root, &lines, &mut catalog)
+            };
+            profile.and_then(|profile| apply_app_list(app, &profile))
+        }
+        DirectoryTick::RestoreApps(apps) => restore_app_list(app, apps),
+        DirectoryTick::Idle | DirectoryTick::Changed => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!(
+            "Failed to apply {} for {}: {}",
+            DOCKPROFILE_FILE,
+            dir.display(),
+            e
+        );
+    }
+}
+
+/// Handles a shell's new working directory once no newer one has arrived for
+/// a moment.
+pub(crate) fn report_directory(app: &AppHandle, dir: PathBuf) -> Result<(), String> {
+    if !dir.is_absolute() {
+        return Err(format!("\"{}\" is not an absolute path", dir.display()));
+    }
+    let reports = app.state::<DirectoryReports>();
+    let report = reports.latest.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(DIRECTORY_DEBOUNCE);
+        let reports = app.state::<DirectoryReports>();
+        if reports.latest.load(Ordering::SeqCst) != report {
+            return;
+        }
+        let Ok(_running) = reports.running.lock() else {
+            return;
+        };
+        handle_directory(&app, &dir);
+    });
+    Ok(())
+}
+
+/// Debounces reports from CLI runs while the app isn't running: each run
+/// records itself as the newest report in `state_dir` and, after `delay`, only
+/// carries on if no newer run has replaced it. The returned file holds a lock
+/// so runs that do carry on don't switch the Dock at the same time.
+pub(crate) fn claim_latest_report(
+    state_dir: &Path,
+    delay: Duration,
+) -> Result<Option<std::fs::File>, String> {
+    let latest = state_dir.join("directory-report");
+    let token = uuid::Uuid::new_v4().to_string();
+    let staged = state_dir.join(format!("directory-report.{}", token));
+    std::fs::create_dir_all(state_dir).map_err(|e| e.to_string())?;
+    std::fs::write(&staged, &token).map_err(|e| e.to_string())?;
+    std::fs::rename(&staged, &latest).map_err(|e| e.to_string())?;
+
+    std::thread::sleep(delay);
+    if std::fs::read_to_string(&latest).ok() != Some(token) {
+        return Ok(None);
+    }
+    let lock = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(state_dir.join("directory-report.lock"))
+        .map_err(|e| e.to_string())?;
+    lock.lock().map_err(|e| e.to_string())?;
+    Ok(Some(lock))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::temp_dir;
+    use crate::store::fixtures::store_with_profiles;
+
+    fn catalog_app(name: &str, bundle_id: &str) -> CatalogApp {
+        CatalogApp {
+            name: name.to_string(),
+            path: format!("/Applications/{}.app", name),
+            bundle_id: Some(bundle_id.to_string()),
+            version: None,
+            category: None,
+            icon_file: None,
+        }
+    }
+
+    fn project(root: &str, spec: DockProfileSpec) -> Option<(PathBuf, DockProfileSpec)> {
+        Some((PathBuf::from(root), spec))
+    }
+
+    #[test]
+    fn parse_dockprofile_reads_a_profile_or_an_app_list() {
+        assert_eq!(
+            parse_dockprofile("# Dock for this repo\n\nWork\n"),
+            Ok(DockProfileSpec::Profile("Work".to_string()))
+        );
+        assert_eq!(
+            parse_dockprofile("Xcode.app\n"),
+            Ok(DockProfileSpec::Apps(vec!["Xcode.app".to_string()]))
+        );
+        assert_eq!(
+            parse_dockprofile("Safari\ncom.tinyspeck.slackmacgap\n"),
+            Ok(DockProfileSpec::Apps(vec![
+                "Safari".to_string(),
+                "com.tinyspeck.slackmacgap".to_string()
+            ]))
+        );
+        assert!(parse_dockprofile("# nothing\n").is_err());
+    }
+
+    #[test]
+    fn find_dockprofile_uses_the_nearest_parent() {
+        let root = temp_dir("dockswitcher-dockprofile");
+        let nested = root.join("app").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(DOCKPROFILE_FILE), "Work").unwrap();
+
+        assert_eq!(find_dockprofile(&nested), Some(root.join(DOCKPROFILE_FILE)));
+
+        std::fs::write(root.join("app").join(DOCKPROFILE_FILE), "Xcode.app").unwrap();
+        let (found, spec) = read_dockprofile(&nested).unwrap().unwrap();
+        assert_eq!(found, root.join("app"));
+        assert_eq!(spec, DockProfileSpec::Apps(vec!["Xcode.app".to_string()]));
+    }
+
+    #[test]
+    fn resolve_inline_app_matches_paths_bundle_ids_and_names() {
+        let apps = [
+            catalog_app("Safari", "com.apple.Safari"),
+            catalog_app("Slack", "com.tinyspeck.slackmacgap"),
+        ];
+        let root = Path::new("/Users/me/repo");
+
+        assert_eq!(
+            resolve_inline_app("com.tinyspeck.slackmacgap", root, &apps),
+            Some("/Applications/Slack.app".to_string())
+        );
+        assert_eq!(
+            resolve_inline_app("safari.app", root, &apps),
+            Some("/Applications/Safari.app".to_string())
+        );
+        assert_eq!(
+            resolve_inline_app("tools/Builder.app", root, &apps),
+            Some("/Users/me/repo/tools/Builder.app".to_string())
+        );
+        assert_eq!(resolve_inline_app("Xcode", root, &apps), None);
+    }
+
+    #[test]
+    fn directory_tick_applies_on_entering_and_restores_on_leaving() {
+        let mut store = store_with_profiles(&["home", "work"]);
+        store.data.active_profile_id = Some("home".to_string());
+        let repo = || {
+            project(
+                "/Users/me/repo",
+                DockProfileSpec::Profile("WORK".to_string()),
+            )
+        };
+
+        assert_eq!(
+            directory_tick(&mut store, repo()),
+            Ok(DirectoryTick::Apply("work".to_string()))
+        );
+        store.data.active_profile_id = Some("work".to_string());
+        // Moving around inside the project.
+        assert_eq!(directory_tick(&mut store, repo()), Ok(DirectoryTick::Idle));
+
+        assert_eq!(
+            directory_tick(&mut store, None),
+            Ok(DirectoryTick::Apply("home".to_string()))
+        );
+        assert_eq!(store.data.directory_session, None);
+        assert_eq!(directory_tick(&mut store, None), Ok(DirectoryTick::Idle));
+    }
+
+    #[test]
+    fn directory_tick_restores_the_dock_snapshot_when_no_profile_was_active() {
+        let mut store = store_with_profiles(&["work"]);
+        let apps = vec!["Xcode.app".to_string()];
+        let repo = project("/Users/me/repo", DockProfileSpec::Apps(apps.clone()));
+
+        let tick = directory_tick(&mut store, repo).unwrap();
+        assert_eq!(
+            tick,
+            DirectoryTick::ApplyApps(PathBuf::from("/Users/me/repo"), apps)
+        );
+        assert!(needs_dock_snapshot(&store, &tick));
+        let dock = vec![AppEntry {
+            name: "Finder".to_string(),
+            path: "/System/Library/CoreServices/Finder.app".to_string(),
+            icon: None,
+            bundle_id: None,
+            version: None,
+            kind: crate::store::DockItemKind::App,
+        }];
+        directory_session(&mut store).unwrap().previous_apps = dock.clone();
+        assert!(!needs_dock_snapshot(&store, &tick));
+
+        assert_eq!(
+            directory_tick(&mut store, None),
+            Ok(DirectoryTick::RestoreApps(dock))
+        );
+    }
+
+    #[test]
+    fn directory_tick_keeps_the_original_profile_across_projects() {
+        let mut store = store_with_profiles(&["home", "work"]);
+        store.data.active_profile_id = Some("home".to_string());
+
+        directory_tick(
+            &mut store,
+            project(
+                "/Users/me/api",
+                DockProfileSpec::Profile("work".to_string()),
+            ),
+        )
+        .unwrap();
+        store.data.active_profile_id = Some("work".to_string());
+        let apps = vec!["Xcode.app".to_string()];
+        assert_eq!(
+            directory_tick(
+                &mut store,
+                project("/Users/me/ios", DockProfileSpec::Apps(apps.clone()))
+            ),
+            Ok(DirectoryTick::ApplyApps(
+                PathBuf::from("/Users/me/ios"),
+                apps
+            ))
+        );
+        // Applying an app list leaves no profile active.
+        store.data.active_profile_id = None;
+        assert_eq!(
+            directory_tick(&mut store, None),
+            Ok(DirectoryTick::Apply("home".to_string()))
+        );
+    }
+
+    #[test]
+    fn directory_tick_leaves_a_manual_switch_alone() {
+        let mut store = store_with_profiles(&["home", "work", "music"]);
+        store.data.active_profile_id = Some("home".to_string());
+
+        directory_tick(
+            &mut store,
+            project(
+                "/Users/me/repo",
+                DockProfileSpec::Profile("work".to_string()),
+            ),
+        )
+        .unwrap();
+        store.data.active_profile_id = Some("music".to_string());
+        assert_eq!(directory_tick(&mut store, None), Ok(DirectoryTick::Changed));
+
+        let missing = project(
+            "/Users/me/other",
+            DockProfileSpec::Profile("Travel".to_string()),
+        );
+        assert!(directory_tick(&mut store, missing).is_err());
+        assert_eq!(store.data.directory_session, None);
+    }
+
+    #[test]
+    fn hooks_report_the_directory_through_the_quoted_program() {
+        let program = Path::new("/Applications/Dock Switcher's.app/Contents/MacOS/DockSwitcher");
+        let quoted = r#"'/Applications/Dock Switcher'\''s.app/Contents/MacOS/DockSwitcher' report-dir "$PWD""#;
+
+        assert!(zsh_hook(program).contains(quoted));
+        assert!(zsh_hook(program).contains("chpwd_functions"));
+        assert!(bash_hook(program).contains(quoted));
+        assert!(bash_hook(program).contains("PROMPT_COMMAND"));
+        assert!(fish_hook(program).contains(quoted));
+        assert!(fish_hook(program).contains("--on-variable PWD"));
+    }
+
+    #[test]
+    fn claim_latest_report_lets_only_the_newest_report_through() {
+        let state_dir = temp_dir("dockswitcher-reports");
+        let earlier = {
+            let state_dir = state_dir.clone();
+            std::thread::spawn(move || {
+                claim_latest_report(&state_dir, Duration::from_millis(300))
+                    .unwrap()
+                    .is_some()
+            })
+        };
+        std::thread::sleep(Duration::from_millis(100));
+        let newest = claim_latest_report(&state_dir, Duration::from_millis(300)).unwrap();
+
+        assert!(!earlier.join().unwrap());
+        assert!(newest.is_some());
+    }
+}
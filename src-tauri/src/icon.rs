@@ -1,75 +1,254 @@
-use base64::engine::general_purpose::STANDARD;
-use base64::Engine;
-use icns::{IconFamily, IconType};
-
-pub(crate) fn extract_app_icon(app_path: &str) -> Option<String> {
-    let info_plist_path = std::path::Path::new(app_path)
-        .join("Contents")
-        .join("Info.plist");
-    let info_plist_file = std::fs::File::open(&info_plist_path).ok()?;
-    let info_plist = plist::Value::from_reader(info_plist_file).ok()?;
-
-    let icon_name = info_plist
-        .as_dictionary()
-        .and_then(|dict| dict.get("CFBundleIconFile"))
-        .and_then(|value| value.as_string())
-        .map(|value| value.to_string());
-
-    let resources_dir = std::path::Path::new(app_path)
-        .join("Contents")
-        .join("Resources");
-
-    let icon_path = if let Some(name) = icon_name {
-        let file_name = if name.ends_with(".icns") {
-            name
-        } else {
-            format!("{}.icns", name)
-        };
-        let candidate = resources_dir.join(&file_name);
-        if candidate.exists() {
-            Some(candidate)
+use icns::{IconFamily, Image, PixelFormat};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+pub(crate) const DEFAULT_ICON_SIZE: u32 = 128;
+
+const FALLBACK_ICON_NAMES: [&str; 3] = ["AppIcon", "electron", "icon"];
+
+struct BundleLayout {
+    info_plist: PathBuf,
+    resources_dir: PathBuf,
+}
+
+fn bundle_layout(app_path: &Path) -> Option<BundleLayout> {
+    let contents = app_path.join("Contents");
+    if contents.join("Info.plist").exists() || contents.join("Resources").is_dir() {
+        return Some(BundleLayout {
+            info_plist: contents.join("Info.plist"),
+            resources_dir: contents.join("Resources"),
+        });
+    }
+
+    // iOS apps running on a Mac wrap a flat iOS bundle in Wrapper/<Name>.app.
+    let mut wrapped = vec![app_path.join("WrappedBundle")];
+    if let Ok(entries) = std::fs::read_dir(app_path.join("Wrapper")) {
+        let mut inner: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "app"))
+            .collect();
+        inner.sort();
+        wrapped.extend(inner);
+    }
+    wrapped
+        .into_iter()
+        .find(|inner| inner.join("Info.plist").exists())
+        .map(|inner| BundleLayout {
+            info_plist: inner.join("Info.plist"),
+            resources_dir: inner,
+        })
+}
+
+fn push_string(names: &mut Vec<String>, value: Option<&plist::Value>) {
+    if let Some(name) = value.and_then(|value| value.as_string()) {
+        names.push(name.to_string());
+    }
+}
+
+fn icon_names(info_plist: &plist::Dictionary) -> Vec<String> {
+    let mut names = Vec::new();
+    push_string(&mut names, info_plist.get("CFBundleIconFile"));
+    push_string(&mut names, info_plist.get("CFBundleIconName"));
+
+    for key in ["CFBundleIcons", "CFBundleIcons~ipad"] {
+        let primary = info_plist
+            .get(key)
+            .and_then(|value| value.as_dictionary())
+            .and_then(|icons| icons.get("CFBundlePrimaryIcon"));
+        if let Some(primary) = primary.and_then(|value| value.as_dictionary()) {
+            push_string(&mut names, primary.get("CFBundleIconName"));
+            if let Some(files) = primary
+                .get("CFBundleIconFiles")
+                .and_then(|value| value.as_array())
+            {
+                // Icon file lists go from smallest to largest, so try the largest first.
+                for file in files.iter().rev() {
+                    push_string(&mut names, Some(file));
+                }
+            }
         } else {
-            None
+            push_string(&mut names, primary);
         }
-    } else {
-        None
+    }
+
+    names.extend(FALLBACK_ICON_NAMES.iter().map(|name| name.to_string()));
+    names
+}
+
+fn strip_icon_extension(name: &str) -> &str {
+    name.strip_suffix(".icns")
+        .or_else(|| name.strip_suffix(".png"))
+        .unwrap_or(name)
+}
+
+fn png_variants(resources_dir: &Path, base: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(resources_dir) else {
+        return Vec::new();
     };
+    let mut variants: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                return false;
+            };
+            file_name
+                .strip_suffix(".png")
+                .and_then(|stem| stem.strip_prefix(base))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['@', '~']))
+        })
+        .collect();
+    variants.sort();
+    variants
+}
 
-    let fallback_path = resources_dir.join("AppIcon.icns");
-    let icns_path = if let Some(path) = icon_path {
-        path
-    } else if fallback_path.exists() {
-        fallback_path
-    } else {
-        return None;
+/// Icon files grouped by base name in priority order, so every size of one
+/// icon (`AppIcon.icns`, `AppIcon.png`, `AppIcon@2x.png`) is weighed together.
+fn icon_source_groups(app_path: &Path) -> Vec<Vec<PathBuf>> {
+    let Some(layout) = bundle_layout(app_path) else {
+        return Vec::new();
     };
+    let info_plist = plist::Value::from_file(&layout.info_plist)
+        .ok()
+        .and_then(plist::Value::into_dictionary)
+        .unwrap_or_default();
 
-    let icon_family = IconFamily::read(std::fs::File::open(icns_path).ok()?).ok()?;
+    let mut seen: Vec<PathBuf> = Vec::new();
+    let mut groups = Vec::new();
+    for name in icon_names(&info_plist) {
+        let base = strip_icon_extension(&name);
+        let icns_path = layout.resources_dir.join(format!("{}.icns", base));
+        let mut candidates = Vec::new();
+        if icns_path.is_file() {
+            candidates.push(icns_path);
+        }
+        candidates.extend(png_variants(&layout.resources_dir, base));
+        candidates.retain(|candidate| !seen.contains(candidate));
+        seen.extend(candidates.iter().cloned());
+        if !candidates.is_empty() {
+            groups.push(candidates);
+        }
+    }
+    groups
+}
 
-    let preferred_types = [
-        IconType::RGBA32_128x128,
-        IconType::RGBA32_64x64,
-        IconType::RGBA32_256x256,
-        IconType::RGBA32_32x32,
-    ];
+pub(crate) fn icon_sources(app_path: &Path) -> Vec<PathBuf> {
+    icon_source_groups(app_path).into_iter().flatten().collect()
+}
 
-    for icon_type in preferred_types {
-        if let Ok(icon) = icon_family.get_icon_with_type(icon_type) {
-            let mut png_bytes = Vec::new();
-            if icon.write_png(&mut png_bytes).is_ok() {
-                return Some(STANDARD.encode(png_bytes));
+fn decode_source(path: &Path) -> Vec<Image> {
+    let Ok(bytes) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    if path.extension().is_some_and(|ext| ext == "png") {
+        return Image::read_png(Cursor::new(bytes))
+            .map(|image| vec![image.convert_to(PixelFormat::RGBA)])
+            .unwrap_or_default();
+    }
+
+    let Ok(family) = IconFamily::read(Cursor::new(bytes)) else {
+        return Vec::new();
+    };
+    family
+        .available_icons()
+        .into_iter()
+        .filter_map(|icon_type| family.get_icon_with_type(icon_type).ok())
+        .map(|image| image.convert_to(PixelFormat::RGBA))
+        .collect()
+}
+
+fn select_image(images: Vec<Image>, target: u32) -> Option<Image> {
+    let smallest_fit = images
+        .iter()
+        .enumerate()
+        .filter(|(_, image)| image.width() >= target)
+        .min_by_key(|(_, image)| image.width())
+        .map(|(index, _)| index);
+    let index = smallest_fit.or_else(|| {
+        images
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, image)| image.width())
+            .map(|(index, _)| index)
+    })?;
+    images.into_iter().nth(index)
+}
+
+pub(crate) fn resize_image(image: &Image, width: u32, height: u32) -> Image {
+    let source = image.convert_to(PixelFormat::RGBA);
+    if source.width() == width && source.height() == height {
+        return source;
+    }
+
+    let mut resized = Image::new(PixelFormat::RGBA, width, height);
+    let (src_width, src_height) = (source.width() as usize, source.height() as usize);
+    let src = source.data();
+    let dst = resized.data_mut();
+    for y in 0..height as usize {
+        let y0 = y * src_height / height as usize;
+        let y1 = ((y + 1) * src_height).div_ceil(height as usize).max(y0 + 1);
+        for x in 0..width as usize {
+            let x0 = x * src_width / width as usize;
+            let x1 = ((x + 1) * src_width).div_ceil(width as usize).max(x0 + 1);
+
+            // Average with premultiplied alpha so transparent pixels don't darken edges.
+            let mut sums = [0u64; 4];
+            let mut count = 0u64;
+            for sy in y0..y1.min(src_height) {
+                for sx in x0..x1.min(src_width) {
+                    let offset = (sy * src_width + sx) * 4;
+                    let alpha = src[offset + 3] as u64;
+                    sums[0] += src[offset] as u64 * alpha;
+                    sums[1] += src[offset + 1] as u64 * alpha;
+                    sums[2] += src[offset + 2] as u64 * alpha;
+                    sums[3] += alpha;
+                    count += 1;
+                }
+            }
+
+            let offset = (y * width as usize + x) * 4;
+            if let Some(alpha) = std::num::NonZeroU64::new(sums[3]) {
+                for channel in 0..3 {
+                    dst[offset + channel] = (sums[channel] / alpha) as u8;
+                }
+                dst[offset + 3] = (alpha.get() / count.max(1)) as u8;
             }
         }
     }
+    resized
+}
+
+pub(crate) fn load_app_icon_image(app_path: &str, size: u32, scale: u32) -> Option<Image> {
+    let target = size.max(1) * scale.max(1);
+    icon_source_groups(Path::new(app_path))
+        .iter()
+        .find_map(|group| {
+            let images = group
+                .iter()
+                .flat_map(|source| decode_source(source))
+                .collect();
+            select_image(images, target)
+        })
+        .map(|image| resize_image(&image, target, target))
+}
+
+pub(crate) fn extract_app_icon_sized(app_path: &str, size: u32, scale: u32) -> Option<Vec<u8>> {
+    let image = load_app_icon_image(app_path, size, scale)?;
+    let mut png_bytes = Vec::new();
+    image.write_png(&mut png_bytes).ok()?;
+    Some(png_bytes)
+}
 
-    None
+pub(crate) fn extract_app_icon(app_path: &str) -> Option<Vec<u8>> {
+    extract_app_icon_sized(app_path, DEFAULT_ICON_SIZE, 1)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::extract_app_icon;
-    use base64::engine::general_purpose::STANDARD;
-    use base64::Engine;
+    use super::*;
+    use crate::bundle::fixtures::{make_bundle, temp_dir};
+    use std::fs;
 
     fn find_test_app() -> Option<&'static str> {
         let candidates = [
@@ -83,6 +262,39 @@ mod tests {
             .find(|path| std::path::Path::new(path).exists())
     }
 
+    fn solid_image(size: u32, rgba: [u8; 4]) -> Image {
+        let data = rgba.repeat((size * size) as usize);
+        Image::from_data(PixelFormat::RGBA, size, size, data).unwrap()
+    }
+
+    fn write_icns(path: &Path, images: &[Image]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut family = IconFamily::new();
+        for image in images {
+            family.add_icon(image).unwrap();
+        }
+        family.write(fs::File::create(path).unwrap()).unwrap();
+    }
+
+    fn write_png(path: &Path, image: &Image) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        image.write_png(fs::File::create(path).unwrap()).unwrap();
+    }
+
+    fn decoded_size(png: &[u8]) -> (u32, u32) {
+        let image = Image::read_png(Cursor::new(png)).unwrap();
+        (image.width(), image.height())
+    }
+
+    fn resources(app: &Path) -> PathBuf {
+        app.join("Contents").join("Resources")
+    }
+
+    fn write_info_plist(path: &Path, dict: plist::Dictionary) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        plist::Value::Dictionary(dict).to_file_xml(path).unwrap();
+    }
+
     #[test]
     fn extract_app_icon_returns_some_for_known_app() {
         let app_path = match find_test_app() {
@@ -101,14 +313,179 @@ mod tests {
     }
 
     #[test]
-    fn extract_app_icon_returns_valid_png_base64() {
+    fn extract_app_icon_returns_valid_png() {
         let app_path = match find_test_app() {
             Some(path) => path,
             None => return,
         };
 
         let icon = extract_app_icon(app_path).expect("expected icon data");
-        let decoded = STANDARD.decode(icon).expect("expected valid base64");
-        assert!(decoded.starts_with(b"\x89PNG"));
+        assert!(icon.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn extract_app_icon_reads_cf_bundle_icon_file() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Legacy.app");
+        make_bundle(&app, &[("CFBundleIconFile", "Legacy")]);
+        write_icns(
+            &resources(&app).join("Legacy.icns"),
+            &[solid_image(128, [255, 0, 0, 255])],
+        );
+
+        let png = extract_app_icon(&app.to_string_lossy()).unwrap();
+        assert_eq!(decoded_size(&png), (128, 128));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn extract_app_icon_reads_cf_bundle_icon_name() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Modern.app");
+        make_bundle(&app, &[("CFBundleIconName", "ModernIcon")]);
+        write_icns(
+            &resources(&app).join("ModernIcon.icns"),
+            &[solid_image(256, [0, 255, 0, 255])],
+        );
+
+        let png = extract_app_icon(&app.to_string_lossy()).unwrap();
+        assert_eq!(decoded_size(&png), (128, 128));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn extract_app_icon_falls_back_to_electron_icns() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Electron.app");
+        make_bundle(&app, &[("CFBundleIconFile", "missing.icns")]);
+        write_icns(
+            &resources(&app).join("electron.icns"),
+            &[solid_image(32, [0, 0, 255, 255])],
+        );
+
+        let png = extract_app_icon_sized(&app.to_string_lossy(), 32, 1).unwrap();
+        assert_eq!(decoded_size(&png), (32, 32));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn extract_app_icon_reads_cf_bundle_icons_png_variants() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Phone.app");
+        let mut primary = plist::Dictionary::new();
+        primary.insert(
+            "CFBundleIconFiles".to_string(),
+            plist::Value::Array(vec![plist::Value::String("AppIcon60x60".to_string())]),
+        );
+        let mut icons = plist::Dictionary::new();
+        icons.insert(
+            "CFBundlePrimaryIcon".to_string(),
+            plist::Value::Dictionary(primary),
+        );
+        let mut info = plist::Dictionary::new();
+        info.insert("CFBundleIcons".to_string(), plist::Value::Dictionary(icons));
+        write_info_plist(&app.join("Contents").join("Info.plist"), info);
+        write_png(
+            &resources(&app).join("AppIcon60x60.png"),
+            &solid_image(60, [255, 0, 0, 255]),
+        );
+        write_png(
+            &resources(&app).join("AppIcon60x60@2x.png"),
+            &solid_image(120, [0, 0, 255, 255]),
+        );
+
+        let app_path = app.to_string_lossy();
+        let standard = load_app_icon_image(&app_path, 60, 1).unwrap();
+        assert_eq!(standard.width(), 60);
+        assert_eq!(&standard.data()[..4], &[255, 0, 0, 255]);
+        let retina = load_app_icon_image(&app_path, 60, 2).unwrap();
+        assert_eq!(retina.width(), 120);
+        assert_eq!(&retina.data()[..4], &[0, 0, 255, 255]);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn extract_app_icon_reads_ios_wrapper_bundles() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Game.app");
+        let inner = app.join("Wrapper").join("Game.app");
+        let mut info = plist::Dictionary::new();
+        info.insert(
+            "CFBundleIconFile".to_string(),
+            plist::Value::String("GameIcon.png".to_string()),
+        );
+        write_info_plist(&inner.join("Info.plist"), info);
+        write_png(
+            &inner.join("GameIcon@2x.png"),
+            &solid_image(64, [1, 2, 3, 255]),
+        );
+
+        let png = extract_app_icon_sized(&app.to_string_lossy(), 32, 2).unwrap();
+        assert_eq!(decoded_size(&png), (64, 64));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn extract_app_icon_selects_smallest_size_that_fits() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Sizes.app");
+        make_bundle(&app, &[]);
+        write_icns(
+            &resources(&app).join("AppIcon.icns"),
+            &[
+                solid_image(32, [255, 0, 0, 255]),
+                solid_image(256, [0, 0, 255, 255]),
+            ],
+        );
+
+        let app_path = app.to_string_lossy();
+        let small = load_app_icon_image(&app_path, 32, 1).unwrap();
+        assert_eq!(small.width(), 32);
+        assert_eq!(&small.data()[..4], &[255, 0, 0, 255]);
+
+        let large = load_app_icon_image(&app_path, 64, 2).unwrap();
+        assert_eq!(large.width(), 128);
+        assert_eq!(&large.data()[..4], &[0, 0, 255, 255]);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn icon_sources_list_candidates_in_priority_order() {
+        let root = temp_dir("dockswitcher-icon");
+        let app = root.join("Both.app");
+        make_bundle(&app, &[("CFBundleIconFile", "Custom")]);
+        write_icns(
+            &resources(&app).join("Custom.icns"),
+            &[solid_image(32, [0, 0, 0, 255])],
+        );
+        write_icns(
+            &resources(&app).join("AppIcon.icns"),
+            &[solid_image(32, [0, 0, 0, 255])],
+        );
+
+        assert_eq!(
+            icon_sources(&app),
+            vec![
+                resources(&app).join("Custom.icns"),
+                resources(&app).join("AppIcon.icns"),
+            ]
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn resize_image_averages_with_alpha() {
+        let mut image = solid_image(2, [0, 0, 0, 0]);
+        image.data_mut()[..4].copy_from_slice(&[200, 100, 50, 255]);
+
+        let resized = resize_image(&image, 1, 1);
+        assert_eq!(resized.data(), &[200, 100, 50, 63]);
     }
 }
@@ -0,0 +1,321 @@
+use crate::bundle::bundle_mtime;
+use crate::store::{AppEntry, Profile};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) const ICON_SCHEME: &str = "dsicon";
+
+pub(crate) fn is_icon_ref(value: &str) -> bool {
+    value.len() == 64
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+pub(crate) fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct IconCache {
+    dir: PathBuf,
+}
+
+impl IconCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn shared() -> Self {
+        Self::new(crate::store::Store::data_dir().join("icons"))
+    }
+
+    fn path_for(&self, icon_ref: &str) -> Option<PathBuf> {
+        is_icon_ref(icon_ref).then(|| self.dir.join(format!("{}.png", icon_ref)))
+    }
+
+    pub(crate) fn bundle_key(app_path: &Path) -> Option<String> {
+        let mtime = bundle_mtime(app_path)?;
+        Some(hex_digest(
+            format!("{}\0{}", app_path.to_string_lossy(), mtime).as_bytes(),
+        ))
+    }
+
+    pub fn cached_app_icon(&self, app_path: &str) -> Option<String> {
+        let key = Self::bundle_key(Path::new(app_path))?;
+        self.path_for(&key)?.exists().then_some(key)
+    }
+
+    pub fn app_icon(&self, app_path: &str) -> Option<String> {
+        if let Some(key) = self.cached_app_icon(app_path) {
+            return Some(key);
+        }
+        let key = Self::bundle_key(Path::new(app_path))?;
+        let png = crate::icon::extract_app_icon(app_path)?;
+        self.write(&key, &png)
+    }
+
+    pub(crate) fn write(&self, key: &str, png: &[u8]) -> Option<String> {
+        let path = self.path_for(key)?;
+        fs::create_dir_all(&self.dir).ok()?;
+        let tmp_path = path.with_extension("png.tmp");
+        fs::write(&tmp_path, png).ok()?;
+        fs::rename(&tmp_path, &path).ok()?;
+        Some(key.to_string())
+    }
+
+    pub fn import_inline(&self, base64_png: &str) -> Option<String> {
+        let png = STANDARD.decode(base64_png.trim()).ok()?;
+        let key = hex_digest(&png);
+        if self.path_for(&key)?.exists() {
+            return Some(key);
+        }
+        self.write(&key, &png)
+    }
+
+    pub fn read(&self, icon_ref: &str) -> Option<Vec<u8>> {
+        fs::read(self.path_for(icon_ref)?).ok()
+    }
+}
+
+pub(crate) const MAX_ICON_WORKERS: usize = 4;
+
+pub(crate) fn icon_worker_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .clamp(1, MAX_ICON_WORKERS)
+}
+
+pub(crate) fn load_icons_parallel<F>(
+    cache: &IconCache,
+    paths: &[String],
+    workers: usize,
+    on_icon: F,
+) where
+    F: Fn(&str, Option<String>) + Sync,
+{
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let workers = workers.clamp(1, paths.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                on_icon(path, cache.app_icon(path));
+            });
+        }
+    });
+}
+
+pub(crate) fn attach_cached_icons(apps: &mut [AppEntry], cache: &IconCache) -> Vec<String> {
+    let mut missing = Vec::new();
+    for entry in apps.iter_mut() {
+        entry.icon = cache.cached_app_icon(&entry.path);
+        if entry.icon.is_none() && !missing.contains(&entry.path) {
+            missing.push(entry.path.clone());
+        }
+    }
+    missing
+}
+
+pub(crate) fn migrate_inline_icons(profiles: &mut [Profile], cache: &IconCache) -> bool {
+    let mut migrated = false;
+    for entry in profiles
+        .iter_mut()
+        .flat_map(|profile| profile.apps.iter_mut())
+    {
+        let Some(icon) = entry.icon.as_deref() else {
+            continue;
+        };
+        if is_icon_ref(icon) {
+            continue;
+        }
+        entry.icon = cache.import_inline(icon);
+        migrated = true;
+    }
+    migrated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::{make_bundle, temp_dir};
+    use crate::store::{DockItemKind, DockLocks};
+    use std::sync::Mutex;
+
+    const TINY_PNG: &[u8] = b"\x89PNG\r\n\x1a\nfake";
+
+    fn profile_with_icon(icon: Option<String>) -> Profile {
+        Profile {
+            id: "p1".to_string(),
+            name: "Work".to_string(),
+            apps: vec![AppEntry {
+                name: "Safari".to_string(),
+                path: "/Applications/Safari.app".to_string(),
+                icon,
+                bundle_id: None,
+                version: None,
+                kind: DockItemKind::App,
+            }],
+            locks: DockLocks::default(),
+            symbol: None,
+            shortcut: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn is_icon_ref_accepts_only_sha256_hex() {
+        assert!(is_icon_ref(&hex_digest(b"icon")));
+        assert!(!is_icon_ref("iVBORw0KGgo"));
+        assert!(!is_icon_ref("../../etc/passwd"));
+        assert!(!is_icon_ref(&"A".repeat(64)));
+    }
+
+    #[test]
+    fn import_inline_is_content_addressed() {
+        let root = temp_dir("dockswitcher-icons");
+        let cache = IconCache::new(root.join("icons"));
+        let inline = STANDARD.encode(TINY_PNG);
+
+        let first = cache.import_inline(&inline).unwrap();
+        let second = cache.import_inline(&inline).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first, hex_digest(TINY_PNG));
+        assert_eq!(cache.read(&first).unwrap(), TINY_PNG);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn read_rejects_invalid_refs() {
+        let root = temp_dir("dockswitcher-icons");
+        let cache = IconCache::new(root.clone());
+        fs::write(root.join("secret.png"), b"secret").unwrap();
+
+        assert_eq!(cache.read("secret"), None);
+        assert_eq!(cache.read(&hex_digest(b"missing")), None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn bundle_key_changes_with_path() {
+        let root = temp_dir("dockswitcher-icons");
+        make_bundle(&root.join("One.app"), &[]);
+        make_bundle(&root.join("Two.app"), &[]);
+
+        let one = IconCache::bundle_key(&root.join("One.app")).unwrap();
+        let two = IconCache::bundle_key(&root.join("Two.app")).unwrap();
+        assert!(is_icon_ref(&one));
+        assert_ne!(one, two);
+        assert_eq!(IconCache::bundle_key(&root.join("Missing.app")), None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn app_icon_returns_none_without_icon() {
+        let root = temp_dir("dockswitcher-icons");
+        let app = root.join("NoIcon.app");
+        make_bundle(&app, &[]);
+        let cache = IconCache::new(root.join("icons"));
+
+        assert_eq!(cache.app_icon(&app.to_string_lossy()), None);
+        assert!(!root.join("icons").exists());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn migrate_inline_icons_moves_base64_to_cache() {
+        let root = temp_dir("dockswitcher-icons");
+        let cache = IconCache::new(root.join("icons"));
+        let mut profiles = vec![profile_with_icon(Some(STANDARD.encode(TINY_PNG)))];
+
+        assert!(migrate_inline_icons(&mut profiles, &cache));
+        let icon_ref = profiles[0].apps[0].icon.clone().unwrap();
+        assert!(is_icon_ref(&icon_ref));
+        assert_eq!(cache.read(&icon_ref).unwrap(), TINY_PNG);
+
+        assert!(!migrate_inline_icons(&mut profiles, &cache));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn migrate_inline_icons_drops_undecodable_icons() {
+        let root = temp_dir("dockswitcher-icons");
+        let cache = IconCache::new(root.join("icons"));
+        let mut profiles = vec![profile_with_icon(Some("not base64!".to_string()))];
+
+        assert!(migrate_inline_icons(&mut profiles, &cache));
+        assert_eq!(profiles[0].apps[0].icon, None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn load_icons_parallel_visits_every_path_once() {
+        let root = temp_dir("dockswitcher-icons");
+        let cache = IconCache::new(root.join("icons"));
+        let paths: Vec<String> = (0..10)
+            .map(|i| format!("/Applications/Missing{}.app", i))
+            .collect();
+        let seen = Mutex::new(Vec::new());
+
+        load_icons_parallel(&cache, &paths, 3, |path, icon| {
+            assert_eq!(icon, None);
+            seen.lock().unwrap().push(path.to_string());
+        });
+
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        let mut expected = paths.clone();
+        expected.sort();
+        assert_eq!(seen, expected);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn load_icons_parallel_handles_empty_input() {
+        let cache = IconCache::new(PathBuf::from("/tmp/dockswitcher-unused-icons"));
+        load_icons_parallel(&cache, &[], 4, |_, _| panic!("no paths to load"));
+    }
+
+    #[test]
+    fn attach_cached_icons_uses_fresh_entries_and_reports_missing() {
+        let root = temp_dir("dockswitcher-icons");
+        let cache = IconCache::new(root.join("icons"));
+        let fresh = root.join("Fresh.app");
+        let stale = root.join("Stale.app");
+        make_bundle(&fresh, &[]);
+        make_bundle(&stale, &[]);
+        let key = IconCache::bundle_key(&fresh).unwrap();
+        cache.write(&key, TINY_PNG).unwrap();
+
+        let mut apps = profile_with_icon(None).apps;
+        apps[0].path = fresh.to_string_lossy().to_string();
+        let mut stale_entry = apps[0].clone();
+        stale_entry.path = stale.to_string_lossy().to_string();
+        apps.push(stale_entry);
+
+        let missing = attach_cached_icons(&mut apps, &cache);
+        assert_eq!(apps[0].icon.as_deref(), Some(key.as_str()));
+        assert_eq!(apps[1].icon, None);
+        assert_eq!(missing, vec![stale.to_string_lossy().to_string()]);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
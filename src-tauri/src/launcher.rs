@@ -0,0 +1,314 @@
+use crate::icon_cache::IconCache;
+use crate::preview::{encode_png, profile_menu_icon, MENU_ICON_SIZE};
+use crate::store::{Profile, Store};
+use crate::tray::ordered_profiles;
+use icns::{Image, PixelFormat};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const LAUNCHER_ICON_DIR: &str = "launcher-icons";
+
+/// Icons are regenerated on every listing, so they live with other caches
+/// rather than next to `config.json`.
+pub(crate) fn launcher_icon_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("com.dockswitcher.app")
+        .join(LAUNCHER_ICON_DIR)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LauncherProfile {
+    pub id: String,
+    pub name: String,
+    pub app_count: usize,
+    pub active: bool,
+    pub icon: Option<PathBuf>,
+}
+
+impl LauncherProfile {
+    fn subtitle(&self) -> String {
+        let apps = format!(
+            "{} app{}",
+            self.app_count,
+            if self.app_count == 1 { "" } else { "s" }
+        );
+        if self.active {
+            format!("Active · {}", apps)
+        } else {
+            apps
+        }
+    }
+}
+
+/// Writes a profile's menu icon to `dir` as `<id>.png`, leaving the file
+/// untouched when it already matches so launchers can keep their own caches.
+pub(crate) fn export_profile_icon(
+    profile: &Profile,
+    cache: &IconCache,
+    dir: &Path,
+) -> Option<PathBuf> {
+    let rgba = profile_menu_icon(profile, cache)?;
+    let image = Image::from_data(PixelFormat::RGBA, MENU_ICON_SIZE, MENU_ICON_SIZE, rgba).ok()?;
+    let png = encode_png(&image).ok()?;
+    let path = dir.join(format!("{}.png", profile.id));
+    if std::fs::read(&path).ok().as_deref() != Some(png.as_slice()) {
+        std::fs::create_dir_all(dir).ok()?;
+        std::fs::write(&path, png).ok()?;
+    }
+    Some(path)
+}
+
+fn remove_stale_icons(dir: &Path, profiles: &[Profile]) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("png") {
+            continue;
+        }
+        let known = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| profiles.iter().any(|p| p.id == stem));
+        if !known {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Collects the profiles in menu order, exporting icons to `icon_dir`.
+pub(crate) fn launcher_profiles(
+    store: &Store,
+    cache: &IconCache,
+    icon_dir: &Path,
+) -> Vec<LauncherProfile> {
+    let active_id = store.data.active_profile_id.as_deref();
+    let profiles = ordered_profiles(store)
+        .into_iter()
+        .map(|profile| LauncherProfile {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+            app_count: profile
+                .apps
+                .iter()
+                .filter(|entry| entry.kind.is_app())
+                .count(),
+            active: Some(profile.id.as_str()) == active_id,
+            icon: export_profile_icon(profile, cache, icon_dir),
+        })
+        .collect();
+    remove_stale_icons(icon_dir, &store.data.profiles);
+    profiles
+}
+
+#[derive(Debug, Serialize)]
+struct AlfredIcon {
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AlfredVariables {
+    profile_id: String,
+    profile_name: String,
+    active: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AlfredText {
+    copy: String,
+    largetype: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AlfredItem {
+    uid: String,
+    title: String,
+    subtitle: String,
+    arg: String,
+    autocomplete: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<AlfredIcon>,
+    variables: AlfredVariables,
+    text: AlfredText,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct AlfredScriptFilter {
+    items: Vec<AlfredItem>,
+}
+
+/// Alfred script filter output. Each item's `arg` is the profile id, ready
+/// for a Run Script action such as `dockswitcher apply "$1"`.
+pub(crate) fn alfred_script_filter(profiles: &[LauncherProfile]) -> AlfredScriptFilter {
+    let items = profiles
+        .iter()
+        .map(|profile| AlfredItem {
+            uid: profile.id.clone(),
+            title: profile.name.clone(),
+            subtitle: profile.subtitle(),
+            arg: profile.id.clone(),
+            autocomplete: profile.name.clone(),
+            icon: profile.icon.as_ref().map(|path| AlfredIcon {
+                path: path.to_string_lossy().to_string(),
+            }),
+            variables: AlfredVariables {
+                profile_id: profile.id.clone(),
+                profile_name: profile.name.clone(),
+                active: profile.active.to_string(),
+            },
+            text: AlfredText {
+                copy: format!("dockswitcher://apply/{}", profile.id),
+                largetype: profile.name.clone(),
+            },
+        })
+        .collect();
+    AlfredScriptFilter { items }
+}
+
+#[derive(Debug, Serialize)]
+struct RaycastAccessory {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RaycastAction {
+    title: String,
+    command: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RaycastItem {
+    id: String,
+    title: String,
+    subtitle: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    accessories: Vec<RaycastAccessory>,
+    actions: Vec<RaycastAction>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct RaycastList {
+    items: Vec<RaycastItem>,
+}
+
+/// List items for a Raycast extension, with the commands to run for each
+/// action spelled out against `program`, the CLI binary.
+pub(crate) fn raycast_list(profiles: &[LauncherProfile], program: &Path) -> RaycastList {
+    let program = program.to_string_lossy().to_string();
+    let command = |args: &[&str]| {
+        std::iter::once(program.clone())
+            .chain(args.iter().map(|arg| arg.to_string()))
+            .collect()
+    };
+    let items = profiles
+        .iter()
+        .map(|profile| RaycastItem {
+            id: profile.id.clone(),
+            title: profile.name.clone(),
+            subtitle: profile.subtitle(),
+            icon: profile
+                .icon
+                .as_ref()
+                .map(|path| path.to_string_lossy().to_string()),
+            accessories: profile
+                .active
+                .then(|| RaycastAccessory {
+                    text: "Active".to_string(),
+                })
+                .into_iter()
+                .collect(),
+            actions: vec![
+                RaycastAction {
+                    title: "Apply Profile".to_string(),
+                    command: command(&["apply", &profile.id]),
+                },
+                RaycastAction {
+                    title: "Save Current Dock to Profile".to_string(),
+                    command: command(&["capture", &profile.id]),
+                },
+            ],
+        })
+        .collect();
+    RaycastList { items }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::temp_dir;
+    use serde_json::{json, Value};
+
+    fn launcher_profile(id: &str, name: &str, app_count: usize, active: bool) -> LauncherProfile {
+        LauncherProfile {
+            id: id.to_string(),
+            name: name.to_string(),
+            app_count,
+            active,
+            icon: None,
+        }
+    }
+
+    #[test]
+    fn alfred_script_filter_applies_by_id_and_flags_active_profile() {
+        let mut work = launcher_profile("work-id", "Work", 2, true);
+        work.icon = Some(PathBuf::from("/icons/work-id.png"));
+        let home = launcher_profile("home-id", "Home", 1, false);
+
+        let output = serde_json::to_value(alfred_script_filter(&[work, home])).unwrap();
+
+        let items = output["items"].as_array().unwrap();
+        assert_eq!(items[0]["arg"], "work-id");
+        assert_eq!(items[0]["subtitle"], "Active · 2 apps");
+        assert_eq!(items[0]["icon"], json!({ "path": "/icons/work-id.png" }));
+        assert_eq!(items[0]["variables"]["active"], "true");
+        assert_eq!(items[1]["subtitle"], "1 app");
+        assert_eq!(items[1]["variables"]["active"], "false");
+        assert!(items[1].get("icon").is_none());
+    }
+
+    #[test]
+    fn raycast_list_builds_apply_commands_and_active_accessory() {
+        let profiles = [
+            launcher_profile("work-id", "Work", 2, true),
+            launcher_profile("home-id", "Home", 1, false),
+        ];
+
+        let output = serde_json::to_value(raycast_list(
+            &profiles,
+            Path::new("/usr/local/bin/dockswitcher"),
+        ))
+        .unwrap();
+
+        let items = output["items"].as_array().unwrap();
+        assert_eq!(items[0]["accessories"], json!([{ "text": "Active" }]));
+        assert_eq!(items[1]["accessories"], json!([]));
+        assert_eq!(
+            items[1]["actions"][0]["command"],
+            json!(["/usr/local/bin/dockswitcher", "apply", "home-id"])
+        );
+        assert_eq!(items[1]["title"], Value::from("Home"));
+    }
+
+    #[test]
+    fn launcher_profiles_prune_icons_of_deleted_profiles() {
+        let root = temp_dir("dockswitcher-launcher");
+        let dir = root.join(LAUNCHER_ICON_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("gone-id.png"), b"png").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"keep").unwrap();
+        let mut store = Store::with_path(root.join("config.json"));
+        let mut work = Profile::new("Work".to_string());
+        work.id = "work-id".to_string();
+        store.data.profiles.push(work);
+        store.data.active_profile_id = Some("work-id".to_string());
+
+        let profiles = launcher_profiles(&store, &IconCache::new(root.join("icons")), &dir);
+
+        assert_eq!(profiles, vec![launcher_profile("work-id", "Work", 0, true)]);
+        assert!(!dir.join("gone-id.png").exists());
+        assert!(dir.join("notes.txt").exists());
+    }
+}
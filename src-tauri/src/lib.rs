@@ -1,18 +1,54 @@
+mod bundle;
+mod calendar;
+mod catalog;
+pub mod cli;
 mod commands;
+mod control;
+mod deep_link;
 mod dock;
+mod dockprofile;
 mod icon;
+mod icon_cache;
+mod launcher;
+mod lock;
+mod preview;
+mod resolver;
+mod schedule;
+mod shortcuts;
+mod signals;
 mod store;
+mod switch;
+mod temporary;
 mod tray;
 
+use crate::catalog::AppCatalog;
+use crate::commands::dock::ApplyLock;
+use crate::control::{remove_control_socket, start_control_server};
+use crate::deep_link::{handle_deep_link, handle_launch_arguments};
+use crate::dockprofile::DirectoryReports;
+use crate::icon_cache::{IconCache, ICON_SCHEME};
+use crate::schedule::start_scheduler;
+use crate::shortcuts::register_shortcuts;
+use crate::signals::start_signal_engine;
 use crate::store::Store;
-use crate::tray::{build_tray_menu, show_window};
+use crate::temporary::start_temporary_timer;
+use crate::tray::{build_tray_menu, handle_tray_menu_event, refresh_tray, show_window, TRAY_ID};
 use tauri::{tray::TrayIconBuilder, Manager, RunEvent, WindowEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let store = Store::load().unwrap_or_default();
+    let catalog = AppCatalog::load(&store.data.settings.app_roots);
 
     let app = tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            if !handle_launch_arguments(app, &args, std::path::Path::new(&cwd)) {
+                if let Some(window) = app.get_webview_window("main") {
+                    show_window(&window);
+                }
+            }
+        }))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -20,7 +56,26 @@ pub fn run() {
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             None,
         ))
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_deep_link::init())
         .manage(std::sync::Mutex::new(store))
+        .manage(std::sync::Mutex::new(catalog))
+        .manage(DirectoryReports::default())
+        .manage(ApplyLock::default())
+        .register_uri_scheme_protocol(ICON_SCHEME, |_ctx, request| {
+            let icon_ref = request.uri().path().trim_start_matches('/');
+            match IconCache::shared().read(icon_ref) {
+                Some(png) => tauri::http::Response::builder()
+                    .header("Content-Type", "image/png")
+                    .header("Cache-Control", "max-age=31536000, immutable")
+                    .body(png)
+                    .unwrap_or_default(),
+                None => tauri::http::Response::builder()
+                    .status(404)
+                    .body(Vec::new())
+                    .unwrap_or_default(),
+            }
+        })
         .setup(|app| {
             #[cfg(target_os = "macos")]
             {
@@ -32,43 +87,34 @@ pub fn run() {
             let menu = build_tray_menu(app.handle(), &store_guard)?;
             drop(store_guard);
 
-            let _tray = TrayIconBuilder::with_id("main-tray")
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .tooltip("DockSwitcher")
                 .icon(app.default_window_icon().unwrap().clone())
                 .icon_as_template(true)
                 .menu(&menu)
                 .show_menu_on_left_click(true)
-                .on_menu_event(|app, event| match event.id().as_ref() {
-                    "open" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            show_window(&window);
-                        }
-                    }
-                    "quit" => {
-                        app.exit(0);
-                    }
-                    profile_id => {
-                        let state = app.state::<std::sync::Mutex<Store>>();
-                        let profile_exists = {
-                            let store = state.lock().unwrap();
-                            store.data.profiles.iter().any(|p| p.id == profile_id)
-                        };
-                        if profile_exists {
-                            let app_clone = app.clone();
-                            let id = profile_id.to_string();
-                            tauri::async_runtime::spawn(async move {
-                                let state = app_clone.state::<std::sync::Mutex<Store>>();
-                                if let Err(e) =
-                                    commands::dock::apply_profile(id, app_clone.clone(), state)
-                                {
-                                    eprintln!("Failed to apply profile: {}", e);
-                                }
-                            });
-                        }
-                    }
-                })
+                .on_menu_event(|app, event| handle_tray_menu_event(app, event.id().as_ref()))
                 .build(app)?;
 
+            if let Ok(store) = store.lock() {
+                refresh_tray(app.handle(), &store);
+                register_shortcuts(app.handle(), &store);
+            };
+            start_control_server(app.handle());
+            start_scheduler(app.handle());
+            start_signal_engine(app.handle());
+            start_temporary_timer(app.handle());
+
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&handle, &url);
+                }
+            });
+            let args: Vec<String> = std::env::args().collect();
+            let cwd = std::env::current_dir().unwrap_or_default();
+            handle_launch_arguments(app.handle(), &args, &cwd);
+
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -84,25 +130,54 @@ pub fn run() {
             }
         })
         .invoke_handler(tauri::generate_handler![
+            commands::catalog::list_installed_apps,
+            commands::catalog::search_apps,
             commands::profiles::get_profiles,
             commands::profiles::get_active_profile_id,
             commands::profiles::create_profile,
             commands::profiles::update_profile,
             commands::profiles::delete_profile,
             commands::profiles::reorder_profiles,
+            commands::profiles::render_profile_preview,
+            commands::profiles::export_profile,
             commands::settings::get_settings,
             commands::settings::update_settings,
+            commands::schedules::get_schedules,
+            commands::schedules::update_schedules,
+            commands::schedules::get_automation_paused,
+            commands::schedules::set_automation_paused,
+            commands::calendar::get_calendar_files,
+            commands::calendar::update_calendar_files,
+            commands::calendar::get_calendar_rules,
+            commands::calendar::update_calendar_rules,
+            commands::signals::get_signal_rules,
+            commands::signals::update_signal_rules,
+            commands::signals::explain_signal_rules,
+            commands::signals::get_last_signal_firing,
+            commands::temporary::get_temporary_session,
+            commands::temporary::apply_profile_temporarily,
+            commands::temporary::extend_temporary_profile,
+            commands::temporary::end_temporary_profile,
             commands::dock::apply_profile,
+            commands::dock::apply_next_profile,
+            commands::dock::apply_previous_profile,
+            commands::dock::toggle_last_profile,
+            commands::dock::get_switch_confirmation,
             commands::dock::get_current_dock_apps,
             commands::dock::save_dock_to_profile,
             commands::dock::add_app_to_profile,
             commands::dock::remove_app_from_profile,
             commands::dock::check_dockutil,
+            commands::dock::get_dock_locks,
+            commands::dock::unlock_dock,
         ])
         .build(tauri::generate_context!())
         .expect("error while building DockSwitcher");
 
     app.run(|app_handle, event| {
+        if let RunEvent::Exit = event {
+            remove_control_socket();
+        }
         #[cfg(target_os = "macos")]
         if let RunEvent::Reopen {
             has_visible_windows,
@@ -0,0 +1,130 @@
+use crate::store::DockLocks;
+
+const DOCK_DOMAIN: &str = "com.apple.dock";
+
+pub(crate) fn lock_flags(locks: &DockLocks) -> [(&'static str, bool); 4] {
+    [
+        ("contents-immutable", locks.contents),
+        ("size-immutable", locks.size),
+        ("position-immutable", locks.position),
+        ("magnify-immutable", locks.magnify),
+    ]
+}
+
+pub(crate) fn parse_defaults_bool(output: &str) -> bool {
+    matches!(
+        output.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes"
+    )
+}
+
+pub(crate) fn read_dock_locks() -> DockLocks {
+    let read = |key: &str| {
+        std::process::Command::new("defaults")
+            .args(["read", DOCK_DOMAIN, key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| parse_defaults_bool(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or(false)
+    };
+
+    DockLocks {
+        contents: read("contents-immutable"),
+        size: read("size-immutable"),
+        position: read("position-immutable"),
+        magnify: read("magnify-immutable"),
+    }
+}
+
+pub(crate) fn write_dock_locks(locks: &DockLocks) -> Result<(), String> {
+    for (key, locked) in lock_flags(locks) {
+        if locked {
+            let output = std::process::Command::new("defaults")
+                .args(["write", DOCK_DOMAIN, key, "-bool", "true"])
+                .output()
+                .map_err(|e| format!("Failed to set {}: {}", key, e))?;
+            if !output.status.success() {
+                return Err(format!(
+                    "defaults write {} failed: {}",
+                    key,
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+        } else {
+            // Deleting a key that was never set fails, which is fine: it is already unlocked.
+            let _ = std::process::Command::new("defaults")
+                .args(["delete", DOCK_DOMAIN, key])
+                .output();
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn unlock_dock() -> Result<(), String> {
+    write_dock_locks(&DockLocks::default())
+}
+
+/// Puts back the locks that were set when it was created, unless it is
+/// disarmed once the Dock has been switched.
+pub(crate) struct RestoreLocks(Option<DockLocks>);
+
+impl RestoreLocks {
+    pub(crate) fn current() -> Self {
+        Self(Some(read_dock_locks()))
+    }
+
+    pub(crate) fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for RestoreLocks {
+    fn drop(&mut self) {
+        if let Some(locks) = self.0.take() {
+            if let Err(e) = write_dock_locks(&locks) {
+                eprintln!("Failed to restore Dock locks: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_flags_maps_every_dock_key() {
+        let locks = DockLocks {
+            contents: true,
+            size: false,
+            position: true,
+            magnify: false,
+        };
+        assert_eq!(
+            lock_flags(&locks),
+            [
+                ("contents-immutable", true),
+                ("size-immutable", false),
+                ("position-immutable", true),
+                ("magnify-immutable", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn lock_flags_default_is_fully_unlocked() {
+        assert!(lock_flags(&DockLocks::default())
+            .iter()
+            .all(|(_, locked)| !locked));
+    }
+
+    #[test]
+    fn parse_defaults_bool_accepts_defaults_output() {
+        assert!(parse_defaults_bool("1\n"));
+        assert!(parse_defaults_bool("true"));
+        assert!(parse_defaults_bool("YES"));
+        assert!(!parse_defaults_bool("0\n"));
+        assert!(!parse_defaults_bool(""));
+    }
+}
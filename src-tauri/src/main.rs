@@ -2,5 +2,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if dockswitcher_lib::cli::is_cli_invocation(&args) {
+        let code = dockswitcher_lib::cli::run(args, &mut std::io::stdout(), &mut std::io::stderr());
+        std::process::exit(code);
+    }
     dockswitcher_lib::run()
 }
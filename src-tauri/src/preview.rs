@@ -0,0 +1,565 @@
+use crate::icon::{load_app_icon_image, resize_image};
+use crate::icon_cache::{hex_digest, IconCache};
+use crate::store::{AppEntry, DockItemKind, Profile};
+use icns::{Image, PixelFormat};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+pub(crate) const DEFAULT_TILE_SIZE: u32 = 64;
+const MIN_TILE_SIZE: u32 = 16;
+const MAX_TILE_SIZE: u32 = 256;
+
+pub(crate) const MENU_ICON_SIZE: u32 = 32;
+const MENU_ICON_APPS: usize = 4;
+
+const BACKGROUND_COLOR: [u8; 4] = [236, 236, 240, 230];
+const PLACEHOLDER_COLOR: [u8; 4] = [176, 176, 184, 255];
+const SEPARATOR_COLOR: [u8; 4] = [112, 112, 120, 170];
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewOrientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PreviewOptions {
+    pub orientation: PreviewOrientation,
+    pub tile_size: u32,
+    pub separator: bool,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            orientation: PreviewOrientation::Horizontal,
+            tile_size: DEFAULT_TILE_SIZE,
+            separator: true,
+        }
+    }
+}
+
+impl PreviewOptions {
+    fn tile_size(&self) -> u32 {
+        self.tile_size.clamp(MIN_TILE_SIZE, MAX_TILE_SIZE)
+    }
+}
+
+enum Slot {
+    Icon(Option<Image>),
+    Spacer(u32),
+    Separator,
+}
+
+impl Slot {
+    fn extent(&self, tile: u32) -> u32 {
+        match self {
+            Slot::Icon(_) => tile,
+            Slot::Spacer(extent) => *extent,
+            Slot::Separator => (tile / 4).max(4),
+        }
+    }
+}
+
+fn blend_pixel(image: &mut Image, x: u32, y: u32, color: [u8; 4]) {
+    if x >= image.width() || y >= image.height() || color[3] == 0 {
+        return;
+    }
+    let offset = ((y * image.width() + x) * 4) as usize;
+    let dst = &mut image.data_mut()[offset..offset + 4];
+    let src_alpha = color[3] as u32;
+    let dst_alpha = dst[3] as u32 * (255 - src_alpha) / 255;
+    let out_alpha = src_alpha + dst_alpha;
+    if out_alpha == 0 {
+        return;
+    }
+    for channel in 0..3 {
+        dst[channel] = ((color[channel] as u32 * src_alpha + dst[channel] as u32 * dst_alpha)
+            / out_alpha) as u8;
+    }
+    dst[3] = out_alpha as u8;
+}
+
+fn fill_rounded_rect(
+    image: &mut Image,
+    (x, y): (u32, u32),
+    (width, height): (u32, u32),
+    radius: u32,
+    color: [u8; 4],
+) {
+    let radius = radius.min(width / 2).min(height / 2) as i64;
+    for dy in 0..height {
+        for dx in 0..width {
+            let corner_x = (radius - dx as i64).max(dx as i64 - (width as i64 - 1 - radius));
+            let corner_y = (radius - dy as i64).max(dy as i64 - (height as i64 - 1 - radius));
+            if corner_x > 0
+                && corner_y > 0
+                && corner_x * corner_x + corner_y * corner_y > radius * radius
+            {
+                continue;
+            }
+            blend_pixel(image, x + dx, y + dy, color);
+        }
+    }
+}
+
+fn draw_image(canvas: &mut Image, icon: &Image, (x, y): (u32, u32)) {
+    let icon = icon.convert_to(PixelFormat::RGBA);
+    let data = icon.data();
+    for dy in 0..icon.height() {
+        for dx in 0..icon.width() {
+            let offset = ((dy * icon.width() + dx) * 4) as usize;
+            let mut color = [0u8; 4];
+            color.copy_from_slice(&data[offset..offset + 4]);
+            blend_pixel(canvas, x + dx, y + dy, color);
+        }
+    }
+}
+
+pub(crate) fn render_dock_strip<F>(
+    apps: &[AppEntry],
+    options: &PreviewOptions,
+    mut load_icon: F,
+) -> Image
+where
+    F: FnMut(&AppEntry, u32) -> Option<Image>,
+{
+    let tile = options.tile_size();
+    let padding = (tile / 8).max(2);
+    let gap = (tile / 16).max(1);
+    let horizontal = options.orientation == PreviewOrientation::Horizontal;
+
+    let mut slots: Vec<Slot> = apps
+        .iter()
+        .map(|entry| match entry.kind {
+            DockItemKind::App => {
+                Slot::Icon(load_icon(entry, tile).map(|icon| resize_image(&icon, tile, tile)))
+            }
+            DockItemKind::Spacer => Slot::Spacer(tile),
+            DockItemKind::SmallSpacer => Slot::Spacer(tile / 2),
+        })
+        .collect();
+    // The Dock divides the apps section from Downloads and Trash with a thin line.
+    if options.separator && !slots.is_empty() {
+        slots.push(Slot::Separator);
+    }
+
+    let content: u32 = slots.iter().map(|slot| slot.extent(tile)).sum::<u32>()
+        + gap * (slots.len() as u32).saturating_sub(1);
+    let thickness = tile + padding * 2;
+    let length = (content + padding * 2).max(thickness);
+    let (width, height) = if horizontal {
+        (length, thickness)
+    } else {
+        (thickness, length)
+    };
+
+    let mut canvas = Image::new(PixelFormat::RGBA, width, height);
+    fill_rounded_rect(
+        &mut canvas,
+        (0, 0),
+        (width, height),
+        padding * 2,
+        BACKGROUND_COLOR,
+    );
+
+    let mut offset = padding;
+    for slot in &slots {
+        let origin = if horizontal {
+            (offset, padding)
+        } else {
+            (padding, offset)
+        };
+        match slot {
+            Slot::Icon(Some(icon)) => draw_image(&mut canvas, icon, origin),
+            Slot::Icon(None) => fill_rounded_rect(
+                &mut canvas,
+                origin,
+                (tile, tile),
+                tile / 5,
+                PLACEHOLDER_COLOR,
+            ),
+            Slot::Spacer(_) => {}
+            Slot::Separator => {
+                let line = (tile / 32).max(1);
+                let inset = tile / 8;
+                let center = offset + slot.extent(tile) / 2 - line / 2;
+                let (position, size) = if horizontal {
+                    ((center, padding + inset), (line, tile - inset * 2))
+                } else {
+                    ((padding + inset, center), (tile - inset * 2, line))
+                };
+                fill_rounded_rect(&mut canvas, position, size, 0, SEPARATOR_COLOR);
+            }
+        }
+        offset += slot.extent(tile) + gap;
+    }
+    canvas
+}
+
+fn load_entry_icon(entry: &AppEntry, tile: u32) -> Option<Image> {
+    load_app_icon_image(&entry.path, tile, 1).or_else(|| {
+        // Apps missing on this Mac can still be drawn from the icon captured with the profile.
+        let png = IconCache::shared().read(entry.icon.as_deref()?)?;
+        Image::read_png(Cursor::new(png)).ok()
+    })
+}
+
+pub(crate) fn render_profile_preview(profile: &Profile, options: &PreviewOptions) -> Image {
+    render_dock_strip(&profile.apps, options, load_entry_icon)
+}
+
+fn menu_icon_refs(profile: &Profile) -> Vec<&str> {
+    profile
+        .apps
+        .iter()
+        .filter(|entry| entry.kind.is_app())
+        .filter_map(|entry| entry.icon.as_deref())
+        .take(MENU_ICON_APPS)
+        .collect()
+}
+
+pub(crate) fn menu_icon_key(icon_refs: &[&str]) -> String {
+    hex_digest(format!("menu-icon\0{}\0{}", MENU_ICON_SIZE, icon_refs.join("\0")).as_bytes())
+}
+
+pub(crate) fn render_menu_icon(icons: &[Image]) -> Image {
+    let size = MENU_ICON_SIZE;
+    let mut canvas = Image::new(PixelFormat::RGBA, size, size);
+    let (tile, columns) = match icons.len() {
+        0 => return canvas,
+        1 => (size, 1),
+        _ => (size / 2, 2),
+    };
+    let rows = (icons.len() as u32).div_ceil(columns);
+    let top = (size - rows * tile) / 2;
+    for (index, icon) in icons.iter().enumerate() {
+        let index = index as u32;
+        let in_row = if index / columns == rows - 1 {
+            icons.len() as u32 - (rows - 1) * columns
+        } else {
+            columns
+        };
+        let left = (size - in_row * tile) / 2;
+        let origin = (
+            left + (index % columns) * tile,
+            top + (index / columns) * tile,
+        );
+        draw_image(&mut canvas, &resize_image(icon, tile, tile), origin);
+    }
+    canvas
+}
+
+pub(crate) fn profile_menu_icon(profile: &Profile, cache: &IconCache) -> Option<Vec<u8>> {
+    let icon_refs = menu_icon_refs(profile);
+    if icon_refs.is_empty() {
+        return None;
+    }
+    // Keyed by the app icon refs, so composites are only rebuilt when the profile's apps change.
+    let key = menu_icon_key(&icon_refs);
+
+    let image = match cache
+        .read(&key)
+        .and_then(|png| Image::read_png(Cursor::new(png)).ok())
+    {
+        Some(image) => image.convert_to(PixelFormat::RGBA),
+        None => {
+            let icons: Vec<Image> = icon_refs
+                .iter()
+                .filter_map(|icon_ref| cache.read(icon_ref))
+                .filter_map(|png| Image::read_png(Cursor::new(png)).ok())
+                .collect();
+            if icons.is_empty() {
+                return None;
+            }
+            let image = render_menu_icon(&icons);
+            if let Ok(png) = encode_png(&image) {
+                cache.write(&key, &png);
+            }
+            image
+        }
+    };
+
+    Some(image.into_data().into_vec())
+}
+
+pub(crate) fn encode_png(image: &Image) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    image
+        .write_png(&mut png)
+        .map_err(|e| format!("Failed to encode preview: {}", e))?;
+    Ok(png)
+}
+
+pub(crate) fn preview_path_for(export_path: &Path) -> PathBuf {
+    export_path.with_extension("png")
+}
+
+pub(crate) fn export_profile(
+    profile: &Profile,
+    export_path: &Path,
+    options: &PreviewOptions,
+) -> Result<PathBuf, String> {
+    // macOS paths are usually case-insensitive, so `Work.PNG` collides too.
+    if export_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+    {
+        return Err(format!(
+            "{} would be overwritten by its preview; export to a .json file",
+            export_path.display()
+        ));
+    }
+    let mut exported = profile.clone();
+    // Icon refs point into this Mac's icon cache, so they mean nothing elsewhere.
+    for entry in exported.apps.iter_mut() {
+        entry.icon = None;
+    }
+    let json = serde_json::to_string_pretty(&exported).map_err(|e| e.to_string())?;
+    std::fs::write(export_path, json)
+        .map_err(|e| format!("Failed to write {}: {}", export_path.display(), e))?;
+
+    let preview_path = preview_path_for(export_path);
+    let png = encode_png(&render_profile_preview(profile, options))?;
+    std::fs::write(&preview_path, png)
+        .map_err(|e| format!("Failed to write {}: {}", preview_path.display(), e))?;
+    Ok(preview_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::temp_dir;
+    use crate::store::DockLocks;
+    use std::fs;
+
+    fn entry(name: &str, kind: DockItemKind) -> AppEntry {
+        AppEntry {
+            name: name.to_string(),
+            path: format!("/Applications/{}.app", name),
+            icon: None,
+            bundle_id: None,
+            version: None,
+            kind,
+        }
+    }
+
+    fn solid_icon(size: u32, rgba: [u8; 4]) -> Image {
+        Image::from_data(
+            PixelFormat::RGBA,
+            size,
+            size,
+            rgba.repeat((size * size) as usize),
+        )
+        .unwrap()
+    }
+
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * image.width() + x) * 4) as usize;
+        let mut color = [0u8; 4];
+        color.copy_from_slice(&image.data()[offset..offset + 4]);
+        color
+    }
+
+    fn options(orientation: PreviewOrientation, separator: bool) -> PreviewOptions {
+        PreviewOptions {
+            orientation,
+            tile_size: 32,
+            separator,
+        }
+    }
+
+    #[test]
+    fn render_dock_strip_lays_out_tiles_horizontally() {
+        let apps = vec![
+            entry("One", DockItemKind::App),
+            entry("Two", DockItemKind::App),
+        ];
+        let strip = render_dock_strip(
+            &apps,
+            &options(PreviewOrientation::Horizontal, false),
+            |_, size| Some(solid_icon(size, [255, 0, 0, 255])),
+        );
+
+        // 4px padding on each side, two 32px tiles and a 2px gap.
+        assert_eq!((strip.width(), strip.height()), (74, 40));
+        assert_eq!(pixel(&strip, 4, 4), [255, 0, 0, 255]);
+        assert_eq!(pixel(&strip, 38, 20), [255, 0, 0, 255]);
+        assert_ne!(pixel(&strip, 37, 20), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn render_dock_strip_supports_vertical_orientation() {
+        let apps = vec![
+            entry("One", DockItemKind::App),
+            entry("Two", DockItemKind::App),
+        ];
+        let strip = render_dock_strip(
+            &apps,
+            &options(PreviewOrientation::Vertical, false),
+            |_, size| Some(solid_icon(size, [0, 255, 0, 255])),
+        );
+
+        assert_eq!((strip.width(), strip.height()), (40, 74));
+        assert_eq!(pixel(&strip, 20, 38), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn render_dock_strip_reserves_space_for_spacers_and_separator() {
+        let apps = vec![
+            entry("One", DockItemKind::App),
+            entry("", DockItemKind::Spacer),
+            entry("", DockItemKind::SmallSpacer),
+            entry("Two", DockItemKind::App),
+        ];
+        let mut loaded = Vec::new();
+        let strip = render_dock_strip(
+            &apps,
+            &options(PreviewOrientation::Horizontal, true),
+            |entry, size| {
+                loaded.push(entry.name.clone());
+                Some(solid_icon(size, [0, 0, 255, 255]))
+            },
+        );
+
+        assert_eq!(loaded, vec!["One", "Two"]);
+        // Tiles 32 + 32 + 16 + 32, separator 8, four 2px gaps and 4px padding on each side.
+        assert_eq!(strip.width(), 4 + 32 + 32 + 16 + 32 + 8 + 4 * 2 + 4);
+        assert_ne!(pixel(&strip, 4 + 32 + 2 + 16, 20), [0, 0, 255, 255]);
+        assert_eq!(pixel(&strip, 4 + 32 * 2 + 16 + 2 * 3, 20), [0, 0, 255, 255]);
+        let separator_x = strip.width() - 4 - 4;
+        assert_ne!(
+            pixel(&strip, separator_x, 20),
+            pixel(&strip, separator_x, 2)
+        );
+    }
+
+    #[test]
+    fn render_dock_strip_draws_placeholder_for_missing_icons() {
+        let apps = vec![entry("Missing", DockItemKind::App)];
+        let strip = render_dock_strip(
+            &apps,
+            &options(PreviewOrientation::Horizontal, false),
+            |_, _| None,
+        );
+
+        assert_eq!(pixel(&strip, 20, 20)[..3], PLACEHOLDER_COLOR[..3]);
+    }
+
+    #[test]
+    fn render_dock_strip_clamps_tile_size() {
+        let apps = vec![entry("One", DockItemKind::App)];
+        let mut requested = 0;
+        render_dock_strip(
+            &apps,
+            &PreviewOptions {
+                tile_size: 1,
+                ..PreviewOptions::default()
+            },
+            |_, size| {
+                requested = size;
+                None
+            },
+        );
+        assert_eq!(requested, MIN_TILE_SIZE);
+    }
+
+    #[test]
+    fn export_profile_writes_json_and_preview() {
+        let root = temp_dir("dockswitcher-preview");
+        let mut app = entry("Missing", DockItemKind::App);
+        app.icon = Some("f".repeat(64));
+        let profile = Profile {
+            id: "p1".to_string(),
+            name: "Work".to_string(),
+            apps: vec![app],
+            locks: DockLocks::default(),
+            symbol: None,
+            shortcut: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+
+        let export_path = root.join("work.json");
+        let preview_path =
+            export_profile(&profile, &export_path, &PreviewOptions::default()).unwrap();
+
+        assert_eq!(preview_path, root.join("work.png"));
+        let exported: Profile =
+            serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+        assert_eq!(exported.name, "Work");
+        assert_eq!(exported.apps[0].icon, None);
+        assert!(fs::read(&preview_path).unwrap().starts_with(b"\x89PNG"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn export_profile_rejects_paths_its_preview_would_overwrite() {
+        let root = temp_dir("dockswitcher-preview");
+        let profile = Profile::new("Work".to_string());
+
+        for name in ["work.png", "Work.PNG"] {
+            let export_path = root.join(name);
+            let error =
+                export_profile(&profile, &export_path, &PreviewOptions::default()).unwrap_err();
+            assert!(error.contains("overwritten"));
+            assert!(!export_path.exists());
+        }
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn render_menu_icon_arranges_icons_in_a_grid() {
+        let single = render_menu_icon(&[solid_icon(64, [255, 0, 0, 255])]);
+        assert_eq!(single.width(), MENU_ICON_SIZE);
+        assert_eq!(pixel(&single, 0, 0), [255, 0, 0, 255]);
+
+        let icons = [
+            solid_icon(32, [255, 0, 0, 255]),
+            solid_icon(32, [0, 255, 0, 255]),
+            solid_icon(32, [0, 0, 255, 255]),
+        ];
+        let grid = render_menu_icon(&icons);
+        assert_eq!(pixel(&grid, 4, 4), [255, 0, 0, 255]);
+        assert_eq!(pixel(&grid, 20, 4), [0, 255, 0, 255]);
+        // The last row is centered when it isn't full.
+        assert_eq!(pixel(&grid, 16, 20), [0, 0, 255, 255]);
+        assert_eq!(pixel(&grid, 4, 20)[3], 0);
+    }
+
+    #[test]
+    fn menu_icon_key_changes_with_app_icons() {
+        let a = "a".repeat(64);
+        let b = "b".repeat(64);
+        assert_eq!(menu_icon_key(&[&a, &b]), menu_icon_key(&[&a, &b]));
+        assert_ne!(menu_icon_key(&[&a, &b]), menu_icon_key(&[&b, &a]));
+        assert_ne!(menu_icon_key(&[&a]), menu_icon_key(&[&a, &b]));
+    }
+
+    #[test]
+    fn profile_menu_icon_composites_and_caches_app_icons() {
+        let root = temp_dir("dockswitcher-preview");
+        let cache = IconCache::new(root.clone());
+        let mut profile = Profile::new("Work".to_string());
+        assert_eq!(profile_menu_icon(&profile, &cache), None);
+
+        let png = encode_png(&solid_icon(16, [9, 9, 9, 255])).unwrap();
+        let icon_ref = cache.write(&hex_digest(&png), &png).unwrap();
+        let mut app = entry("One", DockItemKind::App);
+        app.icon = Some(icon_ref.clone());
+        profile.apps.push(app);
+
+        let rgba = profile_menu_icon(&profile, &cache).unwrap();
+        assert_eq!(rgba.len(), (MENU_ICON_SIZE * MENU_ICON_SIZE * 4) as usize);
+        assert_eq!(&rgba[..4], &[9, 9, 9, 255]);
+        assert!(cache.read(&menu_icon_key(&[&icon_ref])).is_some());
+
+        fs::remove_dir_all(root).ok();
+    }
+}
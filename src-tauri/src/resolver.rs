@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const MAX_SCAN_DEPTH: usize = 3;
+
+pub(crate) fn default_app_roots() -> Vec<PathBuf> {
+    let mut roots = vec![
+        PathBuf::from("/Applications"),
+        PathBuf::from("/System/Applications"),
+    ];
+    if let Some(home) = dirs::home_dir() {
+        roots.push(home.join("Applications"));
+    }
+    roots
+}
+
+pub(crate) fn find_app_bundles(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut bundles = Vec::new();
+    for root in roots {
+        collect_app_bundles(root, 0, &mut bundles);
+    }
+    bundles
+}
+
+fn collect_app_bundles(dir: &Path, depth: usize, bundles: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        if path.extension().is_some_and(|ext| ext == "app") {
+            bundles.push(path);
+        } else if depth < MAX_SCAN_DEPTH && path.is_dir() {
+            collect_app_bundles(&path, depth + 1, bundles);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct AppResolver {
+    by_bundle_id: HashMap<String, PathBuf>,
+}
+
+impl AppResolver {
+    pub(crate) fn from_entries<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (String, PathBuf)>,
+    {
+        let mut resolver = Self::default();
+        for (bundle_id, path) in entries {
+            // Earlier roots win, so /Applications shadows copies in ~/Applications.
+            resolver.by_bundle_id.entry(bundle_id).or_insert(path);
+        }
+        resolver
+    }
+
+    pub(crate) fn resolve(&self, bundle_id: &str) -> Option<&Path> {
+        self.by_bundle_id.get(bundle_id).map(PathBuf::as_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::fixtures::{make_bundle, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn find_app_bundles_includes_nested_folders() {
+        let root = temp_dir("dockswitcher-resolver");
+        make_bundle(&root.join("Top.app"), &[]);
+        make_bundle(&root.join("Setapp").join("Nested.app"), &[]);
+
+        let bundles = find_app_bundles(std::slice::from_ref(&root));
+        assert_eq!(
+            bundles,
+            vec![root.join("Setapp").join("Nested.app"), root.join("Top.app")]
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn find_app_bundles_does_not_descend_into_app_bundles() {
+        let root = temp_dir("dockswitcher-resolver");
+        let outer = root.join("Outer.app");
+        make_bundle(&outer, &[]);
+        make_bundle(
+            &outer.join("Contents").join("Helpers").join("Helper.app"),
+            &[],
+        );
+
+        let bundles = find_app_bundles(std::slice::from_ref(&root));
+        assert_eq!(bundles, vec![outer]);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn find_app_bundles_skips_missing_roots() {
+        let bundles = find_app_bundles(&[PathBuf::from("/definitely/missing/root")]);
+        assert!(bundles.is_empty());
+    }
+
+    #[test]
+    fn resolver_maps_bundle_ids_to_paths() {
+        let resolver = AppResolver::from_entries([(
+            "com.example.app".to_string(),
+            PathBuf::from("/Applications/App.app"),
+        )]);
+        assert_eq!(
+            resolver.resolve("com.example.app"),
+            Some(Path::new("/Applications/App.app"))
+        );
+        assert_eq!(resolver.resolve("com.example.missing"), None);
+    }
+
+    #[test]
+    fn resolver_prefers_first_entry_for_duplicate_ids() {
+        let resolver = AppResolver::from_entries([
+            (
+                "com.example.app".to_string(),
+                PathBuf::from("/Applications/App.app"),
+            ),
+            (
+                "com.example.app".to_string(),
+                PathBuf::from("/Users/test/Applications/App.app"),
+            ),
+        ]);
+        assert_eq!(
+            resolver.resolve("com.example.app"),
+            Some(Path::new("/Applications/App.app"))
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use crate::calendar::{run_calendar, CalendarCache};
+use crate::commands::dock::apply_profile;
+use crate::store::{Profile, ScheduleRule, Store};
+use chrono::{DateTime, Datelike, Days, LocalResult, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30);
+const LOOKBACK_DAYS: u64 = 7;
+
+pub(crate) fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("\"{}\" is not a time like 09:00", value))
+}
+
+pub(crate) fn parse_timezone(value: &str) -> Result<Tz, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("\"{}\" is not a known time zone", value))
+}
+
+pub(crate) fn validate_schedules(
+    schedules: &[ScheduleRule],
+    profiles: &[Profile],
+) -> Result<(), String> {
+    for rule in schedules {
+        if !profiles.iter().any(|p| p.id == rule.profile_id) {
+            return Err("A schedule refers to a profile that no longer exists".to_string());
+        }
+        parse_time(&rule.time)?;
+        if let Some(timezone) = &rule.timezone {
+            parse_timezone(timezone)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn resolve_local<T: TimeZone>(tz: &T, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => Some(at.with_timezone(&Utc)),
+        // The clock skips this time when daylight saving starts, so fire once it resumes.
+        LocalResult::None => tz
+            .from_local_datetime(&(naive + chrono::Duration::hours(1)))
+            .earliest()
+            .map(|at| at.with_timezone(&Utc)),
+    }
+}
+
+fn latest_boundary_in<T: TimeZone>(
+    tz: &T,
+    rule: &ScheduleRule,
+    time: NaiveTime,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let today = now.with_timezone(tz).date_naive();
+    (0..=LOOKBACK_DAYS)
+        .filter_map(|days| today.checked_sub_days(Days::new(days)))
+        .filter(|date| rule.weekdays.is_empty() || rule.weekdays.contains(&date.weekday()))
+        .filter_map(|date| resolve_local(tz, date.and_time(time)))
+        .find(|at| *at <= now)
+}
+
+/// The last time at or before `now` that `rule` fired.
+pub(crate) fn latest_boundary(rule: &ScheduleRule, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let time = parse_time(&rule.time).ok()?;
+    match &rule.timezone {
+        Some(timezone) => latest_boundary_in(&parse_timezone(timezone).ok()?, rule, time, now),
+        None => latest_boundary_in(&chrono::Local, rule, time, now),
+    }
+}
+
+/// Of the enabled rules that fired after `since`, the one that fired last.
+pub(crate) fn due_schedule(
+    schedules: &[ScheduleRule],
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<&ScheduleRule> {
+    schedules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(|rule| latest_boundary(rule, now).map(|at| (rule, at)))
+        .filter(|(_, at)| *at > since)
+        .max_by_key(|(_, at)| *at)
+        .map(|(rule, _)| rule)
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ScheduleTick {
+    Idle,
+    Started,
+    Skipped,
+    Apply(String),
+}
+
+/// Advances the scheduler clock in `store` to `now`. Boundaries missed while
+/// the Mac slept or the app was closed are caught up, but only the latest one
+/// is applied. The first run only starts the clock, so new rules don't fire
+/// for times that have already passed.
+pub(crate) fn schedule_tick(store: &mut Store, now: DateTime<Utc>) -> ScheduleTick {
+    let since = store
+        .data
+        .schedule_checked_at
+        .as_deref()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Utc));
+    store.data.schedule_checked_at = Some(now.to_rfc3339());
+    let Some(since) = since else {
+        return ScheduleTick::Started;
+    };
+    let Some(rule) = due_schedule(&store.data.schedules, since, now) else {
+        return ScheduleTick::Idle;
+    };
+    let already_active = store.data.active_profile_id.as_ref() == Some(&rule.profile_id);
+    if store.data.automation_paused || already_active {
+        return ScheduleTick::Skipped;
+    }
+    ScheduleTick::Apply(rule.profile_id.clone())
+}
+
+fn run_schedule(app: &AppHandle) {
+    let tick = {
+        let state = app.state::<Mutex<Store>>();
+        let Ok(mut store) = state.lock() else {
+            return;
+        };
+        let tick = schedule_tick(&mut store, Utc::now());
+        if tick != ScheduleTick::Idle {
+            if let Err(e) = store.save() {
+                eprintln!("Failed to save schedule state: {}", e);
+            }
+        }
+        tick
+    };
+    if let ScheduleTick::Apply(id) = tick {
+        if let Err(e) = apply_profile(id, app.clone(), app.state::<Mutex<Store>>()) {
+            eprintln!("Failed to apply scheduled profile: {}", e);
+        }
+    }
+}
+
+pub fn start_scheduler(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut calendars = CalendarCache::default();
+        loop {
+            run_schedule(&app);
+            run_calendar(&app, &mut calendars);
+            std::thread::sleep(SCHEDULER_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::fixtures::utc;
+    use chrono::Weekday;
+    use std::path::PathBuf;
+
+    fn rule(profile_id: &str, weekdays: &[Weekday], time: &str) -> ScheduleRule {
+        ScheduleRule {
+            id: format!("{}-{}", profile_id, time),
+            profile_id: profile_id.to_string(),
+            weekdays: weekdays.to_vec(),
+            time: time.to_string(),
+            timezone: Some("Europe/Madrid".to_string()),
+            enabled: true,
+        }
+    }
+
+    fn weekdays() -> Vec<Weekday> {
+        vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]
+    }
+
+    #[test]
+    fn latest_boundary_respects_weekdays_and_time_zone() {
+        let work = rule("work", &weekdays(), "09:00");
+
+        // Wednesday 2026-03-11, 10:00 in Madrid (UTC+1).
+        assert_eq!(
+            latest_boundary(&work, utc("2026-03-11T09:00:00Z")),
+            Some(utc("2026-03-11T08:00:00Z"))
+        );
+        // Monday 08:00 in Madrid falls back to Friday's boundary.
+        assert_eq!(
+            latest_boundary(&work, utc("2026-03-09T07:00:00Z")),
+            Some(utc("2026-03-06T08:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn latest_boundary_moves_past_the_daylight_saving_gap() {
+        let early = rule("early", &[], "02:30");
+
+        // Madrid skips 02:00-03:00 on 2026-03-29, so the rule fires at 03:30 CEST.
+        assert_eq!(
+            latest_boundary(&early, utc("2026-03-29T12:00:00Z")),
+            Some(utc("2026-03-29T01:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn due_schedule_picks_the_latest_boundary_since_last_check() {
+        let schedules = vec![
+            rule("work", &weekdays(), "09:00"),
+            rule("personal", &weekdays(), "18:00"),
+        ];
+
+        // Asleep from Tuesday 08:00 to Wednesday 08:30 Madrid time: Tuesday 18:00 wins.
+        let due = due_schedule(
+            &schedules,
+            utc("2026-03-10T07:00:00Z"),
+            utc("2026-03-11T07:30:00Z"),
+        );
+        assert_eq!(due.map(|r| r.profile_id.as_str()), Some("personal"));
+
+        let due = due_schedule(
+            &schedules,
+            utc("2026-03-11T07:30:00Z"),
+            utc("2026-03-11T07:59:00Z"),
+        );
+        assert_eq!(due, None);
+    }
+
+    #[test]
+    fn schedule_tick_starts_the_clock_then_applies_or_skips() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store.data.schedules = vec![rule("work", &weekdays(), "09:00")];
+
+        assert_eq!(
+            schedule_tick(&mut store, utc("2026-03-11T07:00:00Z")),
+            ScheduleTick::Started
+        );
+        assert_eq!(
+            schedule_tick(&mut store, utc("2026-03-11T07:30:00Z")),
+            ScheduleTick::Idle
+        );
+        assert_eq!(
+            schedule_tick(&mut store, utc("2026-03-11T08:00:30Z")),
+            ScheduleTick::Apply("work".to_string())
+        );
+
+        store.data.automation_paused = true;
+        assert_eq!(
+            schedule_tick(&mut store, utc("2026-03-12T08:00:30Z")),
+            ScheduleTick::Skipped
+        );
+        // A boundary skipped while paused is not replayed after resuming.
+        store.data.automation_paused = false;
+        assert_eq!(
+            schedule_tick(&mut store, utc("2026-03-12T08:01:00Z")),
+            ScheduleTick::Idle
+        );
+    }
+
+    #[test]
+    fn validate_schedules_checks_profiles_times_and_zones() {
+        let profiles = vec![Profile {
+            id: "work".to_string(),
+            ..Profile::new("Work".to_string())
+        }];
+        let valid = rule("work", &[], "09:00");
+        assert!(validate_schedules(std::slice::from_ref(&valid), &profiles).is_ok());
+
+        let gone = rule("gone", &[], "09:00");
+        assert!(validate_schedules(&[gone], &profiles).is_err());
+
+        let late = rule("work", &[], "25:00");
+        assert!(validate_schedules(&[late], &profiles)
+            .unwrap_err()
+            .contains("25:00"));
+
+        let mut mars = valid.clone();
+        mars.timezone = Some("Mars/Olympus".to_string());
+        assert!(validate_schedules(&[mars], &profiles)
+            .unwrap_err()
+            .contains("time zone"));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::commands::dock::request_profile_switch;
+use crate::store::{Profile, Settings, Store};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShortcutAction {
+    Apply(String),
+    Next,
+    Previous,
+    ToggleLast,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutBinding {
+    pub shortcut: Shortcut,
+    pub accelerator: String,
+    pub action: ShortcutAction,
+    pub label: String,
+}
+
+pub(crate) fn parse_accelerator(accelerator: &str) -> Result<Shortcut, String> {
+    let shortcut: Shortcut = accelerator
+        .trim()
+        .parse()
+        .map_err(|_| format!("\"{}\" is not a valid shortcut", accelerator))?;
+    if shortcut.mods.is_empty() {
+        return Err(format!(
+            "\"{}\" needs at least one modifier key such as Cmd, Ctrl, Alt or Shift",
+            accelerator
+        ));
+    }
+    Ok(shortcut)
+}
+
+pub(crate) fn shortcut_bindings(
+    profiles: &[Profile],
+    settings: &Settings,
+) -> Result<Vec<ShortcutBinding>, String> {
+    let global = [
+        (
+            &settings.next_profile_shortcut,
+            ShortcutAction::Next,
+            "Next profile".to_string(),
+        ),
+        (
+            &settings.previous_profile_shortcut,
+            ShortcutAction::Previous,
+            "Previous profile".to_string(),
+        ),
+        (
+            &settings.toggle_last_profile_shortcut,
+            ShortcutAction::ToggleLast,
+            "Toggle last profile".to_string(),
+        ),
+    ];
+    let per_profile = profiles.iter().map(|profile| {
+        (
+            &profile.shortcut,
+            ShortcutAction::Apply(profile.id.clone()),
+            format!("\"{}\"", profile.name),
+        )
+    });
+
+    let mut bindings: Vec<ShortcutBinding> = Vec::new();
+    for (accelerator, action, label) in global.into_iter().chain(per_profile) {
+        let Some(accelerator) = accelerator
+            .as_deref()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+        else {
+            continue;
+        };
+        let shortcut =
+            parse_accelerator(accelerator).map_err(|e| format!("{} for {}", e, label))?;
+        if let Some(existing) = bindings.iter().find(|b| b.shortcut == shortcut) {
+            return Err(format!(
+                "{} is used by both {} and {}",
+                accelerator, existing.label, label
+            ));
+        }
+        bindings.push(ShortcutBinding {
+            shortcut,
+            accelerator: accelerator.to_string(),
+            action,
+            label,
+        });
+    }
+    Ok(bindings)
+}
+
+pub(crate) fn resolve_action(store: &Store, action: &ShortcutAction) -> Option<String> {
+    match action {
+        ShortcutAction::Apply(id) => store
+            .data
+            .profiles
+            .iter()
+            .any(|p| &p.id == id)
+            .then(|| id.clone()),
+        ShortcutAction::Next => store.adjacent_profile_id(1),
+        ShortcutAction::Previous => store.adjacent_profile_id(-1),
+        ShortcutAction::ToggleLast => store.last_profile_id(),
+    }
+}
+
+fn trigger_action(app: &AppHandle, action: &ShortcutAction) {
+    let target = {
+        let state = app.state::<std::sync::Mutex<Store>>();
+        let store = match state.lock() {
+            Ok(store) => store,
+            Err(_) => return,
+        };
+        resolve_action(&store, action)
+    };
+    if let Some(id) = target {
+        request_profile_switch(app, id);
+    }
+}
+
+pub fn register_shortcuts(app: &AppHandle, store: &Store) {
+    let shortcuts = app.global_shortcut();
+    if let Err(e) = shortcuts.unregister_all() {
+        eprintln!("Failed to unregister shortcuts: {}", e);
+    }
+
+    let bindings = match shortcut_bindings(&store.data.profiles, &store.data.settings) {
+        Ok(bindings) => bindings,
+        Err(e) => {
+            eprintln!("Skipping shortcut registration: {}", e);
+            return;
+        }
+    };
+    for binding in bindings {
+        let action = binding.action.clone();
+        let registered = shortcuts.on_shortcut(binding.shortcut, move |app, _, event| {
+            if event.state == ShortcutState::Pressed {
+                trigger_action(app, &action);
+            }
+        });
+        if let Err(e) = registered {
+            eprintln!(
+                "Failed to register {} for {}: {}",
+                binding.accelerator, binding.label, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn profile_with_shortcut(id: &str, name: &str, shortcut: Option<&str>) -> Profile {
+        let mut profile = Profile::new(name.to_string());
+        profile.id = id.to_string();
+        profile.shortcut = shortcut.map(str::to_string);
+        profile
+    }
+
+    #[test]
+    fn parse_accelerator_requires_a_modifier_and_known_key() {
+        assert!(parse_accelerator("Cmd+Alt+1").is_ok());
+        assert!(parse_accelerator(" Shift+F5 ").is_ok());
+        assert!(parse_accelerator("K").unwrap_err().contains("modifier"));
+        assert!(parse_accelerator("Cmd+NotAKey")
+            .unwrap_err()
+            .contains("not a valid shortcut"));
+        assert!(parse_accelerator("Cmd+").is_err());
+    }
+
+    #[test]
+    fn shortcut_bindings_collects_global_and_profile_shortcuts() {
+        let settings = Settings {
+            next_profile_shortcut: Some("Ctrl+Alt+Right".to_string()),
+            toggle_last_profile_shortcut: Some("  ".to_string()),
+            ..Settings::default()
+        };
+        let profiles = vec![
+            profile_with_shortcut("a", "Work", Some("Ctrl+Alt+1")),
+            profile_with_shortcut("b", "Home", None),
+        ];
+
+        let bindings = shortcut_bindings(&profiles, &settings).unwrap();
+
+        let actions: Vec<_> = bindings.iter().map(|b| b.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![ShortcutAction::Next, ShortcutAction::Apply("a".to_string())]
+        );
+    }
+
+    #[test]
+    fn shortcut_bindings_reports_conflicts_regardless_of_modifier_order() {
+        let settings = Settings {
+            previous_profile_shortcut: Some("Shift+Ctrl+P".to_string()),
+            ..Settings::default()
+        };
+        let profiles = vec![profile_with_shortcut("a", "Work", Some("Ctrl+Shift+P"))];
+
+        let err = shortcut_bindings(&profiles, &settings).unwrap_err();
+
+        assert!(err.contains("Previous profile"));
+        assert!(err.contains("\"Work\""));
+    }
+
+    #[test]
+    fn shortcut_bindings_names_the_profile_with_an_invalid_shortcut() {
+        let profiles = vec![profile_with_shortcut("a", "Work", Some("Hyper+1"))];
+
+        let err = shortcut_bindings(&profiles, &Settings::default()).unwrap_err();
+
+        assert!(err.contains("for \"Work\""));
+    }
+
+    #[test]
+    fn resolve_action_uses_profile_order_and_history() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store
+            .data
+            .profiles
+            .push(profile_with_shortcut("a", "Work", None));
+        store
+            .data
+            .profiles
+            .push(profile_with_shortcut("b", "Home", None));
+        store.data.active_profile_id = Some("a".to_string());
+        store.data.recent_profile_ids = vec!["a".to_string(), "b".to_string()];
+
+        let resolve = |action| resolve_action(&store, &action);
+        assert_eq!(resolve(ShortcutAction::Next).as_deref(), Some("b"));
+        assert_eq!(resolve(ShortcutAction::ToggleLast).as_deref(), Some("b"));
+        assert_eq!(resolve(ShortcutAction::Apply("gone".to_string())), None);
+    }
+}
@@ -0,0 +1,592 @@
+use crate::commands::dock::apply_profile;
+use crate::store::{Profile, Signal, SignalCondition, SignalFiring, SignalRule, Store};
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const SIGNAL_INTERVAL: Duration = Duration::from_secs(15);
+/// File-system events tend to come in bursts, such as a volume mounting.
+const SIGNAL_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Where rules read the state of the Mac from.
+pub(crate) trait SignalProvider {
+    fn path_exists(&self, path: &Path) -> bool;
+    fn read_file(&self, path: &Path) -> Option<String>;
+    fn hostname(&self) -> String;
+}
+
+pub(crate) struct SystemSignals;
+
+impl SignalProvider for SystemSignals {
+    fn path_exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn read_file(&self, path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn hostname(&self) -> String {
+        gethostname::gethostname().to_string_lossy().to_string()
+    }
+}
+
+/// Expands a leading `~/` to the home folder.
+pub(crate) fn expand_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn normalize_hostname(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    name.strip_suffix(".local").unwrap_or(&name).to_string()
+}
+
+/// Reads `KEY=value` lines, skipping comments and an `export ` prefix. The
+/// last assignment wins, as it would when sourcing the file.
+fn flag_value(text: &str, key: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.strip_prefix("export ").unwrap_or(line).split_once('='))
+        .filter(|(name, _)| name.trim() == key)
+        .map(|(_, value)| {
+            value
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+        .next_back()
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.to_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(crate) struct ConditionResult {
+    pub met: bool,
+    /// What was observed, such as "/Volumes/Work exists".
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(crate) struct RuleEvaluation {
+    pub rule_id: String,
+    pub matched: bool,
+    pub conditions: Vec<ConditionResult>,
+}
+
+pub(crate) fn check_condition(
+    condition: &SignalCondition,
+    provider: &dyn SignalProvider,
+) -> ConditionResult {
+    let (holds, description) = match &condition.signal {
+        Signal::PathExists { path } => {
+            if provider.path_exists(&expand_path(path)) {
+                (true, format!("{} exists", path))
+            } else {
+                (false, format!("{} does not exist", path))
+            }
+        }
+        Signal::EnvFlag { file, key, value } => {
+            match provider
+                .read_file(&expand_path(file))
+                .and_then(|text| flag_value(&text, key))
+            {
+                Some(actual) if value.as_ref().map_or(is_truthy(&actual), |v| *v == actual) => {
+                    (true, format!("{}={} in {}", key, actual, file))
+                }
+                Some(actual) => (false, format!("{}={} in {}", key, actual, file)),
+                None => (false, format!("{} is not set in {}", key, file)),
+            }
+        }
+        Signal::Hostname { name } => {
+            let hostname = provider.hostname();
+            let holds = normalize_hostname(&hostname) == normalize_hostname(name);
+            (holds, format!("hostname is {}", hostname))
+        }
+    };
+    ConditionResult {
+        met: holds != condition.negate,
+        description,
+    }
+}
+
+pub(crate) fn evaluate_rule(rule: &SignalRule, provider: &dyn SignalProvider) -> RuleEvaluation {
+    let conditions: Vec<ConditionResult> = rule
+        .conditions
+        .iter()
+        .map(|condition| check_condition(condition, provider))
+        .collect();
+    RuleEvaluation {
+        rule_id: rule.id.clone(),
+        matched: rule.enabled
+            && !conditions.is_empty()
+            && conditions.iter().all(|condition| condition.met),
+        conditions,
+    }
+}
+
+/// The matching rule with the highest priority, the earliest in the list
+/// among equals, with the observations that made it match.
+pub(crate) fn winning_rule<'a>(
+    rules: &'a [SignalRule],
+    provider: &dyn SignalProvider,
+) -> Option<(&'a SignalRule, RuleEvaluation)> {
+    rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .map(|rule| (rule, evaluate_rule(rule, provider)))
+        .filter(|(_, evaluation)| evaluation.matched)
+        .rev()
+        .max_by_key(|(rule, _)| rule.priority)
+}
+
+pub(crate) fn validate_signal_rules(
+    rules: &[SignalRule],
+    profiles: &[Profile],
+) -> Result<(), String> {
+    for rule in rules {
+        if !profiles.iter().any(|p| p.id == rule.profile_id) {
+            return Err(format!(
+                "Rule \"{}\" refers to a profile that no longer exists",
+                rule.name
+            ));
+        }
+        if rule.conditions.is_empty() {
+            return Err(format!(
+                "Rule \"{}\" needs at least one condition",
+                rule.name
+            ));
+        }
+        for condition in &rule.conditions {
+            let path = match &condition.signal {
+                Signal::PathExists { path } => path,
+                Signal::EnvFlag { file, key, .. } => {
+                    if key.is_empty() {
+                        return Err(format!("Rule \"{}\" has a flag without a name", rule.name));
+                    }
+                    file
+                }
+                Signal::Hostname { name } => {
+                    if name.is_empty() {
+                        return Err(format!("Rule \"{}\" has an empty hostname", rule.name));
+                    }
+                    continue;
+                }
+            };
+            if !expand_path(path).is_absolute() {
+                return Err(format!(
+                    "\"{}\" should be an absolute path or start with ~/",
+                    path
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum SignalTick {
+    Idle,
+    Changed,
+    Apply(String),
+}
+
+/// Switches only when a different rule starts winning, so a manual switch
+/// sticks until the signals change.
+pub(crate) fn signal_tick(
+    store: &mut Store,
+    provider: &dyn SignalProvider,
+    now: chrono::DateTime<chrono::Utc>,
+) -> SignalTick {
+    if store.data.automation_paused {
+        return SignalTick::Idle;
+    }
+    // Rules left pointing at a deleted profile never win.
+    let rules: Vec<SignalRule> = store
+        .data
+        .signal_rules
+        .iter()
+        .filter(|rule| store.data.profiles.iter().any(|p| p.id == rule.profile_id))
+        .cloned()
+        .collect();
+    let Some((rule, evaluation)) = winning_rule(&rules, provider) else {
+        return match store.data.signal_rule_id.take() {
+            Some(_) => SignalTick::Changed,
+            None => SignalTick::Idle,
+        };
+    };
+    if store.data.signal_rule_id.as_ref() == Some(&rule.id) {
+        return SignalTick::Idle;
+    }
+    let firing = SignalFiring {
+        rule_id: rule.id.clone(),
+        rule_name: rule.name.clone(),
+        profile_id: rule.profile_id.clone(),
+        reasons: evaluation
+            .conditions
+            .into_iter()
+            .map(|condition| condition.description)
+            .collect(),
+        at: now.to_rfc3339(),
+    };
+    store.data.signal_rule_id = Some(firing.rule_id.clone());
+    let already_active = store.data.active_profile_id.as_ref() == Some(&firing.profile_id);
+    let profile_id = firing.profile_id.clone();
+    store.data.last_signal_firing = Some(firing);
+    if already_active {
+        SignalTick::Changed
+    } else {
+        SignalTick::Apply(profile_id)
+    }
+}
+
+/// Folders to watch so a rule is re-checked as soon as its paths change: the
+/// closest existing folder above each path, since the path itself may not
+/// exist yet.
+pub(crate) fn watch_dirs(rules: &[SignalRule]) -> HashSet<PathBuf> {
+    rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .flat_map(|rule| &rule.conditions)
+        .filter_map(|condition| match &condition.signal {
+            Signal::PathExists { path } => Some(expand_path(path)),
+            Signal::EnvFlag { file, .. } => Some(expand_path(file)),
+            Signal::Hostname { .. } => None,
+        })
+        .filter_map(|path| {
+            path.ancestors()
+                .skip(1)
+                .find(|dir| dir.is_dir())
+                .map(Path::to_path_buf)
+        })
+        .collect()
+}
+
+fn run_signals(app: &AppHandle) -> HashSet<PathBuf> {
+    let (tick, dirs) = {
+        let state = app.state::<Mutex<Store>>();
+        let Ok(mut store) = state.lock() else {
+            return HashSet::new();
+        };
+        let tick = signal_tick(&mut store, &SystemSignals, chrono::Utc::now());
+        if tick != SignalTick::Idle {
+            if let Err(e) = store.save() {
+                eprintln!("Failed to save rule state: {}", e);
+            }
+        }
+        if let (SignalTick::Apply(_), Some(firing)) = (&tick, &store.data.last_signal_firing) {
+            eprintln!(
+                "Rule \"{}\" fired: {}",
+                firing.rule_name,
+                firing.reasons.join(", ")
+            );
+        }
+        (tick, watch_dirs(&store.data.signal_rules))
+    };
+    if let SignalTick::Apply(id) = tick {
+        if let Err(e) = apply_profile(id, app.clone(), app.state::<Mutex<Store>>()) {
+            eprintln!("Failed to apply profile for rule: {}", e);
+        }
+    }
+    dirs
+}
+
+/// Wakes the rule engine early, for example after the rules are edited.
+pub(crate) struct SignalEngine {
+    wake: Sender<()>,
+}
+
+impl SignalEngine {
+    pub fn wake(&self) {
+        let _ = self.wake.send(());
+    }
+}
+
+pub fn start_signal_engine(app: &AppHandle) {
+    let (wake, events) = channel();
+    app.manage(SignalEngine { wake: wake.clone() });
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut watcher = notify::recommended_watcher(move |_| {
+            let _ = wake.send(());
+        })
+        .map_err(|e| eprintln!("Failed to watch rule paths: {}", e))
+        .ok();
+        let mut watched: HashSet<PathBuf> = HashSet::new();
+        loop {
+            let dirs = run_signals(&app);
+            if let Some(watcher) = watcher.as_mut() {
+                for dir in watched.difference(&dirs) {
+                    let _ = watcher.unwatch(dir);
+                }
+                for dir in dirs.difference(&watched) {
+                    if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                        eprintln!("Failed to watch {}: {}", dir.display(), e);
+                    }
+                }
+            }
+            watched = dirs;
+            if events.recv_timeout(SIGNAL_INTERVAL).is_ok() {
+                std::thread::sleep(SIGNAL_DEBOUNCE);
+                while events.try_recv().is_ok() {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::fixtures::{store_with_profiles, utc};
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct FakeSignals {
+        paths: HashSet<PathBuf>,
+        files: HashMap<PathBuf, String>,
+        hostname: String,
+    }
+
+    impl SignalProvider for FakeSignals {
+        fn path_exists(&self, path: &Path) -> bool {
+            self.paths.contains(path) || self.files.contains_key(path)
+        }
+
+        fn read_file(&self, path: &Path) -> Option<String> {
+            self.files.get(path).cloned()
+        }
+
+        fn hostname(&self) -> String {
+            self.hostname.clone()
+        }
+    }
+
+    fn condition(signal: Signal) -> SignalCondition {
+        SignalCondition {
+            signal,
+            negate: false,
+        }
+    }
+
+    fn path_exists(path: &str) -> Signal {
+        Signal::PathExists {
+            path: path.to_string(),
+        }
+    }
+
+    fn rule(
+        id: &str,
+        profile_id: &str,
+        priority: i32,
+        conditions: Vec<SignalCondition>,
+    ) -> SignalRule {
+        SignalRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            profile_id: profile_id.to_string(),
+            conditions,
+            priority,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn check_condition_describes_paths_flags_and_hostnames() {
+        let signals = FakeSignals {
+            paths: HashSet::from([PathBuf::from("/Volumes/Work")]),
+            files: HashMap::from([(
+                PathBuf::from("/etc/dockflags"),
+                "# flags\nexport VPN=\"on\"\nDEMO=0\n".to_string(),
+            )]),
+            hostname: "Studio-Mac.local".to_string(),
+        };
+
+        let mounted = check_condition(&condition(path_exists("/Volumes/Work")), &signals);
+        assert_eq!(
+            mounted,
+            ConditionResult {
+                met: true,
+                description: "/Volumes/Work exists".to_string()
+            }
+        );
+
+        let vpn = condition(Signal::EnvFlag {
+            file: "/etc/dockflags".to_string(),
+            key: "VPN".to_string(),
+            value: None,
+        });
+        assert!(check_condition(&vpn, &signals).met);
+        let demo = condition(Signal::EnvFlag {
+            file: "/etc/dockflags".to_string(),
+            key: "DEMO".to_string(),
+            value: None,
+        });
+        assert_eq!(
+            check_condition(&demo, &signals).description,
+            "DEMO=0 in /etc/dockflags"
+        );
+        assert!(!check_condition(&demo, &signals).met);
+
+        let studio = condition(Signal::Hostname {
+            name: "studio-mac".to_string(),
+        });
+        assert!(check_condition(&studio, &signals).met);
+
+        let unplugged = SignalCondition {
+            signal: path_exists("/Volumes/Work"),
+            negate: true,
+        };
+        assert!(!check_condition(&unplugged, &signals).met);
+    }
+
+    #[test]
+    fn winning_rule_prefers_priority_then_list_order() {
+        let signals = FakeSignals {
+            paths: HashSet::from([PathBuf::from("/Volumes/Work"), PathBuf::from("/tmp/vpn")]),
+            ..Default::default()
+        };
+        let rules = vec![
+            rule(
+                "office",
+                "work",
+                0,
+                vec![condition(path_exists("/Volumes/Work"))],
+            ),
+            rule("vpn", "remote", 5, vec![condition(path_exists("/tmp/vpn"))]),
+            rule(
+                "vpn-again",
+                "other",
+                5,
+                vec![condition(path_exists("/tmp/vpn"))],
+            ),
+            rule(
+                "both",
+                "hybrid",
+                9,
+                vec![
+                    condition(path_exists("/tmp/vpn")),
+                    condition(path_exists("/Volumes/Home")),
+                ],
+            ),
+        ];
+
+        let (winner, evaluation) = winning_rule(&rules, &signals).unwrap();
+        assert_eq!(winner.id, "vpn");
+        assert_eq!(evaluation.conditions[0].description, "/tmp/vpn exists");
+    }
+
+    #[test]
+    fn signal_tick_fires_once_per_winning_rule_and_records_why() {
+        let mut signals = FakeSignals {
+            paths: HashSet::from([PathBuf::from("/Volumes/Work")]),
+            ..Default::default()
+        };
+        let mut store = store_with_profiles(&["work", "home"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.signal_rules = vec![rule(
+            "office",
+            "work",
+            0,
+            vec![condition(path_exists("/Volumes/Work"))],
+        )];
+
+        assert_eq!(
+            signal_tick(&mut store, &signals, utc("2026-03-02T09:00:00Z")),
+            SignalTick::Apply("work".to_string())
+        );
+        let firing = store.data.last_signal_firing.clone().unwrap();
+        assert_eq!(firing.reasons, vec!["/Volumes/Work exists".to_string()]);
+
+        // Switched away by hand: the same rule doesn't fire again.
+        store.data.active_profile_id = Some("home".to_string());
+        assert_eq!(
+            signal_tick(&mut store, &signals, utc("2026-03-02T09:01:00Z")),
+            SignalTick::Idle
+        );
+
+        signals.paths.clear();
+        assert_eq!(
+            signal_tick(&mut store, &signals, utc("2026-03-02T09:02:00Z")),
+            SignalTick::Changed
+        );
+        assert_eq!(store.data.signal_rule_id, None);
+        assert_eq!(store.data.last_signal_firing, Some(firing));
+
+        store.data.automation_paused = true;
+        signals.paths.insert(PathBuf::from("/Volumes/Work"));
+        assert_eq!(
+            signal_tick(&mut store, &signals, utc("2026-03-02T09:03:00Z")),
+            SignalTick::Idle
+        );
+    }
+
+    #[test]
+    fn signal_tick_skips_rules_for_deleted_profiles() {
+        let signals = FakeSignals {
+            paths: HashSet::from([PathBuf::from("/Volumes/Work")]),
+            ..Default::default()
+        };
+        let mut store = store_with_profiles(&["home"]);
+        let mounted = || vec![condition(path_exists("/Volumes/Work"))];
+        store.data.signal_rules = vec![
+            rule("office", "deleted", 0, mounted()),
+            rule("fallback", "home", 1, mounted()),
+        ];
+
+        assert_eq!(
+            signal_tick(&mut store, &signals, utc("2026-03-02T09:00:00Z")),
+            SignalTick::Apply("home".to_string())
+        );
+        assert_eq!(store.data.signal_rule_id.as_deref(), Some("fallback"));
+    }
+
+    #[test]
+    fn watch_dirs_use_the_closest_existing_folder() {
+        let root = crate::bundle::fixtures::temp_dir("dockswitcher-signals");
+        let marker = root.join("vpn").join("connected");
+        let rules = vec![rule(
+            "vpn",
+            "remote",
+            0,
+            vec![condition(path_exists(&marker.to_string_lossy()))],
+        )];
+
+        assert_eq!(watch_dirs(&rules), HashSet::from([root.clone()]));
+        std::fs::create_dir_all(root.join("vpn")).unwrap();
+        assert_eq!(watch_dirs(&rules), HashSet::from([root.join("vpn")]));
+    }
+
+    #[test]
+    fn validate_signal_rules_needs_conditions_and_absolute_paths() {
+        let profiles = vec![Profile {
+            id: "work".to_string(),
+            ..Profile::new("Work".to_string())
+        }];
+        let valid = rule("office", "work", 0, vec![condition(path_exists("~/VPN"))]);
+        assert!(validate_signal_rules(std::slice::from_ref(&valid), &profiles).is_ok());
+
+        let empty = rule("office", "work", 0, Vec::new());
+        assert!(validate_signal_rules(&[empty], &profiles).is_err());
+
+        let relative = rule("office", "work", 0, vec![condition(path_exists("VPN"))]);
+        assert!(validate_signal_rules(&[relative], &profiles)
+            .unwrap_err()
+            .contains("absolute"));
+
+        let gone = rule("office", "gone", 0, valid.conditions.clone());
+        assert!(validate_signal_rules(&[gone], &profiles).is_err());
+    }
+}
@@ -1,6 +1,23 @@
+use crate::icon_cache::{migrate_inline_icons, IconCache};
+use crate::shortcuts::shortcut_bindings;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DockItemKind {
+    #[default]
+    App,
+    Spacer,
+    SmallSpacer,
+}
+
+impl DockItemKind {
+    pub fn is_app(&self) -> bool {
+        *self == DockItemKind::App
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AppEntry {
@@ -10,6 +27,25 @@ pub struct AppEntry {
     pub icon: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bundle_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "DockItemKind::is_app")]
+    pub kind: DockItemKind,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DockLocks {
+    pub contents: bool,
+    pub size: bool,
+    pub position: bool,
+    pub magnify: bool,
+}
+
+impl DockLocks {
+    pub fn any(&self) -> bool {
+        self.contents || self.size || self.position || self.magnify
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -17,21 +53,232 @@ pub struct Profile {
     pub id: String,
     pub name: String,
     pub apps: Vec<AppEntry>,
+    #[serde(default)]
+    pub locks: DockLocks,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortcut: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayDisplay {
+    #[default]
+    Icon,
+    Name,
+    ProfileIcon,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileOrder {
+    #[default]
+    Manual,
+    RecentlyUsed,
+}
+
+impl Profile {
+    pub fn new(name: String) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            apps: Vec::new(),
+            locks: DockLocks::default(),
+            symbol: None,
+            shortcut: None,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct Settings {
     pub launch_at_login: bool,
     pub confirm_before_switch: bool,
+    #[serde(default)]
+    pub update_moved_app_paths: bool,
+    #[serde(default)]
+    pub app_roots: Vec<String>,
+    #[serde(default)]
+    pub tray_display: TrayDisplay,
+    #[serde(default)]
+    pub tray_profile_order: ProfileOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_profile_shortcut: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_profile_shortcut: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toggle_last_profile_shortcut: Option<String>,
 }
 
-impl Default for Settings {
-    fn default() -> Self {
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduleRule {
+    pub id: String,
+    pub profile_id: String,
+    /// Days the rule fires on; empty means every day.
+    #[serde(default)]
+    pub weekdays: Vec<chrono::Weekday>,
+    /// Time of day as `HH:MM`.
+    pub time: String,
+    /// IANA time zone such as `Europe/Madrid`; the Mac's own zone when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CalendarRule {
+    pub id: String,
+    /// Regular expression matched case-insensitively against event titles.
+    pub pattern: String,
+    pub profile_id: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+/// A profile put in place for a while, and what to put back when it ends.
+/// Calendar events, project folders and temporary profiles each keep one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    /// The profile applied, or none for an app list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_profile_id: Option<String>,
+    /// The Dock as it was when no profile was active.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_apps: Vec<AppEntry>,
+}
+
+impl Session {
+    /// Started with no profile active, so only a copy of the Dock taken before
+    /// the session's apps go in can bring it back.
+    pub(crate) fn needs_dock_snapshot(&self) -> bool {
+        self.previous_profile_id.is_none() && self.previous_apps.is_empty()
+    }
+}
+
+/// What ending a session puts back.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Restore {
+    Nothing,
+    Profile(String),
+    Apps(Vec<AppEntry>),
+}
+
+/// The calendar event that currently owns the Dock, and what to go back to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CalendarSession {
+    pub event_key: String,
+    pub rule_id: String,
+    #[serde(flatten)]
+    pub session: Session,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Signal {
+    /// A file or folder exists, such as a mounted volume or a VPN marker file.
+    PathExists {
+        path: String,
+    },
+    /// A `KEY=value` line in a flag file is set, or set to `value` when given.
+    EnvFlag {
+        file: String,
+        key: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
+    Hostname {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalCondition {
+    #[serde(flatten)]
+    pub signal: Signal,
+    #[serde(default)]
+    pub negate: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalRule {
+    pub id: String,
+    pub name: String,
+    pub profile_id: String,
+    /// All conditions must hold for the rule to match.
+    pub conditions: Vec<SignalCondition>,
+    /// The matching rule with the highest priority wins.
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+/// Why a signal rule last switched profiles.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalFiring {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub profile_id: String,
+    pub reasons: Vec<String>,
+    pub at: String,
+}
+
+/// The project folder whose `.dockprofile` set the Dock, and what to go back to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DirectorySession {
+    pub root: String,
+    #[serde(flatten)]
+    pub session: Session,
+}
+
+/// A profile applied for a while, and the Dock to go back to afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemporarySession {
+    pub ends_at: String,
+    #[serde(flatten)]
+    pub session: Session,
+}
+
+pub const MAX_RECENT_PROFILES: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApplyResult {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub at: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<String>,
+}
+
+impl ApplyResult {
+    pub fn new(
+        profile_id: String,
+        profile_name: String,
+        result: &Result<Vec<String>, String>,
+    ) -> Self {
         Self {
-            launch_at_login: false,
-            confirm_before_switch: false,
+            profile_id,
+            profile_name,
+            at: chrono::Utc::now().to_rfc3339(),
+            ok: result.is_ok(),
+            messages: match result {
+                Ok(warnings) => warnings.clone(),
+                Err(error) => vec![error.clone()],
+            },
         }
     }
 }
@@ -42,6 +289,34 @@ pub struct StoreData {
     pub active_profile_id: Option<String>,
     pub settings: Settings,
     pub schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_apply: Option<ApplyResult>,
+    #[serde(default)]
+    pub recent_profile_ids: Vec<String>,
+    #[serde(default)]
+    pub schedules: Vec<ScheduleRule>,
+    #[serde(default)]
+    pub automation_paused: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_checked_at: Option<String>,
+    /// Paths of local `.ics` files.
+    #[serde(default)]
+    pub calendar_files: Vec<String>,
+    #[serde(default)]
+    pub calendar_rules: Vec<CalendarRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_session: Option<CalendarSession>,
+    #[serde(default)]
+    pub signal_rules: Vec<SignalRule>,
+    /// The signal rule currently winning, so it only switches when it starts to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_rule_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_signal_firing: Option<SignalFiring>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory_session: Option<DirectorySession>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporary_session: Option<TemporarySession>,
 }
 
 impl Default for StoreData {
@@ -51,6 +326,19 @@ impl Default for StoreData {
             active_profile_id: None,
             settings: Settings::default(),
             schema_version: 1,
+            last_apply: None,
+            recent_profile_ids: Vec::new(),
+            schedules: Vec::new(),
+            automation_paused: false,
+            schedule_checked_at: None,
+            calendar_files: Vec::new(),
+            calendar_rules: Vec::new(),
+            calendar_session: None,
+            signal_rules: Vec::new(),
+            signal_rule_id: None,
+            last_signal_firing: None,
+            directory_session: None,
+            temporary_session: None,
         }
     }
 }
@@ -70,9 +358,13 @@ impl Default for Store {
 }
 
 impl Store {
-    fn config_path() -> PathBuf {
+    pub fn data_dir() -> PathBuf {
         let base = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-        base.join("com.dockswitcher.app").join("config.json")
+        base.join("com.dockswitcher.app")
+    }
+
+    pub(crate) fn config_path() -> PathBuf {
+        Self::data_dir().join("config.json")
     }
 
     #[cfg(test)]
@@ -92,7 +384,12 @@ impl Store {
         if path.exists() {
             let contents = fs::read_to_string(&path)?;
             let data: StoreData = serde_json::from_str(&contents)?;
-            Ok(Self { data, path })
+            let mut store = Self { data, path };
+            let icons = IconCache::new(store.path.with_file_name("icons"));
+            if migrate_inline_icons(&mut store.data.profiles, &icons) {
+                store.save()?;
+            }
+            Ok(store)
         } else {
             Ok(Self {
                 data: StoreData::default(),
@@ -101,6 +398,10 @@ impl Store {
         }
     }
 
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent)?;
@@ -117,6 +418,243 @@ impl Store {
 
         Ok(())
     }
+
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let id = self.data.active_profile_id.as_ref()?;
+        self.data.profiles.iter().find(|p| &p.id == id)
+    }
+
+    /// Finds a profile by id or exact name, falling back to a case-insensitive
+    /// name match when that is unambiguous.
+    pub fn find_profile_index(&self, query: &str) -> Result<usize, String> {
+        let profiles = &self.data.profiles;
+        if let Some(index) = profiles
+            .iter()
+            .position(|p| p.id == query || p.name == query)
+        {
+            return Ok(index);
+        }
+        let matches: Vec<usize> = profiles
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.name.eq_ignore_ascii_case(query))
+            .map(|(index, _)| index)
+            .collect();
+        match matches.as_slice() {
+            [index] => Ok(*index),
+            [] => Err(format!("No profile named \"{}\"", query)),
+            _ => Err(format!(
+                "\"{}\" matches several profiles; use its id",
+                query
+            )),
+        }
+    }
+
+    pub fn has_automation(&self) -> bool {
+        self.data.schedules.iter().any(|rule| rule.enabled)
+            || self.data.calendar_rules.iter().any(|rule| rule.enabled)
+            || self.data.signal_rules.iter().any(|rule| rule.enabled)
+    }
+
+    pub fn unique_profile_name(&self, base: &str) -> String {
+        let taken = |name: &str| self.data.profiles.iter().any(|p| p.name == name);
+        if !taken(base) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{} {}", base, n))
+            .find(|name| !taken(name))
+            .unwrap_or_else(|| base.to_string())
+    }
+
+    /// Reads a profile written by `preview::export_profile` and prepares it
+    /// under a fresh id and unused name. The imported shortcut is dropped when
+    /// it clashes with an existing one, which the returned warning explains.
+    pub(crate) fn import_profile(
+        &self,
+        import_path: &Path,
+        name: Option<String>,
+    ) -> Result<(Profile, Option<String>), String> {
+        let contents = fs::read_to_string(import_path)
+            .map_err(|e| format!("Failed to read {}: {}", import_path.display(), e))?;
+        let imported: Profile = serde_json::from_str(&contents).map_err(|e| {
+            format!(
+                "{} is not a DockSwitcher profile: {}",
+                import_path.display(),
+                e
+            )
+        })?;
+
+        let mut profile = Profile::new(self.unique_profile_name(&name.unwrap_or(imported.name)));
+        profile.apps = imported.apps;
+        profile.locks = imported.locks;
+        profile.symbol = imported.symbol;
+        profile.shortcut = imported.shortcut;
+
+        let mut candidates = self.data.profiles.clone();
+        candidates.push(profile.clone());
+        let warning = match shortcut_bindings(&candidates, &self.data.settings) {
+            Ok(_) => None,
+            Err(e) => {
+                profile.shortcut = None;
+                Some(format!("dropped keyboard shortcut: {}", e))
+            }
+        };
+        Ok((profile, warning))
+    }
+
+    pub fn record_apply(&mut self, result: ApplyResult) {
+        if result.ok {
+            self.data.active_profile_id = Some(result.profile_id.clone());
+            self.data
+                .recent_profile_ids
+                .retain(|id| id != &result.profile_id);
+            self.data
+                .recent_profile_ids
+                .insert(0, result.profile_id.clone());
+            self.data.recent_profile_ids.truncate(MAX_RECENT_PROFILES);
+        }
+        self.data.last_apply = Some(result);
+    }
+
+    pub fn profiles_by_recent(&self) -> Vec<&Profile> {
+        let rank = |profile: &Profile| {
+            self.data
+                .recent_profile_ids
+                .iter()
+                .position(|id| id == &profile.id)
+                .unwrap_or(usize::MAX)
+        };
+        let mut profiles: Vec<&Profile> = self.data.profiles.iter().collect();
+        profiles.sort_by_key(|profile| rank(profile));
+        profiles
+    }
+
+    pub fn adjacent_profile_id(&self, step: isize) -> Option<String> {
+        let profiles = &self.data.profiles;
+        let len = profiles.len() as isize;
+        if len == 0 {
+            return None;
+        }
+        let current = self
+            .data
+            .active_profile_id
+            .as_ref()
+            .and_then(|id| profiles.iter().position(|p| &p.id == id));
+        let index = match current {
+            Some(index) => (index as isize + step).rem_euclid(len),
+            None if step < 0 => len - 1,
+            None => 0,
+        };
+        let id = &profiles[index as usize].id;
+        (Some(id) != self.data.active_profile_id.as_ref()).then(|| id.clone())
+    }
+
+    pub fn last_profile_id(&self) -> Option<String> {
+        self.data
+            .recent_profile_ids
+            .iter()
+            .filter(|id| Some(*id) != self.data.active_profile_id.as_ref())
+            .find(|id| self.data.profiles.iter().any(|p| &p.id == *id))
+            .cloned()
+    }
+
+    fn sessions_mut(&mut self) -> impl Iterator<Item = &mut Session> {
+        let data = &mut self.data;
+        let calendar = data.calendar_session.iter_mut().map(|s| &mut s.session);
+        let directory = data.directory_session.iter_mut().map(|s| &mut s.session);
+        let temporary = data.temporary_session.iter_mut().map(|s| &mut s.session);
+        calendar.chain(directory).chain(temporary)
+    }
+
+    /// A session for `profile_id` that goes back to what is active now. When
+    /// it replaces a session that is still on top, it goes back to what that
+    /// one would have.
+    pub(crate) fn next_session(
+        &self,
+        profile_id: Option<String>,
+        replacing: Option<&Session>,
+    ) -> Session {
+        match replacing {
+            Some(old) if old.profile_id == self.data.active_profile_id => Session {
+                profile_id,
+                ..old.clone()
+            },
+            _ => Session {
+                profile_id,
+                previous_profile_id: self.data.active_profile_id.clone(),
+                previous_apps: Vec::new(),
+            },
+        }
+    }
+
+    /// Like `next_session`, also ending the session it replaces.
+    pub(crate) fn start_session(
+        &mut self,
+        profile_id: Option<String>,
+        replacing: Option<Session>,
+    ) -> Session {
+        let session = self.next_session(profile_id, replacing.as_ref());
+        if let Some(old) = replacing {
+            self.hand_off(&old);
+        }
+        session
+    }
+
+    /// Sessions started on top of `ended` go back to what it replaced, since
+    /// its own profile is no longer wanted.
+    pub(crate) fn hand_off(&mut self, ended: &Session) {
+        let Some(profile_id) = ended.profile_id.clone() else {
+            return;
+        };
+        for session in self.sessions_mut() {
+            if session.previous_profile_id.as_ref() == Some(&profile_id) {
+                session.previous_profile_id = ended.previous_profile_id.clone();
+                session.previous_apps = ended.previous_apps.clone();
+            }
+        }
+    }
+
+    /// Ends `session`, already taken out of the store, and says what to put
+    /// back. Nothing is, if its profile has been switched away from since.
+    pub(crate) fn end_session(&mut self, session: Session) -> Restore {
+        self.hand_off(&session);
+        if self.data.active_profile_id != session.profile_id {
+            return Restore::Nothing;
+        }
+        match session.previous_profile_id {
+            Some(previous) if Some(&previous) == session.profile_id.as_ref() => Restore::Nothing,
+            Some(previous) if self.data.profiles.iter().any(|p| p.id == previous) => {
+                Restore::Profile(previous)
+            }
+            None if !session.previous_apps.is_empty() => Restore::Apps(session.previous_apps),
+            _ => Restore::Nothing,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::{Profile, Store};
+    use chrono::{DateTime, Utc};
+    use std::path::PathBuf;
+
+    pub(crate) fn utc(at: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(at)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    /// A store that is never saved, with a profile named in capitals for each id.
+    pub(crate) fn store_with_profiles(ids: &[&str]) -> Store {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        for id in ids {
+            let mut profile = Profile::new(id.to_uppercase());
+            profile.id = id.to_string();
+            store.data.profiles.push(profile);
+        }
+        store
+    }
 }
 
 #[cfg(test)]
@@ -139,7 +677,12 @@ mod tests {
                 path: "/Applications/Safari.app".to_string(),
                 icon: None,
                 bundle_id: Some("com.apple.Safari".to_string()),
+                version: None,
+                kind: DockItemKind::App,
             }],
+            locks: DockLocks::default(),
+            symbol: None,
+            shortcut: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         }
@@ -153,6 +696,7 @@ mod tests {
         assert_eq!(data.schema_version, 1);
         assert!(!data.settings.launch_at_login);
         assert!(!data.settings.confirm_before_switch);
+        assert!(!data.settings.update_moved_app_paths);
     }
 
     #[test]
@@ -234,10 +778,13 @@ mod tests {
             path: "/Applications/Test.app".to_string(),
             icon: None,
             bundle_id: None,
+            version: None,
+            kind: DockItemKind::App,
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(!json.contains("icon"));
         assert!(!json.contains("bundle_id"));
+        assert!(!json.contains("version"));
     }
 
     #[test]
@@ -247,10 +794,28 @@ mod tests {
             path: "/Applications/Test.app".to_string(),
             icon: Some("base64data".to_string()),
             bundle_id: Some("com.test.app".to_string()),
+            version: Some("1.0".to_string()),
+            kind: DockItemKind::App,
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains("icon"));
         assert!(json.contains("bundle_id"));
+        assert!(json.contains("version"));
+    }
+
+    #[test]
+    fn app_entry_kind_defaults_to_app_and_round_trips_spacers() {
+        let entry: AppEntry =
+            serde_json::from_str(r#"{"name":"Test","path":"/Applications/Test.app"}"#).unwrap();
+        assert_eq!(entry.kind, DockItemKind::App);
+        assert!(!serde_json::to_string(&entry).unwrap().contains("kind"));
+
+        let spacer: AppEntry =
+            serde_json::from_str(r#"{"name":"","path":"","kind":"small_spacer"}"#).unwrap();
+        assert_eq!(spacer.kind, DockItemKind::SmallSpacer);
+        assert!(serde_json::to_string(&spacer)
+            .unwrap()
+            .contains(r#""kind":"small_spacer""#));
     }
 
     #[test]
@@ -267,6 +832,7 @@ mod tests {
         let data: StoreData = serde_json::from_str(json).unwrap();
         assert!(data.profiles.is_empty());
         assert_eq!(data.schema_version, 1);
+        assert_eq!(data.settings.tray_display, TrayDisplay::Icon);
     }
 
     #[test]
@@ -280,14 +846,21 @@ mod tests {
                     path: "/Applications/Safari.app".to_string(),
                     icon: None,
                     bundle_id: Some("com.apple.Safari".to_string()),
+                    version: None,
+                    kind: DockItemKind::App,
                 },
                 AppEntry {
                     name: "Visual Studio Code".to_string(),
                     path: "/Applications/Visual Studio Code.app".to_string(),
                     icon: None,
                     bundle_id: None,
+                    version: None,
+                    kind: DockItemKind::App,
                 },
             ],
+            locks: DockLocks::default(),
+            symbol: None,
+            shortcut: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         };
@@ -316,4 +889,250 @@ mod tests {
 
         fs::remove_dir_all(path.parent().unwrap()).ok();
     }
+
+    #[test]
+    fn profile_without_locks_deserializes_unlocked() {
+        let json = r#"{
+            "id": "p1",
+            "name": "Work",
+            "apps": [],
+            "created_at": "2025-01-01T00:00:00Z",
+            "updated_at": "2025-01-01T00:00:00Z"
+        }"#;
+        let profile: Profile = serde_json::from_str(json).unwrap();
+        assert_eq!(profile.locks, DockLocks::default());
+        assert!(!profile.locks.any());
+    }
+
+    #[test]
+    fn dock_locks_partial_fields_roundtrip() {
+        let json = r#"{ "contents": true }"#;
+        let locks: DockLocks = serde_json::from_str(json).unwrap();
+        assert!(locks.contents);
+        assert!(!locks.size);
+        assert!(locks.any());
+    }
+
+    #[test]
+    fn store_load_migrates_inline_icons_to_cache() {
+        use base64::Engine;
+
+        let path = temp_store_path();
+        let mut store = Store::with_path(path.clone());
+        let mut profile = sample_profile("p1", "Work");
+        profile.apps[0].icon =
+            Some(base64::engine::general_purpose::STANDARD.encode(b"\x89PNGdata"));
+        store.data.profiles.push(profile);
+        store.save().unwrap();
+
+        let loaded = Store::load_from(path.clone()).unwrap();
+        let icon_ref = loaded.data.profiles[0].apps[0].icon.clone().unwrap();
+        assert!(crate::icon_cache::is_icon_ref(&icon_ref));
+        assert!(path
+            .with_file_name("icons")
+            .join(format!("{}.png", icon_ref))
+            .exists());
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains(&icon_ref));
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    fn apply_result(profile_id: &str, ok: bool) -> ApplyResult {
+        ApplyResult {
+            profile_id: profile_id.to_string(),
+            profile_name: profile_id.to_string(),
+            at: "2025-01-01T00:00:00Z".to_string(),
+            ok,
+            messages: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn record_apply_tracks_recent_profiles_most_recent_first() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        for id in ["a", "b", "a", "c", "d", "e", "f"] {
+            store.record_apply(apply_result(id, true));
+        }
+
+        assert_eq!(store.data.active_profile_id.as_deref(), Some("f"));
+        assert_eq!(store.data.recent_profile_ids, vec!["f", "e", "d", "c", "a"]);
+        assert_eq!(store.data.recent_profile_ids.len(), MAX_RECENT_PROFILES);
+    }
+
+    #[test]
+    fn record_apply_failure_keeps_active_profile() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store.record_apply(apply_result("a", true));
+        store.record_apply(apply_result("b", false));
+
+        assert_eq!(store.data.active_profile_id.as_deref(), Some("a"));
+        assert_eq!(store.data.recent_profile_ids, vec!["a"]);
+        assert_eq!(store.data.last_apply, Some(apply_result("b", false)));
+    }
+
+    #[test]
+    fn unique_profile_name_appends_counter() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        assert_eq!(store.unique_profile_name("Dock"), "Dock");
+        store.data.profiles.push(sample_profile("p1", "Dock"));
+        store.data.profiles.push(sample_profile("p2", "Dock 2"));
+        assert_eq!(store.unique_profile_name("Dock"), "Dock 3");
+    }
+
+    #[test]
+    fn import_profile_gives_a_fresh_id_and_unused_name() {
+        let path = temp_store_path().with_file_name("Dock.json");
+        fs::write(
+            &path,
+            serde_json::to_string(&sample_profile("p1", "Dock")).unwrap(),
+        )
+        .unwrap();
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store.data.profiles.push(sample_profile("p1", "Dock"));
+
+        let (profile, warning) = store.import_profile(&path, None).unwrap();
+        assert_ne!(profile.id, "p1");
+        assert_eq!(profile.name, "Dock 2");
+        assert_eq!(profile.apps, store.data.profiles[0].apps);
+        assert_eq!(warning, None);
+
+        let (renamed, _) = store
+            .import_profile(&path, Some("Work".to_string()))
+            .unwrap();
+        assert_eq!(renamed.name, "Work");
+        fs::write(&path, "{}").unwrap();
+        assert!(store
+            .import_profile(&path, None)
+            .unwrap_err()
+            .contains("not a DockSwitcher profile"));
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn find_profile_index_prefers_exact_matches() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store.data.profiles.push(sample_profile("p1", "Work"));
+        store.data.profiles.push(sample_profile("p2", "work"));
+        store.data.profiles.push(sample_profile("p3", "Home"));
+
+        assert_eq!(store.find_profile_index("p3"), Ok(2));
+        assert_eq!(store.find_profile_index("work"), Ok(1));
+        assert_eq!(store.find_profile_index("HOME"), Ok(2));
+        assert!(store
+            .find_profile_index("WORK")
+            .unwrap_err()
+            .contains("several"));
+        assert!(store.find_profile_index("Gaming").is_err());
+    }
+
+    #[test]
+    fn adjacent_profile_id_wraps_around_profile_order() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        assert_eq!(store.adjacent_profile_id(1), None);
+        for id in ["a", "b", "c"] {
+            store.data.profiles.push(sample_profile(id, id));
+        }
+
+        assert_eq!(store.adjacent_profile_id(1).as_deref(), Some("a"));
+        assert_eq!(store.adjacent_profile_id(-1).as_deref(), Some("c"));
+
+        store.data.active_profile_id = Some("c".to_string());
+        assert_eq!(store.adjacent_profile_id(1).as_deref(), Some("a"));
+        assert_eq!(store.adjacent_profile_id(-1).as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn last_profile_id_skips_active_and_deleted_profiles() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store.data.profiles.push(sample_profile("a", "A"));
+        store.data.profiles.push(sample_profile("c", "C"));
+        for id in ["a", "b", "c"] {
+            store.record_apply(apply_result(id, true));
+        }
+
+        assert_eq!(store.last_profile_id().as_deref(), Some("a"));
+
+        store.data.recent_profile_ids = vec!["c".to_string()];
+        assert_eq!(store.last_profile_id(), None);
+    }
+
+    #[test]
+    fn profiles_by_recent_puts_used_profiles_first() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        for id in ["a", "b", "c", "d"] {
+            store.data.profiles.push(sample_profile(id, id));
+        }
+        for id in ["c", "a"] {
+            store.record_apply(apply_result(id, true));
+        }
+
+        let ids: Vec<&str> = store
+            .profiles_by_recent()
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn ending_a_covered_session_hands_what_it_replaced_to_the_one_on_top() {
+        let mut store = Store::with_path(PathBuf::from("config.json"));
+        store.data.profiles = vec![
+            sample_profile("home", "Home"),
+            sample_profile("presenting", "Presenting"),
+            sample_profile("work", "Work"),
+        ];
+        store.data.active_profile_id = Some("home".to_string());
+        let session = store.start_session(Some("presenting".to_string()), None);
+        store.data.calendar_session = Some(CalendarSession {
+            event_key: "demo".to_string(),
+            rule_id: "r1".to_string(),
+            session,
+        });
+        store.data.active_profile_id = Some("presenting".to_string());
+        let session = store.start_session(Some("work".to_string()), None);
+        store.data.directory_session = Some(DirectorySession {
+            root: "/Users/me/repo".to_string(),
+            session,
+        });
+        store.data.active_profile_id = Some("work".to_string());
+
+        // The event ends while the folder's profile is on top.
+        let event = store.data.calendar_session.take().unwrap();
+        assert_eq!(store.end_session(event.session), Restore::Nothing);
+        // Leaving the folder skips the event's profile.
+        let folder = store.data.directory_session.take().unwrap();
+        assert_eq!(
+            store.end_session(folder.session),
+            Restore::Profile("home".to_string())
+        );
+    }
+
+    #[test]
+    fn sessions_keep_their_fields_at_the_top_level_of_the_config() {
+        let temporary = TemporarySession {
+            ends_at: "2026-03-02T11:00:00+00:00".to_string(),
+            session: Session {
+                profile_id: Some("p1".to_string()),
+                previous_profile_id: Some("p2".to_string()),
+                previous_apps: Vec::new(),
+            },
+        };
+        let json = serde_json::to_value(&temporary).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ends_at": "2026-03-02T11:00:00+00:00",
+                "profile_id": "p1",
+                "previous_profile_id": "p2",
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<TemporarySession>(json).unwrap(),
+            temporary
+        );
+    }
 }
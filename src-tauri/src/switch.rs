@@ -0,0 +1,206 @@
+use crate::lock::lock_flags;
+use crate::resolver::AppResolver;
+use crate::store::{AppEntry, DockLocks, Profile};
+use serde::Serialize;
+use std::path::Path;
+
+const MAX_LISTED_APPS: usize = 5;
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct SwitchPlan {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub kept: usize,
+    pub missing: Vec<String>,
+    pub locks: DockLocks,
+    pub summary: String,
+}
+
+fn same_app(a: &AppEntry, b: &AppEntry) -> bool {
+    a.path == b.path || (a.bundle_id.is_some() && a.bundle_id == b.bundle_id)
+}
+
+fn list_names(names: &[String]) -> String {
+    let listed = names
+        .iter()
+        .take(MAX_LISTED_APPS)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    match names.len().saturating_sub(MAX_LISTED_APPS) {
+        0 => listed,
+        more => format!("{} and {} more", listed, more),
+    }
+}
+
+fn lock_names(locks: &DockLocks) -> Vec<&'static str> {
+    lock_flags(locks)
+        .into_iter()
+        .filter(|(_, locked)| *locked)
+        .map(|(key, _)| key.trim_end_matches("-immutable"))
+        .collect()
+}
+
+fn describe(plan: &SwitchPlan) -> String {
+    let mut lines = Vec::new();
+    if !plan.added.is_empty() {
+        lines.push(format!("Adds: {}", list_names(&plan.added)));
+    }
+    if !plan.removed.is_empty() {
+        lines.push(format!("Removes: {}", list_names(&plan.removed)));
+    }
+    if plan.kept > 0 {
+        lines.push(format!(
+            "Keeps {} app{}",
+            plan.kept,
+            if plan.kept == 1 { "" } else { "s" }
+        ));
+    }
+    if !plan.missing.is_empty() {
+        lines.push(format!(
+            "Not found on this Mac: {}",
+            list_names(&plan.missing)
+        ));
+    }
+    let locks = lock_names(&plan.locks);
+    if !locks.is_empty() {
+        lines.push(format!("Locks: {}", locks.join(", ")));
+    }
+    if plan.added.is_empty() && plan.removed.is_empty() {
+        lines.push("The Dock already has these apps; they will be re-added in order.".to_string());
+    }
+    lines.join("\n")
+}
+
+/// Apps whose path is gone but whose bundle id `resolver` finds elsewhere
+/// still get placed, so only apps found neither way count as missing.
+pub(crate) fn plan_switch(
+    current: &[AppEntry],
+    profile: &Profile,
+    resolver: &AppResolver,
+) -> SwitchPlan {
+    let target: Vec<&AppEntry> = profile
+        .apps
+        .iter()
+        .filter(|entry| entry.kind.is_app())
+        .collect();
+
+    let mut plan = SwitchPlan {
+        profile_id: profile.id.clone(),
+        profile_name: profile.name.clone(),
+        locks: profile.locks,
+        ..SwitchPlan::default()
+    };
+    for entry in &target {
+        if current.iter().any(|existing| same_app(existing, entry)) {
+            plan.kept += 1;
+        } else {
+            plan.added.push(entry.name.clone());
+        }
+        let found = Path::new(&entry.path).exists()
+            || entry
+                .bundle_id
+                .as_deref()
+                .is_some_and(|bundle_id| resolver.resolve(bundle_id).is_some());
+        if !found {
+            plan.missing.push(entry.name.clone());
+        }
+    }
+    plan.removed = current
+        .iter()
+        .filter(|existing| existing.kind.is_app())
+        .filter(|existing| !target.iter().any(|entry| same_app(existing, entry)))
+        .map(|existing| existing.name.clone())
+        .collect();
+    plan.summary = describe(&plan);
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::DockItemKind;
+    use std::path::PathBuf;
+
+    fn app(name: &str, bundle_id: Option<&str>) -> AppEntry {
+        AppEntry {
+            name: name.to_string(),
+            path: format!("/definitely/missing/{}.app", name),
+            icon: None,
+            bundle_id: bundle_id.map(str::to_string),
+            version: None,
+            kind: DockItemKind::App,
+        }
+    }
+
+    fn profile(apps: Vec<AppEntry>) -> Profile {
+        let mut profile = Profile::new("Work".to_string());
+        profile.apps = apps;
+        profile
+    }
+
+    #[test]
+    fn plan_switch_diffs_current_dock_against_profile() {
+        let current = vec![app("Safari", None), app("Music", None)];
+        let mut moved_safari = app("Safari", Some("com.apple.Safari"));
+        moved_safari.path = "/Applications/Moved/Safari.app".to_string();
+        let mut current_with_id = current.clone();
+        current_with_id[0].bundle_id = Some("com.apple.Safari".to_string());
+
+        let resolver = AppResolver::from_entries([(
+            "com.apple.Safari".to_string(),
+            PathBuf::from("/Applications/Safari.app"),
+        )]);
+
+        let plan = plan_switch(
+            &current_with_id,
+            &profile(vec![
+                moved_safari,
+                app("Slack", None),
+                app("Pages", Some("com.apple.iWork.Pages")),
+            ]),
+            &resolver,
+        );
+
+        assert_eq!(plan.kept, 1);
+        assert_eq!(plan.added, vec!["Slack", "Pages"]);
+        assert_eq!(plan.removed, vec!["Music"]);
+        assert_eq!(plan.missing, vec!["Slack", "Pages"]);
+        assert_eq!(
+            plan.summary,
+            "Adds: Slack, Pages\nRemoves: Music\nKeeps 1 app\nNot found on this Mac: Slack, Pages"
+        );
+    }
+
+    #[test]
+    fn plan_switch_ignores_spacers_and_lists_locks() {
+        let mut spacer = app("", None);
+        spacer.kind = DockItemKind::Spacer;
+        let mut target = profile(vec![app("Notes", None), spacer.clone()]);
+        target.locks.contents = true;
+        target.locks.size = true;
+
+        let plan = plan_switch(
+            &[app("Notes", None), spacer],
+            &target,
+            &AppResolver::default(),
+        );
+
+        assert_eq!(plan.kept, 1);
+        assert!(plan.added.is_empty());
+        assert!(plan.removed.is_empty());
+        assert!(plan.summary.contains("Locks: contents, size"));
+        assert!(plan.summary.contains("already has these apps"));
+    }
+
+    #[test]
+    fn list_names_truncates_long_lists() {
+        let names: Vec<String> = (1..=7).map(|n| format!("App {}", n)).collect();
+        assert_eq!(
+            list_names(&names),
+            "App 1, App 2, App 3, App 4, App 5 and 2 more"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use crate::commands::dock::{apply_profile, restore_app_list, PROFILES_CHANGED_EVENT};
+use crate::store::{AppEntry, Restore, Store, TemporarySession};
+use crate::tray::refresh_tray;
+use chrono::{DateTime, Utc};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How long to wait when no temporary profile is running.
+const TEMPORARY_INTERVAL: Duration = Duration::from_secs(60);
+pub(crate) const EXTEND_MINUTES: i64 = 15;
+
+fn parse_ends_at(session: &TemporarySession) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&session.ends_at)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// The temporary session in `store`, unless its profile has since been switched away from.
+pub(crate) fn running_session(store: &Store) -> Option<&TemporarySession> {
+    store
+        .data
+        .temporary_session
+        .as_ref()
+        .filter(|temporary| store.data.active_profile_id == temporary.session.profile_id)
+}
+
+/// A session for `profile_id` that goes back to what is active now. Starting
+/// one while another runs keeps the original Dock to go back to.
+pub(crate) fn new_session(
+    store: &Store,
+    profile_id: &str,
+    ends_at: DateTime<Utc>,
+) -> TemporarySession {
+    let replacing = store.data.temporary_session.as_ref().map(|t| &t.session);
+    TemporarySession {
+        ends_at: ends_at.to_rfc3339(),
+        session: store.next_session(Some(profile_id.to_string()), replacing),
+    }
+}
+
+/// Pushes the end of the running session back by `by`, counting from now if
+/// it is already overdue.
+pub(crate) fn extend_session(
+    store: &mut Store,
+    by: chrono::Duration,
+    now: DateTime<Utc>,
+) -> Result<TemporarySession, String> {
+    let running = running_session(store)
+        .cloned()
+        .ok_or_else(|| "No temporary profile is running".to_string())?;
+    let ends_at = parse_ends_at(&running).unwrap_or(now).max(now) + by;
+    let session = TemporarySession {
+        ends_at: ends_at.to_rfc3339(),
+        ..running
+    };
+    store.data.temporary_session = Some(session.clone());
+    Ok(session)
+}
+
+pub(crate) fn remaining(store: &Store, now: DateTime<Utc>) -> Option<chrono::Duration> {
+    let ends_at = parse_ends_at(running_session(store)?)?;
+    Some((ends_at - now).max(chrono::Duration::zero()))
+}
+
+/// Minutes left, rounded up so the countdown never shows zero while running.
+pub(crate) fn format_remaining(remaining: chrono::Duration) -> String {
+    let minutes = (remaining.num_seconds() + 59) / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// The time until the countdown shows a different number of minutes.
+fn next_countdown_change(remaining: chrono::Duration) -> Duration {
+    match remaining.num_milliseconds() % 60_000 {
+        0 => Duration::from_secs(60),
+        millis => Duration::from_millis(millis as u64),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TemporaryTick {
+    Idle,
+    Running(chrono::Duration),
+    Ended,
+    RevertProfile(String),
+    RevertApps(Vec<AppEntry>),
+}
+
+/// Ends the session in `store` once its time is up, saying what to go back
+/// to. A session whose profile was switched away from ends without reverting.
+pub(crate) fn temporary_tick(store: &mut Store, now: DateTime<Utc>) -> TemporaryTick {
+    if store.data.temporary_session.is_none() {
+        return TemporaryTick::Idle;
+    }
+    // A session that can't be read has nothing to count down to, so it ends now.
+    let ends_at = running_session(store).and_then(parse_ends_at);
+    if let Some(ends_at) = ends_at.filter(|ends_at| *ends_at > now) {
+        return TemporaryTick::Running(ends_at - now);
+    }
+    let Some(ended) = store.data.temporary_session.take() else {
+        return TemporaryTick::Idle;
+    };
+    match store.end_session(ended.session) {
+        Restore::Nothing => TemporaryTick::Ended,
+        Restore::Profile(id) => TemporaryTick::RevertProfile(id),
+        Restore::Apps(apps) => TemporaryTick::RevertApps(apps),
+    }
+}
+
+/// Checks the temporary session, reverting the Dock when it is over, and
+/// returns how long to wait before checking again.
+pub(crate) fn run_temporary(app: &AppHandle) -> Duration {
+    let tick = {
+        let state = app.state::<Mutex<Store>>();
+        let Ok(mut store) = state.lock() else {
+            return TEMPORARY_INTERVAL;
+        };
+        let tick = temporary_tick(&mut store, Utc::now());
+        if !matches!(tick, TemporaryTick::Idle | TemporaryTick::Running(_)) {
+            if let Err(e) = store.save() {
+                eprintln!("Failed to save temporary profile state: {}", e);
+            }
+        }
+        // Redraw the countdown, or clear it once the session is over.
+        if tick != TemporaryTick::Idle {
+            refresh_tray(app, &store);
+        }
+        tick
+    };
+    let result = match tick {
+        TemporaryTick::Idle => return TEMPORARY_INTERVAL,
+        TemporaryTick::Running(remaining) => return next_countdown_change(remaining),
+        TemporaryTick::Ended => Ok(()),
+        TemporaryTick::RevertProfile(id) => {
+            apply_profile(id, app.clone(), app.state::<Mutex<Store>>())
+        }
+        TemporaryTick::RevertApps(apps) => restore_app_list(app, apps),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to revert temporary profile: {}", e);
+    }
+    let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+    TEMPORARY_INTERVAL
+}
+
+/// Wakes the countdown early, after a session starts, is extended or ends.
+pub(crate) struct TemporaryTimer {
+    wake: Sender<()>,
+}
+
+impl TemporaryTimer {
+    pub fn wake(&self) {
+        let _ = self.wake.send(());
+    }
+}
+
+pub fn start_temporary_timer(app: &AppHandle) {
+    let (wake, events) = channel();
+    app.manage(TemporaryTimer { wake });
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        let wait = run_temporary(&app);
+        let _ = events.recv_timeout(wait);
+        while events.try_recv().is_ok() {}
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::fixtures::{store_with_profiles, utc};
+
+    fn app(name: &str) -> AppEntry {
+        AppEntry {
+            name: name.to_string(),
+            path: format!("/Applications/{}.app", name),
+            icon: None,
+            bundle_id: None,
+            version: None,
+            kind: Default::default(),
+        }
+    }
+
+    #[test]
+    fn new_session_keeps_the_original_dock_when_chained() {
+        let mut store = store_with_profiles(&["home", "presenting", "focus"]);
+        store.data.active_profile_id = Some("home".to_string());
+        let session = new_session(&store, "presenting", utc("2026-03-02T10:45:00Z"));
+        assert_eq!(session.session.previous_profile_id.as_deref(), Some("home"));
+
+        store.data.temporary_session = Some(session);
+        store.data.active_profile_id = Some("presenting".to_string());
+        let chained = new_session(&store, "focus", utc("2026-03-02T11:00:00Z"));
+        assert_eq!(chained.session.previous_profile_id.as_deref(), Some("home"));
+        assert_eq!(chained.ends_at, "2026-03-02T11:00:00+00:00");
+    }
+
+    #[test]
+    fn temporary_tick_counts_down_then_reverts() {
+        let mut store = store_with_profiles(&["home", "presenting"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.temporary_session = Some(new_session(
+            &store,
+            "presenting",
+            utc("2026-03-02T10:45:00Z"),
+        ));
+        store.data.active_profile_id = Some("presenting".to_string());
+
+        assert_eq!(
+            temporary_tick(&mut store, utc("2026-03-02T10:00:30Z")),
+            TemporaryTick::Running(chrono::Duration::seconds(44 * 60 + 30))
+        );
+        assert_eq!(
+            format_remaining(remaining(&store, utc("2026-03-02T10:00:30Z")).unwrap()),
+            "45m"
+        );
+        // Restarting the app after the end still reverts.
+        assert_eq!(
+            temporary_tick(&mut store, utc("2026-03-02T12:00:00Z")),
+            TemporaryTick::RevertProfile("home".to_string())
+        );
+        assert_eq!(store.data.temporary_session, None);
+        assert_eq!(
+            temporary_tick(&mut store, utc("2026-03-02T12:00:00Z")),
+            TemporaryTick::Idle
+        );
+    }
+
+    #[test]
+    fn temporary_tick_restores_the_dock_when_no_profile_was_active() {
+        let mut store = store_with_profiles(&["presenting"]);
+        let mut session = new_session(&store, "presenting", utc("2026-03-02T10:45:00Z"));
+        session.session.previous_apps = vec![app("Safari"), app("Mail")];
+        store.data.temporary_session = Some(session);
+        store.data.active_profile_id = Some("presenting".to_string());
+
+        assert_eq!(
+            temporary_tick(&mut store, utc("2026-03-02T10:45:00Z")),
+            TemporaryTick::RevertApps(vec![app("Safari"), app("Mail")])
+        );
+    }
+
+    #[test]
+    fn temporary_tick_ends_quietly_after_a_manual_switch() {
+        let mut store = store_with_profiles(&["home", "presenting", "music"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.temporary_session = Some(new_session(
+            &store,
+            "presenting",
+            utc("2026-03-02T10:45:00Z"),
+        ));
+        store.data.active_profile_id = Some("music".to_string());
+
+        assert_eq!(remaining(&store, utc("2026-03-02T10:00:00Z")), None);
+        assert_eq!(
+            temporary_tick(&mut store, utc("2026-03-02T10:00:00Z")),
+            TemporaryTick::Ended
+        );
+        assert_eq!(store.data.temporary_session, None);
+    }
+
+    #[test]
+    fn extend_session_counts_from_now_when_overdue() {
+        let mut store = store_with_profiles(&["home", "presenting"]);
+        store.data.active_profile_id = Some("home".to_string());
+        store.data.temporary_session = Some(new_session(
+            &store,
+            "presenting",
+            utc("2026-03-02T10:45:00Z"),
+        ));
+        store.data.active_profile_id = Some("presenting".to_string());
+        let by = chrono::Duration::minutes(EXTEND_MINUTES);
+
+        let extended = extend_session(&mut store, by, utc("2026-03-02T10:00:00Z")).unwrap();
+        assert_eq!(extended.ends_at, "2026-03-02T11:00:00+00:00");
+        let extended = extend_session(&mut store, by, utc("2026-03-02T11:30:00Z")).unwrap();
+        assert_eq!(extended.ends_at, "2026-03-02T11:45:00+00:00");
+
+        store.data.active_profile_id = Some("home".to_string());
+        assert!(extend_session(&mut store, by, utc("2026-03-02T11:30:00Z")).is_err());
+    }
+
+    #[test]
+    fn countdown_shows_whole_minutes_and_hours() {
+        assert_eq!(format_remaining(chrono::Duration::seconds(1)), "1m");
+        assert_eq!(format_remaining(chrono::Duration::minutes(45)), "45m");
+        assert_eq!(
+            format_remaining(chrono::Duration::seconds(90 * 60 + 1)),
+            "1h 31m"
+        );
+        assert_eq!(
+            next_countdown_change(chrono::Duration::seconds(44 * 60 + 30)),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            next_countdown_change(chrono::Duration::minutes(45)),
+            Duration::from_secs(60)
+        );
+    }
+}
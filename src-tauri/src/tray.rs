@@ -1,9 +1,560 @@
-use crate::store::Store;
+use crate::commands::dock::{
+    capture_dock_to_profile, request_profile_switch, PROFILES_CHANGED_EVENT,
+};
+use crate::commands::temporary::{end_temporary_profile, extend_temporary_profile};
+use crate::icon_cache::IconCache;
+use crate::preview::{profile_menu_icon, MENU_ICON_SIZE};
+use crate::store::{Profile, ProfileOrder, Store, TrayDisplay};
+use crate::temporary::{format_remaining, remaining, running_session, EXTEND_MINUTES};
 use tauri::{
-    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
-    Manager, WebviewWindow,
+    image::Image,
+    menu::{CheckMenuItem, IconMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
+    tray::TrayIcon,
+    Emitter, Manager, WebviewWindow,
 };
 
+pub(crate) const TRAY_ID: &str = "main-tray";
+pub(crate) const PROFILE_CREATED_EVENT: &str = "profile-created";
+const PROFILE_PREFIX: &str = "profile:";
+const RECENT_PREFIX: &str = "recent:";
+const SAVE_TO_PREFIX: &str = "save-to:";
+const NEW_PROFILE_NAME: &str = "New Profile";
+const MAX_INLINE_PROFILES: usize = 12;
+const MAX_RECENT_MENU_ITEMS: usize = 3;
+const TRAY_TOOLTIP: &str = "DockSwitcher";
+const TRAY_ICON_SIZE: u32 = 44;
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+const GLYPHS: [(char, [u8; 7]); 36] = [
+    (
+        'A',
+        [
+            0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'B',
+        [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110,
+        ],
+    ),
+    (
+        'C',
+        [
+            0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        'D',
+        [
+            0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110,
+        ],
+    ),
+    (
+        'E',
+        [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111,
+        ],
+    ),
+    (
+        'F',
+        [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+    ),
+    (
+        'G',
+        [
+            0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111,
+        ],
+    ),
+    (
+        'H',
+        [
+            0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'I',
+        [
+            0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+    ),
+    (
+        'J',
+        [
+            0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+    ),
+    (
+        'K',
+        [
+            0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001,
+        ],
+    ),
+    (
+        'L',
+        [
+            0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111,
+        ],
+    ),
+    (
+        'M',
+        [
+            0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'N',
+        [
+            0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'O',
+        [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        'P',
+        [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+    ),
+    (
+        'Q',
+        [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101,
+        ],
+    ),
+    (
+        'R',
+        [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001,
+        ],
+    ),
+    (
+        'S',
+        [
+            0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+    ),
+    (
+        'T',
+        [
+            0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+    ),
+    (
+        'U',
+        [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        'V',
+        [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100,
+        ],
+    ),
+    (
+        'W',
+        [
+            0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010,
+        ],
+    ),
+    (
+        'X',
+        [
+            0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001,
+        ],
+    ),
+    (
+        'Y',
+        [
+            0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100,
+        ],
+    ),
+    (
+        'Z',
+        [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111,
+        ],
+    ),
+    (
+        '0',
+        [
+            0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '1',
+        [
+            0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+    ),
+    (
+        '2',
+        [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+        ],
+    ),
+    (
+        '3',
+        [
+            0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '4',
+        [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+        ],
+    ),
+    (
+        '5',
+        [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '6',
+        [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '7',
+        [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+        ],
+    ),
+    (
+        '8',
+        [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+        ],
+    ),
+    (
+        '9',
+        [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+        ],
+    ),
+];
+
+fn glyph(c: char) -> Option<[u8; 7]> {
+    let c = c.to_ascii_uppercase();
+    GLYPHS
+        .iter()
+        .find(|(glyph_char, _)| *glyph_char == c)
+        .map(|(_, rows)| *rows)
+}
+
+pub(crate) fn profile_initials(name: &str) -> String {
+    let words: Vec<&str> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let initials: String = match words.as_slice() {
+        [] => String::new(),
+        [word] => word.chars().take(1).collect(),
+        [first, second, ..] => first
+            .chars()
+            .take(1)
+            .chain(second.chars().take(1))
+            .collect(),
+    };
+    initials.to_uppercase()
+}
+
+pub(crate) fn render_symbol_icon(symbol: &str) -> Option<Vec<u8>> {
+    let glyphs: Vec<[u8; 7]> = symbol.chars().map(glyph).collect::<Option<_>>()?;
+    if glyphs.is_empty() || glyphs.len() > 2 {
+        return None;
+    }
+
+    let size = TRAY_ICON_SIZE;
+    let scale = if glyphs.len() == 1 { 4 } else { 3 };
+    let spacing = scale;
+    let text_width =
+        glyphs.len() as u32 * GLYPH_WIDTH * scale + (glyphs.len() as u32 - 1) * spacing;
+    let text_height = GLYPH_HEIGHT * scale;
+    let left = (size - text_width) / 2;
+    let top = (size - text_height) / 2;
+    let radius = (size / 5) as i64;
+
+    // Template icons only use alpha: draw a filled rounded square and knock the letters out of it.
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    for y in 0..size {
+        for x in 0..size {
+            let corner_x = (radius - x as i64).max(x as i64 - (size as i64 - 1 - radius));
+            let corner_y = (radius - y as i64).max(y as i64 - (size as i64 - 1 - radius));
+            if corner_x > 0
+                && corner_y > 0
+                && corner_x * corner_x + corner_y * corner_y > radius * radius
+            {
+                continue;
+            }
+            rgba[((y * size + x) * 4 + 3) as usize] = 255;
+        }
+    }
+
+    for (index, rows) in glyphs.iter().enumerate() {
+        let glyph_left = left + index as u32 * (GLYPH_WIDTH * scale + spacing);
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = glyph_left + column * scale + dx;
+                        let y = top + row as u32 * scale + dy;
+                        rgba[((y * size + x) * 4 + 3) as usize] = 0;
+                    }
+                }
+            }
+        }
+    }
+    Some(rgba)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TrayAppearance {
+    Default,
+    Title(String),
+    Icon(Vec<u8>),
+    TitleOnly(String),
+    IconAndTitle(Vec<u8>, String),
+}
+
+pub(crate) fn tray_appearance(store: &Store) -> TrayAppearance {
+    let Some(profile) = store.active_profile() else {
+        return TrayAppearance::Default;
+    };
+
+    match store.data.settings.tray_display {
+        TrayDisplay::Icon => TrayAppearance::Default,
+        TrayDisplay::Name => TrayAppearance::Title(profile.name.clone()),
+        TrayDisplay::ProfileIcon => {
+            let symbol = profile
+                .symbol
+                .as_deref()
+                .map(str::trim)
+                .filter(|symbol| !symbol.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| profile_initials(&profile.name));
+            match render_symbol_icon(&symbol) {
+                Some(rgba) => TrayAppearance::Icon(rgba),
+                // Emoji can't be drawn into a template image, so show them as the title instead.
+                None if !symbol.is_empty() => TrayAppearance::TitleOnly(symbol),
+                None => TrayAppearance::Default,
+            }
+        }
+    }
+}
+
+/// Adds the time left on a temporary profile next to the tray icon.
+pub(crate) fn with_countdown(appearance: TrayAppearance, countdown: &str) -> TrayAppearance {
+    match appearance {
+        TrayAppearance::Default => TrayAppearance::Title(countdown.to_string()),
+        TrayAppearance::Title(title) => TrayAppearance::Title(format!("{} · {}", title, countdown)),
+        TrayAppearance::TitleOnly(title) => {
+            TrayAppearance::TitleOnly(format!("{} {}", title, countdown))
+        }
+        TrayAppearance::Icon(rgba) | TrayAppearance::IconAndTitle(rgba, _) => {
+            TrayAppearance::IconAndTitle(rgba, countdown.to_string())
+        }
+    }
+}
+
+fn apply_tray_appearance(app: &tauri::AppHandle, tray: &TrayIcon, appearance: TrayAppearance) {
+    let default_icon = app.default_window_icon().cloned();
+    let (icon, title) = match appearance {
+        TrayAppearance::Default => (default_icon, None),
+        TrayAppearance::Title(title) => (default_icon, Some(title)),
+        TrayAppearance::Icon(rgba) => (
+            Some(Image::new_owned(rgba, TRAY_ICON_SIZE, TRAY_ICON_SIZE)),
+            None,
+        ),
+        TrayAppearance::TitleOnly(title) => (None, Some(title)),
+        TrayAppearance::IconAndTitle(rgba, title) => (
+            Some(Image::new_owned(rgba, TRAY_ICON_SIZE, TRAY_ICON_SIZE)),
+            Some(title),
+        ),
+    };
+    let _ = tray.set_icon(icon);
+    let _ = tray.set_icon_as_template(true);
+    let _ = tray.set_title(title);
+}
+
+pub(crate) fn refresh_tray(app: &tauri::AppHandle, store: &Store) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if let Ok(menu) = build_tray_menu(app, store) {
+        let _ = tray.set_menu(Some(menu));
+    }
+
+    let countdown = remaining(store, chrono::Utc::now()).map(format_remaining);
+    let tooltip = match (store.active_profile(), &countdown) {
+        (Some(profile), Some(countdown)) => {
+            format!("{} – {} ({} left)", TRAY_TOOLTIP, profile.name, countdown)
+        }
+        (Some(profile), None) => format!("{} – {}", TRAY_TOOLTIP, profile.name),
+        (None, _) => TRAY_TOOLTIP.to_string(),
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+    let appearance = match &countdown {
+        Some(countdown) => with_countdown(tray_appearance(store), countdown),
+        None => tray_appearance(store),
+    };
+    apply_tray_appearance(app, &tray, appearance);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TrayAction {
+    ApplyProfile(String),
+    SaveToActive,
+    SaveToProfile(String),
+    SaveAsNew,
+    ToggleAutomation,
+    ExtendTemporary,
+    EndTemporary,
+    Open,
+    Quit,
+}
+
+impl TrayAction {
+    pub(crate) fn menu_id(&self) -> String {
+        match self {
+            TrayAction::ApplyProfile(id) => format!("{}{}", PROFILE_PREFIX, id),
+            TrayAction::SaveToActive => "action:save-to-active".to_string(),
+            TrayAction::SaveToProfile(id) => format!("{}{}", SAVE_TO_PREFIX, id),
+            TrayAction::SaveAsNew => "action:save-as-new".to_string(),
+            TrayAction::ToggleAutomation => "action:toggle-automation".to_string(),
+            TrayAction::ExtendTemporary => "action:extend-temporary".to_string(),
+            TrayAction::EndTemporary => "action:end-temporary".to_string(),
+            TrayAction::Open => "action:open".to_string(),
+            TrayAction::Quit => "action:quit".to_string(),
+        }
+    }
+
+    pub(crate) fn from_menu_id(menu_id: &str) -> Option<Self> {
+        if let Some(id) = menu_id
+            .strip_prefix(PROFILE_PREFIX)
+            .or_else(|| menu_id.strip_prefix(RECENT_PREFIX))
+        {
+            return (!id.is_empty()).then(|| TrayAction::ApplyProfile(id.to_string()));
+        }
+        if let Some(id) = menu_id.strip_prefix(SAVE_TO_PREFIX) {
+            return (!id.is_empty()).then(|| TrayAction::SaveToProfile(id.to_string()));
+        }
+        match menu_id {
+            "action:save-to-active" => Some(TrayAction::SaveToActive),
+            "action:save-as-new" => Some(TrayAction::SaveAsNew),
+            "action:toggle-automation" => Some(TrayAction::ToggleAutomation),
+            "action:extend-temporary" => Some(TrayAction::ExtendTemporary),
+            "action:end-temporary" => Some(TrayAction::EndTemporary),
+            "action:open" => Some(TrayAction::Open),
+            "action:quit" => Some(TrayAction::Quit),
+            _ => None,
+        }
+    }
+}
+
+fn format_apply_time(at: &str) -> Option<String> {
+    let at = chrono::DateTime::parse_from_rfc3339(at).ok()?;
+    Some(at.with_timezone(&chrono::Local).format("%H:%M").to_string())
+}
+
+pub(crate) fn status_lines(store: &Store) -> Vec<String> {
+    let mut lines = vec![match store.active_profile() {
+        Some(profile) => format!("Active: {}", profile.name),
+        None => "No active profile".to_string(),
+    }];
+    if let Some(session) = running_session(store) {
+        let previous = session
+            .session
+            .previous_profile_id
+            .as_ref()
+            .and_then(|id| store.data.profiles.iter().find(|p| &p.id == id))
+            .map(|profile| profile.name.as_str())
+            .unwrap_or("the previous Dock");
+        lines.push(match format_apply_time(&session.ends_at) {
+            Some(time) => format!("Until {}, then {}", time, previous),
+            None => format!("Then {}", previous),
+        });
+    }
+    if let Some(last) = &store.data.last_apply {
+        let time = format_apply_time(&last.at)
+            .map(|time| format!(" at {}", time))
+            .unwrap_or_default();
+        lines.push(if !last.ok {
+            format!("Last switch to {} failed{}", last.profile_name, time)
+        } else if last.messages.is_empty() {
+            format!("Switched to {}{}", last.profile_name, time)
+        } else {
+            format!(
+                "Switched to {}{} ({} warning{})",
+                last.profile_name,
+                time,
+                last.messages.len(),
+                if last.messages.len() == 1 { "" } else { "s" }
+            )
+        });
+    }
+    lines
+}
+
+pub(crate) fn recent_profiles(store: &Store) -> Vec<&Profile> {
+    store
+        .data
+        .recent_profile_ids
+        .iter()
+        .filter(|id| store.data.active_profile_id.as_ref() != Some(*id))
+        .filter_map(|id| store.data.profiles.iter().find(|p| &p.id == id))
+        .take(MAX_RECENT_MENU_ITEMS)
+        .collect()
+}
+
+fn group_label(name: &str) -> String {
+    name.chars()
+        .find(|c| c.is_alphanumeric())
+        .filter(|c| c.is_alphabetic())
+        .map(|c| c.to_uppercase().collect())
+        .unwrap_or_else(|| "#".to_string())
+}
+
+pub(crate) fn ordered_profiles(store: &Store) -> Vec<&Profile> {
+    match store.data.settings.tray_profile_order {
+        ProfileOrder::Manual => store.data.profiles.iter().collect(),
+        ProfileOrder::RecentlyUsed => store.profiles_by_recent(),
+    }
+}
+
+pub(crate) fn profile_groups<'a>(
+    profiles: &[&'a Profile],
+) -> Vec<(Option<String>, Vec<&'a Profile>)> {
+    if profiles.len() <= MAX_INLINE_PROFILES {
+        return vec![(None, profiles.to_vec())];
+    }
+    let mut groups: std::collections::BTreeMap<String, Vec<&Profile>> = Default::default();
+    for &profile in profiles {
+        groups
+            .entry(group_label(&profile.name))
+            .or_default()
+            .push(profile);
+    }
+    groups
+        .into_iter()
+        .map(|(label, profiles)| (Some(label), profiles))
+        .collect()
+}
+
 pub(crate) fn show_window(window: &WebviewWindow) {
     #[cfg(target_os = "macos")]
     {
@@ -15,40 +566,524 @@ pub(crate) fn show_window(window: &WebviewWindow) {
     window.set_focus().unwrap_or_default();
 }
 
+fn profile_menu_item(
+    app: &tauri::AppHandle,
+    profile: &Profile,
+    active_id: Option<&str>,
+    icons: &IconCache,
+) -> tauri::Result<Box<dyn IsMenuItem<tauri::Wry>>> {
+    let id = TrayAction::ApplyProfile(profile.id.clone()).menu_id();
+    let is_active = active_id == Some(profile.id.as_str());
+    match profile_menu_icon(profile, icons) {
+        Some(rgba) => {
+            // Icon items have no check state, so mark the active profile in its label.
+            let label = if is_active {
+                format!("✓ {}", profile.name)
+            } else {
+                profile.name.clone()
+            };
+            let icon = Image::new_owned(rgba, MENU_ICON_SIZE, MENU_ICON_SIZE);
+            Ok(Box::new(IconMenuItem::with_id(
+                app,
+                id,
+                label,
+                true,
+                Some(icon),
+                None::<&str>,
+            )?))
+        }
+        None => Ok(Box::new(CheckMenuItem::with_id(
+            app,
+            id,
+            &profile.name,
+            true,
+            is_active,
+            None::<&str>,
+        )?)),
+    }
+}
+
 pub(crate) fn build_tray_menu(
     app: &tauri::AppHandle,
     store: &Store,
 ) -> tauri::Result<Menu<tauri::Wry>> {
-    let separator = PredefinedMenuItem::separator(app)?;
-    let open_item = MenuItem::with_id(app, "open", "Open DockSwitcher", true, None::<&str>)?;
-    let separator2 = PredefinedMenuItem::separator(app)?;
-    let quit_item = MenuItem::with_id(app, "quit", "Quit DockSwitcher", true, None::<&str>)?;
-
     let menu = Menu::new(app)?;
 
-    for profile in &store.data.profiles {
-        let is_active = store
-            .data
-            .active_profile_id
-            .as_ref()
-            .map(|id| id == &profile.id)
-            .unwrap_or(false);
-
-        let item = CheckMenuItem::with_id(
+    for (index, line) in status_lines(store).iter().enumerate() {
+        let item = MenuItem::with_id(app, format!("status:{}", index), line, false, None::<&str>)?;
+        menu.append(&item)?;
+    }
+    if running_session(store).is_some() {
+        menu.append(&MenuItem::with_id(
             app,
-            &profile.id,
-            &profile.name,
+            TrayAction::ExtendTemporary.menu_id(),
+            format!("Extend by {} Minutes", EXTEND_MINUTES),
             true,
-            is_active,
             None::<&str>,
-        )?;
-        menu.append(&item)?;
+        )?)?;
+        menu.append(&MenuItem::with_id(
+            app,
+            TrayAction::EndTemporary.menu_id(),
+            "End Now",
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+
+    let recent = recent_profiles(store);
+    if !recent.is_empty() {
+        menu.append(&MenuItem::with_id(
+            app,
+            "status:recent",
+            "Recent",
+            false,
+            None::<&str>,
+        )?)?;
+        for profile in recent {
+            let item = MenuItem::with_id(
+                app,
+                format!("{}{}", RECENT_PREFIX, profile.id),
+                &profile.name,
+                true,
+                None::<&str>,
+            )?;
+            menu.append(&item)?;
+        }
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
     }
 
-    menu.append(&separator)?;
-    menu.append(&open_item)?;
-    menu.append(&separator2)?;
-    menu.append(&quit_item)?;
+    let active_id = store.data.active_profile_id.as_deref();
+    let icons = IconCache::shared();
+    for (label, profiles) in profile_groups(&ordered_profiles(store)) {
+        let items = profiles
+            .iter()
+            .map(|profile| profile_menu_item(app, profile, active_id, &icons))
+            .collect::<tauri::Result<Vec<_>>>()?;
+        match label {
+            Some(label) => {
+                let submenu = Submenu::with_id(app, format!("group:{}", label), &label, true)?;
+                for item in &items {
+                    submenu.append(item.as_ref())?;
+                }
+                menu.append(&submenu)?;
+            }
+            None => {
+                for item in &items {
+                    menu.append(item.as_ref())?;
+                }
+            }
+        }
+    }
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+
+    let active = store.active_profile();
+    let save_label = match active {
+        Some(profile) => format!("Save Current Dock to \"{}\"", profile.name),
+        None => "Save Current Dock to Active Profile".to_string(),
+    };
+    menu.append(&MenuItem::with_id(
+        app,
+        TrayAction::SaveToActive.menu_id(),
+        save_label,
+        active.is_some(),
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        TrayAction::SaveAsNew.menu_id(),
+        "Save Current Dock as New Profile…",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    if store.has_automation() {
+        menu.append(&CheckMenuItem::with_id(
+            app,
+            TrayAction::ToggleAutomation.menu_id(),
+            "Pause Automation",
+            true,
+            store.data.automation_paused,
+            None::<&str>,
+        )?)?;
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    menu.append(&MenuItem::with_id(
+        app,
+        TrayAction::Open.menu_id(),
+        "Open DockSwitcher",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        TrayAction::Quit.menu_id(),
+        "Quit DockSwitcher",
+        true,
+        None::<&str>,
+    )?)?;
 
     Ok(menu)
 }
+
+fn save_dock_to_active(app: &tauri::AppHandle) -> Result<(), String> {
+    let id = {
+        let state = app.state::<std::sync::Mutex<Store>>();
+        let store = state.lock().map_err(|e| e.to_string())?;
+        store
+            .data
+            .active_profile_id
+            .clone()
+            .ok_or_else(|| "No active profile".to_string())?
+    };
+    save_dock_to_profile(app, &id)
+}
+
+fn save_dock_to_profile(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    capture_dock_to_profile(app, id)?;
+    Ok(())
+}
+
+fn save_dock_as_new_profile(app: &tauri::AppHandle) -> Result<Profile, String> {
+    let profile = {
+        let state = app.state::<std::sync::Mutex<Store>>();
+        let mut store = state.lock().map_err(|e| e.to_string())?;
+        let profile = Profile::new(store.unique_profile_name(NEW_PROFILE_NAME));
+        store.data.profiles.push(profile.clone());
+        store.save().map_err(|e| e.to_string())?;
+        profile
+    };
+    capture_dock_to_profile(app, &profile.id)?;
+    Ok(profile)
+}
+
+pub(crate) fn spawn_dock_capture(app: &tauri::AppHandle, action: TrayAction) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match action {
+            TrayAction::SaveAsNew => save_dock_as_new_profile(&app).map(|profile| {
+                // The new profile gets a placeholder name, so open it for renaming.
+                if let Some(window) = app.get_webview_window("main") {
+                    show_window(&window);
+                }
+                let _ = app.emit(PROFILE_CREATED_EVENT, profile.id);
+            }),
+            TrayAction::SaveToProfile(id) => save_dock_to_profile(&app, &id),
+            _ => save_dock_to_active(&app),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to save Dock from tray: {}", e);
+        }
+
+        let state = app.state::<std::sync::Mutex<Store>>();
+        if let Ok(store) = state.lock() {
+            refresh_tray(&app, &store);
+        };
+        let _ = app.emit(PROFILES_CHANGED_EVENT, ());
+    });
+}
+
+pub(crate) fn handle_tray_menu_event(app: &tauri::AppHandle, menu_id: &str) {
+    let Some(action) = TrayAction::from_menu_id(menu_id) else {
+        return;
+    };
+    match action {
+        TrayAction::Open => {
+            if let Some(window) = app.get_webview_window("main") {
+                show_window(&window);
+            }
+        }
+        TrayAction::Quit => app.exit(0),
+        TrayAction::ToggleAutomation => {
+            let state = app.state::<std::sync::Mutex<Store>>();
+            let Ok(mut store) = state.lock() else {
+                return;
+            };
+            store.data.automation_paused = !store.data.automation_paused;
+            if let Err(e) = store.save() {
+                eprintln!("Failed to save automation state: {}", e);
+            }
+            refresh_tray(app, &store);
+        }
+        TrayAction::ExtendTemporary => {
+            let state = app.state::<std::sync::Mutex<Store>>();
+            let by = (EXTEND_MINUTES * 60) as u64;
+            if let Err(e) = extend_temporary_profile(by, app.clone(), state) {
+                eprintln!("Failed to extend temporary profile: {}", e);
+            }
+        }
+        TrayAction::EndTemporary => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<std::sync::Mutex<Store>>();
+                if let Err(e) = end_temporary_profile(app.clone(), state) {
+                    eprintln!("Failed to end temporary profile: {}", e);
+                }
+            });
+        }
+        TrayAction::ApplyProfile(id) => {
+            let profile_exists = {
+                let state = app.state::<std::sync::Mutex<Store>>();
+                let store = state.lock().unwrap();
+                store.data.profiles.iter().any(|p| p.id == id)
+            };
+            if profile_exists {
+                request_profile_switch(app, id);
+            }
+        }
+        TrayAction::SaveToActive | TrayAction::SaveToProfile(_) | TrayAction::SaveAsNew => {
+            spawn_dock_capture(app, action)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{ApplyResult, DockLocks, Session, TemporarySession};
+
+    fn store_with_active(name: &str, symbol: Option<&str>, display: TrayDisplay) -> Store {
+        let mut store = Store::with_path(std::env::temp_dir().join("unused-config.json"));
+        store.data.profiles.push(Profile {
+            id: "p1".to_string(),
+            name: name.to_string(),
+            apps: Vec::new(),
+            locks: DockLocks::default(),
+            symbol: symbol.map(str::to_string),
+            shortcut: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        });
+        store.data.active_profile_id = Some("p1".to_string());
+        store.data.settings.tray_display = display;
+        store
+    }
+
+    fn alpha(rgba: &[u8], x: u32, y: u32) -> u8 {
+        rgba[((y * TRAY_ICON_SIZE + x) * 4 + 3) as usize]
+    }
+
+    #[test]
+    fn profile_initials_use_first_two_words() {
+        assert_eq!(profile_initials("Deep work"), "DW");
+        assert_eq!(profile_initials("gaming"), "G");
+        assert_eq!(profile_initials("  client - acme  corp"), "CA");
+        assert_eq!(profile_initials("!!"), "");
+    }
+
+    #[test]
+    fn render_symbol_icon_knocks_letters_out_of_square() {
+        let rgba = render_symbol_icon("I").unwrap();
+        assert_eq!(rgba.len(), (TRAY_ICON_SIZE * TRAY_ICON_SIZE * 4) as usize);
+        // Corners are transparent, the square is opaque and the stem of the I is cut out.
+        assert_eq!(alpha(&rgba, 0, 0), 0);
+        assert_eq!(alpha(&rgba, 4, TRAY_ICON_SIZE / 2), 255);
+        assert_eq!(alpha(&rgba, TRAY_ICON_SIZE / 2, TRAY_ICON_SIZE / 2), 0);
+    }
+
+    #[test]
+    fn render_symbol_icon_rejects_unsupported_symbols() {
+        assert!(render_symbol_icon("").is_none());
+        assert!(render_symbol_icon("ABC").is_none());
+        assert!(render_symbol_icon("🚀").is_none());
+        assert!(render_symbol_icon("w2").is_some());
+    }
+
+    #[test]
+    fn tray_appearance_follows_display_mode() {
+        assert_eq!(
+            tray_appearance(&store_with_active("Work", None, TrayDisplay::Icon)),
+            TrayAppearance::Default
+        );
+        assert_eq!(
+            tray_appearance(&store_with_active("Work", None, TrayDisplay::Name)),
+            TrayAppearance::Title("Work".to_string())
+        );
+        assert_eq!(
+            tray_appearance(&store_with_active(
+                "Deep Work",
+                None,
+                TrayDisplay::ProfileIcon
+            )),
+            TrayAppearance::Icon(render_symbol_icon("DW").unwrap())
+        );
+        assert_eq!(
+            tray_appearance(&store_with_active(
+                "Work",
+                Some("🚀"),
+                TrayDisplay::ProfileIcon
+            )),
+            TrayAppearance::TitleOnly("🚀".to_string())
+        );
+    }
+
+    #[test]
+    fn tray_appearance_is_default_without_active_profile() {
+        let mut store = store_with_active("Work", None, TrayDisplay::Name);
+        store.data.active_profile_id = None;
+        assert_eq!(tray_appearance(&store), TrayAppearance::Default);
+    }
+
+    #[test]
+    fn with_countdown_puts_the_time_left_next_to_the_icon() {
+        assert_eq!(
+            with_countdown(TrayAppearance::Default, "45m"),
+            TrayAppearance::Title("45m".to_string())
+        );
+        assert_eq!(
+            with_countdown(TrayAppearance::Title("Work".to_string()), "45m"),
+            TrayAppearance::Title("Work · 45m".to_string())
+        );
+        assert_eq!(
+            with_countdown(TrayAppearance::Icon(vec![1]), "45m"),
+            TrayAppearance::IconAndTitle(vec![1], "45m".to_string())
+        );
+    }
+
+    #[test]
+    fn status_lines_say_what_a_temporary_profile_returns_to() {
+        let mut store = store_with_active("Presenting", None, TrayDisplay::Icon);
+        store.data.profiles.push(named_profile("p2", "Home"));
+        store.data.temporary_session = Some(TemporarySession {
+            ends_at: "not a timestamp".to_string(),
+            session: Session {
+                profile_id: Some("p1".to_string()),
+                previous_profile_id: Some("p2".to_string()),
+                previous_apps: Vec::new(),
+            },
+        });
+        assert_eq!(
+            status_lines(&store),
+            vec!["Active: Presenting", "Then Home"]
+        );
+
+        store
+            .data
+            .temporary_session
+            .as_mut()
+            .unwrap()
+            .session
+            .previous_profile_id = None;
+        assert_eq!(status_lines(&store)[1], "Then the previous Dock");
+
+        store.data.active_profile_id = Some("p2".to_string());
+        assert_eq!(status_lines(&store), vec!["Active: Home"]);
+    }
+
+    fn named_profile(id: &str, name: &str) -> Profile {
+        let mut profile = Profile::new(name.to_string());
+        profile.id = id.to_string();
+        profile
+    }
+
+    #[test]
+    fn tray_action_menu_ids_round_trip() {
+        for action in [
+            TrayAction::ApplyProfile("open".to_string()),
+            TrayAction::SaveToActive,
+            TrayAction::SaveToProfile("p1".to_string()),
+            TrayAction::SaveAsNew,
+            TrayAction::ToggleAutomation,
+            TrayAction::ExtendTemporary,
+            TrayAction::EndTemporary,
+            TrayAction::Open,
+            TrayAction::Quit,
+        ] {
+            assert_eq!(TrayAction::from_menu_id(&action.menu_id()), Some(action));
+        }
+        assert_eq!(
+            TrayAction::from_menu_id("recent:p1"),
+            Some(TrayAction::ApplyProfile("p1".to_string()))
+        );
+        assert_eq!(TrayAction::from_menu_id("open"), None);
+        assert_eq!(TrayAction::from_menu_id("profile:"), None);
+        assert_eq!(TrayAction::from_menu_id("save-to:"), None);
+        assert_eq!(TrayAction::from_menu_id("status:0"), None);
+    }
+
+    #[test]
+    fn status_lines_report_active_profile_and_last_switch() {
+        let mut store = store_with_active("Work", None, TrayDisplay::Icon);
+        assert_eq!(status_lines(&store), vec!["Active: Work"]);
+
+        store.record_apply(ApplyResult {
+            profile_id: "p1".to_string(),
+            profile_name: "Work".to_string(),
+            at: "not a timestamp".to_string(),
+            ok: true,
+            messages: vec!["Slack not found".to_string()],
+        });
+        assert_eq!(status_lines(&store)[1], "Switched to Work (1 warning)");
+
+        store.record_apply(ApplyResult {
+            profile_id: "p2".to_string(),
+            profile_name: "Home".to_string(),
+            at: "2025-01-01T12:00:00Z".to_string(),
+            ok: false,
+            messages: vec!["dockutil not found".to_string()],
+        });
+        let lines = status_lines(&store);
+        assert_eq!(lines[0], "Active: Work");
+        assert!(lines[1].starts_with("Last switch to Home failed at "));
+    }
+
+    #[test]
+    fn recent_profiles_skip_active_and_deleted_profiles() {
+        let mut store = store_with_active("Work", None, TrayDisplay::Icon);
+        store.data.profiles.push(named_profile("p2", "Home"));
+        store.data.recent_profile_ids =
+            vec!["p1".to_string(), "deleted".to_string(), "p2".to_string()];
+
+        let names: Vec<&str> = recent_profiles(&store)
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Home"]);
+    }
+
+    #[test]
+    fn ordered_profiles_follow_the_tray_order_setting() {
+        let mut store = store_with_active("Work", None, TrayDisplay::Icon);
+        store.data.profiles.push(named_profile("p2", "Home"));
+        store.data.recent_profile_ids = vec!["p2".to_string(), "p1".to_string()];
+
+        let names = |store: &Store| {
+            ordered_profiles(store)
+                .iter()
+                .map(|profile| profile.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&store), vec!["Work", "Home"]);
+
+        store.data.settings.tray_profile_order = ProfileOrder::RecentlyUsed;
+        assert_eq!(names(&store), vec!["Home", "Work"]);
+    }
+
+    #[test]
+    fn profile_groups_stay_flat_until_there_are_many_profiles() {
+        let few: Vec<Profile> = (0..MAX_INLINE_PROFILES)
+            .map(|n| named_profile(&n.to_string(), &format!("Profile {}", n)))
+            .collect();
+        let groups = profile_groups(&few.iter().collect::<Vec<_>>());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, None);
+        assert_eq!(groups[0].1.len(), MAX_INLINE_PROFILES);
+
+        let mut many = few.clone();
+        many.push(named_profile("w", "work"));
+        many.push(named_profile("n", "2024 setup"));
+        let labels: Vec<Option<String>> = profile_groups(&many.iter().collect::<Vec<_>>())
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some("#".to_string()),
+                Some("P".to_string()),
+                Some("W".to_string())
+            ]
+        );
+    }
+}
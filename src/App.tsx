@@ -1,10 +1,25 @@
-import { useState, useEffect } from "react";
-import { open } from "@tauri-apps/plugin-dialog";
+import { useState, useEffect, useCallback } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import { Sidebar, ConfirmDialog } from "./components";
 import { ProfileView, SettingsView } from "./views";
 import type { ConfirmDialogState } from "./views";
-import { useProfiles, useDock, useSettings } from "./hooks";
-import type { Profile } from "./types/profile";
+import {
+  useProfiles,
+  useDock,
+  useSettings,
+  useSchedules,
+  useCalendar,
+  useSignalRules,
+  useTemporaryProfile,
+} from "./hooks";
+import type {
+  AddAppError,
+  DockItemKind,
+  DockLocks,
+  Profile,
+  SwitchPlan,
+} from "./types/profile";
 
 function App() {
   const {
@@ -17,12 +32,17 @@ function App() {
     deleteProfile,
     reorderProfiles,
     applyProfile,
+    renderProfilePreview,
+    exportProfile,
   } = useProfiles();
 
   const {
     saveDockToProfile,
     addAppToProfile,
     removeAppFromProfile,
+    getDockLocks,
+    unlockDock,
+    getSwitchConfirmation,
   } = useDock();
 
   const {
@@ -31,8 +51,33 @@ function App() {
     updateSettings,
   } = useSettings();
 
+  const { schedules, refresh: refreshSchedules, updateSchedules } = useSchedules();
+  const {
+    calendarFiles,
+    calendarRules,
+    refresh: refreshCalendar,
+    updateCalendarFiles,
+    updateCalendarRules,
+  } = useCalendar();
+  const {
+    signalRules,
+    evaluations: signalEvaluations,
+    lastFiring: lastSignalFiring,
+    refresh: refreshSignalRules,
+    explain: explainSignalRules,
+    updateSignalRules,
+  } = useSignalRules();
+  const {
+    session: temporarySession,
+    applyTemporarily,
+    extend: extendTemporary,
+    end: endTemporary,
+  } = useTemporaryProfile();
+
   const [selectedProfileId, setSelectedProfileId] = useState<string | null>(null);
   const [showSettings, setShowSettings] = useState(false);
+  const [dockLocks, setDockLocks] = useState<DockLocks | null>(null);
+  const [previewSrc, setPreviewSrc] = useState<string | null>(null);
   
   const [confirmDialogState, setConfirmDialogState] = useState<ConfirmDialogState>({
     open: false,
@@ -42,6 +87,56 @@ function App() {
     confirmLabel: "Confirm",
   });
 
+  const refreshDockLocks = useCallback(async () => {
+    try {
+      setDockLocks(await getDockLocks());
+    } catch (error) {
+      console.error("Failed to read Dock locks:", error);
+    }
+  }, [getDockLocks]);
+
+  useEffect(() => {
+    refreshDockLocks();
+  }, [refreshDockLocks]);
+
+  useEffect(() => {
+    const selectProfile = ({ payload }: { payload: string }) => {
+      setSelectedProfileId(payload);
+      setShowSettings(false);
+    };
+    const unlisteners = [
+      listen<string>("profile-created", selectProfile),
+      listen<string>("open-profile", selectProfile),
+    ];
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((stop) => stop()));
+    };
+  }, []);
+
+  const previewProfile = profiles.find((p) => p.id === selectedProfileId);
+  const previewKey = previewProfile
+    ? `${previewProfile.id}:${previewProfile.updated_at}:${previewProfile.apps.length}`
+    : null;
+
+  useEffect(() => {
+    if (!previewKey || !selectedProfileId) {
+      setPreviewSrc(null);
+      return;
+    }
+    let cancelled = false;
+    renderProfilePreview(selectedProfileId)
+      .then((png) => {
+        if (!cancelled) setPreviewSrc(`data:image/png;base64,${png}`);
+      })
+      .catch((error) => {
+        console.error("Failed to render profile preview:", error);
+        if (!cancelled) setPreviewSrc(null);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [previewKey, selectedProfileId, renderProfilePreview]);
+
   useEffect(() => {
     if (!selectedProfileId && profiles.length > 0 && !profilesLoading) {
       setSelectedProfileId(profiles[0].id);
@@ -66,6 +161,9 @@ function App() {
       isDelete: true,
       action: async () => {
         await deleteProfile(id);
+        await refreshSchedules();
+        await refreshCalendar();
+        await refreshSignalRules();
         if (selectedProfileId === id) {
           setSelectedProfileId(null);
         }
@@ -74,17 +172,25 @@ function App() {
     });
   };
 
-  const handleApplyProfile = (id: string) => {
+  const handleApplyProfile = async (id: string) => {
     const apply = async () => {
       await applyProfile(id);
+      await refreshDockLocks();
     };
 
-    if (settings.confirm_before_switch) {
-      const profileName = profiles.find((p) => p.id === id)?.name || "this profile";
+    let plan: SwitchPlan | null = null;
+    try {
+      plan = await getSwitchConfirmation(id);
+    } catch (error) {
+      console.error("Failed to plan profile switch:", error);
+      return;
+    }
+
+    if (plan) {
       setConfirmDialogState({
         open: true,
-        title: "Switch Dock Profile?",
-        message: `Are you sure you want to switch to "${profileName}"? Your current Dock will be replaced.`,
+        title: `Switch Dock to "${plan.profile_name}"?`,
+        message: plan.summary,
         confirmLabel: "Switch",
         action: async () => {
           await apply();
@@ -92,7 +198,34 @@ function App() {
         },
       });
     } else {
-      apply();
+      await apply();
+    }
+  };
+
+  const handleApplyTemporarily = async (id: string, minutes: number) => {
+    try {
+      await applyTemporarily(id, minutes);
+      await refreshProfiles();
+      await refreshDockLocks();
+    } catch (error) {
+      console.error("Failed to apply profile temporarily:", error);
+    }
+  };
+
+  const handleExtendTemporary = async (minutes: number) => {
+    try {
+      await extendTemporary(minutes);
+    } catch (error) {
+      console.error("Failed to extend temporary profile:", error);
+    }
+  };
+
+  const handleEndTemporary = async () => {
+    try {
+      await endTemporary();
+      await refreshProfiles();
+    } catch (error) {
+      console.error("Failed to end temporary profile:", error);
     }
   };
 
@@ -118,7 +251,12 @@ function App() {
       if (selected) {
         const paths = Array.isArray(selected) ? selected : [selected];
         for (const path of paths) {
-          await addAppToProfile(id, path);
+          try {
+            await addAppToProfile(id, path);
+          } catch (error) {
+            const { message } = error as AddAppError;
+            console.error(`Failed to add ${path}:`, message ?? error);
+          }
         }
         await refreshProfiles();
       }
@@ -136,6 +274,42 @@ function App() {
     }
   };
 
+  const handleUnlockDock = async () => {
+    try {
+      await unlockDock();
+      await refreshDockLocks();
+    } catch (error) {
+      console.error("Failed to unlock Dock:", error);
+    }
+  };
+
+  const handleAddSpacer = async (profile: Profile, kind: DockItemKind) => {
+    try {
+      const name = kind === "small_spacer" ? "Small Spacer" : "Spacer";
+      await updateProfile({
+        ...profile,
+        apps: [...profile.apps, { name, path: "", kind }],
+      });
+    } catch (error) {
+      console.error("Failed to add spacer:", error);
+    }
+  };
+
+  const handleExportProfile = async (profile: Profile) => {
+    try {
+      const path = await save({
+        title: "Export Profile",
+        defaultPath: `${profile.name}.json`,
+        filters: [{ name: "Profile", extensions: ["json"] }],
+      });
+      if (path) {
+        await exportProfile(profile.id, path);
+      }
+    } catch (error) {
+      console.error("Failed to export profile:", error);
+    }
+  };
+
   const handleRenameProfile = async (profile: Profile, newName: string) => {
     try {
       await updateProfile({ ...profile, name: newName });
@@ -152,6 +326,10 @@ function App() {
     }
   };
 
+  const handleSetShortcut = async (profile: Profile, shortcut: string | undefined) => {
+    await updateProfile({ ...profile, shortcut });
+  };
+
   if (profilesLoading || settingsLoading) {
     return (
       <div className="flex h-screen items-center justify-center bg-gray-50 dark:bg-slate-900">
@@ -189,7 +367,19 @@ function App() {
         {showSettings ? (
           <SettingsView
             settings={settings}
+            profiles={profiles}
+            schedules={schedules}
+            calendarFiles={calendarFiles}
+            calendarRules={calendarRules}
+            signalRules={signalRules}
+            signalEvaluations={signalEvaluations}
+            lastSignalFiring={lastSignalFiring}
             onUpdateSettings={updateSettings}
+            onUpdateSchedules={updateSchedules}
+            onUpdateCalendarFiles={updateCalendarFiles}
+            onUpdateCalendarRules={updateCalendarRules}
+            onUpdateSignalRules={updateSignalRules}
+            onExplainSignalRules={explainSignalRules}
             onBack={() => {
               setShowSettings(false);
               if (profiles.length > 0) {
@@ -201,11 +391,21 @@ function App() {
           <ProfileView
             profile={selectedProfile}
             activeProfileId={activeProfileId}
+            dockLocked={dockLocks !== null && Object.values(dockLocks).some(Boolean)}
+            onUnlockDock={handleUnlockDock}
             onApplyProfile={handleApplyProfile}
+            temporarySession={temporarySession}
+            onApplyTemporarily={handleApplyTemporarily}
+            onExtendTemporary={handleExtendTemporary}
+            onEndTemporary={handleEndTemporary}
             onSaveDock={handleSaveDock}
             onAddApp={handleAddApp}
+            onAddSpacer={handleAddSpacer}
+            onExportProfile={handleExportProfile}
+            previewSrc={previewSrc}
             onRemoveApp={handleRemoveApp}
             onUpdateProfile={handleUpdateProfile}
+            onSetShortcut={handleSetShortcut}
           />
         )}
       </main>
@@ -0,0 +1,124 @@
+import React, { useState } from 'react';
+import { Plus, Trash2 } from 'lucide-react';
+import type { CalendarRule, Profile } from '../types';
+
+const inputClassName =
+  'rounded-lg border border-gray-300 bg-white px-2 py-1 text-sm text-gray-900 shadow-sm focus:border-blue-600 focus:outline-none focus:ring-1 focus:ring-blue-600 dark:border-slate-600 dark:bg-slate-800 dark:text-white';
+
+interface CalendarRuleListProps {
+  files: string[];
+  rules: CalendarRule[];
+  profiles: Profile[];
+  onSaveFiles: (files: string[]) => Promise<void>;
+  onSaveRules: (rules: CalendarRule[]) => Promise<void>;
+}
+
+const CalendarRuleList: React.FC<CalendarRuleListProps> = ({
+  files,
+  rules,
+  profiles,
+  onSaveFiles,
+  onSaveRules,
+}) => {
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async (action: Promise<void>) => {
+    try {
+      await action;
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const saveRules = (next: CalendarRule[]) => save(onSaveRules(next));
+
+  const updateRule = (index: number, changes: Partial<CalendarRule>) =>
+    saveRules(rules.map((rule, i) => (i === index ? { ...rule, ...changes } : rule)));
+
+  const addRule = () => {
+    if (profiles.length === 0) return;
+    saveRules([
+      ...rules,
+      {
+        id: '',
+        pattern: 'demo|presentation',
+        profile_id: profiles[0].id,
+        enabled: true,
+      },
+    ]);
+  };
+
+  return (
+    <div className="space-y-3">
+      <textarea
+        key={files.join('\n')}
+        defaultValue={files.join('\n')}
+        rows={Math.max(2, files.length)}
+        placeholder="/Users/you/Calendars/Work.ics"
+        spellCheck={false}
+        onBlur={(event) => {
+          const next = event.target.value.split('\n').filter((file) => file.trim());
+          if (next.join('\n') !== files.join('\n')) {
+            save(onSaveFiles(next));
+          }
+        }}
+        className={`w-full font-mono ${inputClassName}`}
+      />
+      {rules.map((rule, index) => (
+        <div key={rule.id || index} className="flex flex-wrap items-center gap-2">
+          <input
+            type="checkbox"
+            checked={rule.enabled}
+            onChange={() => updateRule(index, { enabled: !rule.enabled })}
+            className="h-4 w-4 rounded border-gray-300 text-blue-600 focus:ring-blue-600"
+          />
+          <input
+            type="text"
+            defaultValue={rule.pattern}
+            placeholder="Title pattern"
+            spellCheck={false}
+            onBlur={(event) => {
+              const pattern = event.target.value.trim();
+              if (pattern !== rule.pattern) {
+                updateRule(index, { pattern });
+              }
+            }}
+            className={`w-48 font-mono ${inputClassName}`}
+          />
+          <span className="text-sm text-gray-500 dark:text-gray-400">→</span>
+          <select
+            value={rule.profile_id}
+            onChange={(event) => updateRule(index, { profile_id: event.target.value })}
+            className={inputClassName}
+          >
+            {profiles.map((profile) => (
+              <option key={profile.id} value={profile.id}>
+                {profile.name}
+              </option>
+            ))}
+          </select>
+          <button
+            type="button"
+            onClick={() => saveRules(rules.filter((_, i) => i !== index))}
+            className="rounded-lg p-1.5 text-gray-400 hover:bg-red-50 hover:text-red-600 dark:hover:bg-red-900/20 dark:hover:text-red-400"
+          >
+            <Trash2 className="h-4 w-4" />
+          </button>
+        </div>
+      ))}
+      <button
+        type="button"
+        onClick={addRule}
+        disabled={profiles.length === 0}
+        className="inline-flex items-center gap-1.5 rounded-lg px-2 py-1 text-sm font-medium text-blue-600 hover:bg-blue-50 disabled:opacity-50 dark:text-blue-400 dark:hover:bg-slate-800"
+      >
+        <Plus className="h-4 w-4" />
+        Add rule
+      </button>
+      {error && <p className="text-xs text-red-600 dark:text-red-400">{error}</p>}
+    </div>
+  );
+};
+
+export default CalendarRuleList;
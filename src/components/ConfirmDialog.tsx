@@ -34,7 +34,7 @@ const ConfirmDialog: React.FC<ConfirmDialogProps> = ({
           <h3 className="text-base font-semibold leading-6 text-gray-900 dark:text-white">
             {title}
           </h3>
-          <p className="mt-2 text-sm text-gray-500 dark:text-gray-400">
+          <p className="mt-2 whitespace-pre-line text-sm text-gray-500 dark:text-gray-400">
             {message}
           </p>
         </div>
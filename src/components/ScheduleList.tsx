@@ -0,0 +1,144 @@
+import React, { useState } from 'react';
+import { Plus, Trash2 } from 'lucide-react';
+import type { Profile, ScheduleRule, Weekday } from '../types';
+
+const WEEKDAYS: { value: Weekday; label: string }[] = [
+  { value: 'Mon', label: 'M' },
+  { value: 'Tue', label: 'T' },
+  { value: 'Wed', label: 'W' },
+  { value: 'Thu', label: 'T' },
+  { value: 'Fri', label: 'F' },
+  { value: 'Sat', label: 'S' },
+  { value: 'Sun', label: 'S' },
+];
+
+const inputClassName =
+  'rounded-lg border border-gray-300 bg-white px-2 py-1 text-sm text-gray-900 shadow-sm focus:border-blue-600 focus:outline-none focus:ring-1 focus:ring-blue-600 dark:border-slate-600 dark:bg-slate-800 dark:text-white';
+
+interface ScheduleListProps {
+  schedules: ScheduleRule[];
+  profiles: Profile[];
+  onSave: (schedules: ScheduleRule[]) => Promise<void>;
+}
+
+const ScheduleList: React.FC<ScheduleListProps> = ({ schedules, profiles, onSave }) => {
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async (next: ScheduleRule[]) => {
+    try {
+      await onSave(next);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const updateRule = (index: number, changes: Partial<ScheduleRule>) =>
+    save(schedules.map((rule, i) => (i === index ? { ...rule, ...changes } : rule)));
+
+  const toggleWeekday = (index: number, weekday: Weekday) => {
+    const weekdays = schedules[index].weekdays;
+    updateRule(index, {
+      weekdays: weekdays.includes(weekday)
+        ? weekdays.filter((day) => day !== weekday)
+        : WEEKDAYS.map(({ value }) => value).filter(
+            (day) => day === weekday || weekdays.includes(day),
+          ),
+    });
+  };
+
+  const addRule = () => {
+    if (profiles.length === 0) return;
+    save([
+      ...schedules,
+      {
+        id: '',
+        profile_id: profiles[0].id,
+        weekdays: ['Mon', 'Tue', 'Wed', 'Thu', 'Fri'],
+        time: '09:00',
+        enabled: true,
+      },
+    ]);
+  };
+
+  return (
+    <div className="space-y-3">
+      {schedules.map((rule, index) => (
+        <div key={rule.id || index} className="flex flex-wrap items-center gap-2">
+          <input
+            type="checkbox"
+            checked={rule.enabled}
+            onChange={() => updateRule(index, { enabled: !rule.enabled })}
+            className="h-4 w-4 rounded border-gray-300 text-blue-600 focus:ring-blue-600"
+          />
+          <select
+            value={rule.profile_id}
+            onChange={(event) => updateRule(index, { profile_id: event.target.value })}
+            className={inputClassName}
+          >
+            {profiles.map((profile) => (
+              <option key={profile.id} value={profile.id}>
+                {profile.name}
+              </option>
+            ))}
+          </select>
+          <input
+            type="time"
+            value={rule.time}
+            onChange={(event) => event.target.value && updateRule(index, { time: event.target.value })}
+            className={inputClassName}
+          />
+          <div className="flex gap-0.5">
+            {WEEKDAYS.map(({ value, label }) => (
+              <button
+                key={value}
+                type="button"
+                title={value}
+                onClick={() => toggleWeekday(index, value)}
+                className={`h-7 w-7 rounded-full text-xs font-medium transition-colors ${
+                  rule.weekdays.includes(value)
+                    ? 'bg-blue-600 text-white'
+                    : 'bg-gray-100 text-gray-500 hover:bg-gray-200 dark:bg-slate-800 dark:text-gray-400 dark:hover:bg-slate-700'
+                }`}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+          <input
+            type="text"
+            defaultValue={rule.timezone ?? ''}
+            placeholder="Local time"
+            spellCheck={false}
+            onBlur={(event) => {
+              const timezone = event.target.value.trim() || undefined;
+              if (timezone !== rule.timezone) {
+                updateRule(index, { timezone });
+              }
+            }}
+            className={`w-36 ${inputClassName}`}
+          />
+          <button
+            type="button"
+            onClick={() => save(schedules.filter((_, i) => i !== index))}
+            className="rounded-lg p-1.5 text-gray-400 hover:bg-red-50 hover:text-red-600 dark:hover:bg-red-900/20 dark:hover:text-red-400"
+          >
+            <Trash2 className="h-4 w-4" />
+          </button>
+        </div>
+      ))}
+      <button
+        type="button"
+        onClick={addRule}
+        disabled={profiles.length === 0}
+        className="inline-flex items-center gap-1.5 rounded-lg px-2 py-1 text-sm font-medium text-blue-600 hover:bg-blue-50 disabled:opacity-50 dark:text-blue-400 dark:hover:bg-slate-800"
+      >
+        <Plus className="h-4 w-4" />
+        Add schedule
+      </button>
+      {error && <p className="text-xs text-red-600 dark:text-red-400">{error}</p>}
+    </div>
+  );
+};
+
+export default ScheduleList;
@@ -0,0 +1,54 @@
+import React, { useState } from 'react';
+
+interface ShortcutFieldProps {
+  value?: string;
+  placeholder?: string;
+  onSave: (shortcut: string | undefined) => Promise<void>;
+}
+
+const ShortcutField: React.FC<ShortcutFieldProps> = ({
+  value,
+  placeholder = 'e.g. Cmd+Alt+1',
+  onSave,
+}) => {
+  const [error, setError] = useState<string | null>(null);
+
+  const save = async (raw: string) => {
+    const shortcut = raw.trim() || undefined;
+    if (shortcut === value) {
+      setError(null);
+      return;
+    }
+    try {
+      await onSave(shortcut);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div>
+      <input
+        type="text"
+        defaultValue={value ?? ''}
+        placeholder={placeholder}
+        spellCheck={false}
+        onBlur={(event) => save(event.target.value)}
+        onKeyDown={(event) => {
+          if (event.key === 'Enter') {
+            event.currentTarget.blur();
+          }
+        }}
+        className={`w-48 rounded-lg border bg-white px-3 py-1.5 text-sm text-gray-900 shadow-sm focus:outline-none focus:ring-1 dark:bg-slate-800 dark:text-white ${
+          error
+            ? 'border-red-500 focus:border-red-500 focus:ring-red-500'
+            : 'border-gray-300 focus:border-blue-600 focus:ring-blue-600 dark:border-slate-600'
+        }`}
+      />
+      {error && <p className="mt-1 text-xs text-red-600 dark:text-red-400">{error}</p>}
+    </div>
+  );
+};
+
+export default ShortcutField;
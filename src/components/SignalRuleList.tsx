@@ -0,0 +1,327 @@
+import React, { useEffect, useState } from 'react';
+import { Check, Plus, RefreshCw, Trash2, X } from 'lucide-react';
+import type {
+  Profile,
+  RuleEvaluation,
+  Signal,
+  SignalCondition,
+  SignalFiring,
+  SignalRule,
+} from '../types';
+
+const SIGNAL_KINDS: { value: Signal['kind']; label: string }[] = [
+  { value: 'path_exists', label: 'Path exists' },
+  { value: 'env_flag', label: 'Flag file sets' },
+  { value: 'hostname', label: 'Hostname is' },
+];
+
+const inputClassName =
+  'rounded-lg border border-gray-300 bg-white px-2 py-1 text-sm text-gray-900 shadow-sm focus:border-blue-600 focus:outline-none focus:ring-1 focus:ring-blue-600 dark:border-slate-600 dark:bg-slate-800 dark:text-white';
+
+const emptyCondition = (kind: Signal['kind']): SignalCondition => {
+  switch (kind) {
+    case 'path_exists':
+      return { kind, path: '', negate: false };
+    case 'env_flag':
+      return { kind, file: '', key: '', negate: false };
+    case 'hostname':
+      return { kind, name: '', negate: false };
+  }
+};
+
+interface SignalRuleListProps {
+  rules: SignalRule[];
+  evaluations: RuleEvaluation[];
+  lastFiring: SignalFiring | null;
+  profiles: Profile[];
+  onSave: (rules: SignalRule[]) => Promise<void>;
+  onExplain: () => Promise<void>;
+}
+
+const SignalRuleList: React.FC<SignalRuleListProps> = ({
+  rules,
+  evaluations,
+  lastFiring,
+  profiles,
+  onSave,
+  onExplain,
+}) => {
+  const [draft, setDraft] = useState<SignalRule[]>(rules);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setDraft(rules);
+  }, [rules]);
+
+  const dirty = JSON.stringify(draft) !== JSON.stringify(rules);
+
+  const save = async () => {
+    try {
+      await onSave(draft);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const updateRule = (index: number, changes: Partial<SignalRule>) =>
+    setDraft(draft.map((rule, i) => (i === index ? { ...rule, ...changes } : rule)));
+
+  const updateCondition = (ruleIndex: number, conditionIndex: number, condition: SignalCondition) =>
+    updateRule(ruleIndex, {
+      conditions: draft[ruleIndex].conditions.map((existing, i) =>
+        i === conditionIndex ? condition : existing,
+      ),
+    });
+
+  const addRule = () => {
+    if (profiles.length === 0) return;
+    setDraft([
+      ...draft,
+      {
+        id: '',
+        name: 'New rule',
+        profile_id: profiles[0].id,
+        conditions: [emptyCondition('path_exists')],
+        priority: 0,
+        enabled: true,
+      },
+    ]);
+  };
+
+  const conditionFields = (ruleIndex: number, conditionIndex: number, condition: SignalCondition) => {
+    const set = (changes: Partial<SignalCondition>) =>
+      updateCondition(ruleIndex, conditionIndex, { ...condition, ...changes } as SignalCondition);
+    switch (condition.kind) {
+      case 'path_exists':
+        return (
+          <input
+            type="text"
+            value={condition.path}
+            placeholder="/Volumes/Work"
+            spellCheck={false}
+            onChange={(event) => set({ path: event.target.value })}
+            className={`w-56 font-mono ${inputClassName}`}
+          />
+        );
+      case 'env_flag':
+        return (
+          <>
+            <input
+              type="text"
+              value={condition.key}
+              placeholder="VPN"
+              spellCheck={false}
+              onChange={(event) => set({ key: event.target.value })}
+              className={`w-24 font-mono ${inputClassName}`}
+            />
+            <input
+              type="text"
+              value={condition.value ?? ''}
+              placeholder="any value"
+              spellCheck={false}
+              onChange={(event) => set({ value: event.target.value || undefined })}
+              className={`w-24 font-mono ${inputClassName}`}
+            />
+            <span className="text-sm text-gray-500 dark:text-gray-400">in</span>
+            <input
+              type="text"
+              value={condition.file}
+              placeholder="~/.dockflags"
+              spellCheck={false}
+              onChange={(event) => set({ file: event.target.value })}
+              className={`w-40 font-mono ${inputClassName}`}
+            />
+          </>
+        );
+      case 'hostname':
+        return (
+          <input
+            type="text"
+            value={condition.name}
+            placeholder="studio-mac"
+            spellCheck={false}
+            onChange={(event) => set({ name: event.target.value })}
+            className={`w-40 font-mono ${inputClassName}`}
+          />
+        );
+    }
+  };
+
+  const firingProfile = profiles.find((profile) => profile.id === lastFiring?.profile_id);
+
+  return (
+    <div className="space-y-4">
+      {draft.map((rule, ruleIndex) => {
+        const evaluation = evaluations.find((result) => result.rule_id === rule.id);
+        return (
+          <div
+            key={rule.id || ruleIndex}
+            className={`space-y-2 rounded-lg border p-3 ${
+              evaluation?.matched
+                ? 'border-blue-300 dark:border-blue-800'
+                : 'border-gray-200 dark:border-slate-800'
+            }`}
+          >
+            <div className="flex flex-wrap items-center gap-2">
+              <input
+                type="checkbox"
+                checked={rule.enabled}
+                onChange={() => updateRule(ruleIndex, { enabled: !rule.enabled })}
+                className="h-4 w-4 rounded border-gray-300 text-blue-600 focus:ring-blue-600"
+              />
+              <input
+                type="text"
+                value={rule.name}
+                onChange={(event) => updateRule(ruleIndex, { name: event.target.value })}
+                className={`w-36 ${inputClassName}`}
+              />
+              <span className="text-sm text-gray-500 dark:text-gray-400">→</span>
+              <select
+                value={rule.profile_id}
+                onChange={(event) => updateRule(ruleIndex, { profile_id: event.target.value })}
+                className={inputClassName}
+              >
+                {profiles.map((profile) => (
+                  <option key={profile.id} value={profile.id}>
+                    {profile.name}
+                  </option>
+                ))}
+              </select>
+              <label className="flex items-center gap-1 text-sm text-gray-500 dark:text-gray-400">
+                Priority
+                <input
+                  type="number"
+                  value={rule.priority}
+                  onChange={(event) =>
+                    updateRule(ruleIndex, { priority: Number(event.target.value) || 0 })
+                  }
+                  className={`w-16 ${inputClassName}`}
+                />
+              </label>
+              <button
+                type="button"
+                onClick={() => setDraft(draft.filter((_, i) => i !== ruleIndex))}
+                className="rounded-lg p-1.5 text-gray-400 hover:bg-red-50 hover:text-red-600 dark:hover:bg-red-900/20 dark:hover:text-red-400"
+              >
+                <Trash2 className="h-4 w-4" />
+              </button>
+            </div>
+            {rule.conditions.map((condition, conditionIndex) => {
+              const result = dirty ? undefined : evaluation?.conditions[conditionIndex];
+              return (
+                <div key={conditionIndex} className="ml-6 space-y-1">
+                  <div className="flex flex-wrap items-center gap-2">
+                    <label className="flex items-center gap-1 text-sm text-gray-500 dark:text-gray-400">
+                      <input
+                        type="checkbox"
+                        checked={condition.negate}
+                        onChange={() =>
+                          updateCondition(ruleIndex, conditionIndex, {
+                            ...condition,
+                            negate: !condition.negate,
+                          })
+                        }
+                        className="h-4 w-4 rounded border-gray-300 text-blue-600 focus:ring-blue-600"
+                      />
+                      Not
+                    </label>
+                    <select
+                      value={condition.kind}
+                      onChange={(event) =>
+                        updateCondition(ruleIndex, conditionIndex, {
+                          ...emptyCondition(event.target.value as Signal['kind']),
+                          negate: condition.negate,
+                        })
+                      }
+                      className={inputClassName}
+                    >
+                      {SIGNAL_KINDS.map(({ value, label }) => (
+                        <option key={value} value={value}>
+                          {label}
+                        </option>
+                      ))}
+                    </select>
+                    {conditionFields(ruleIndex, conditionIndex, condition)}
+                    <button
+                      type="button"
+                      onClick={() =>
+                        updateRule(ruleIndex, {
+                          conditions: rule.conditions.filter((_, i) => i !== conditionIndex),
+                        })
+                      }
+                      className="rounded-lg p-1.5 text-gray-400 hover:bg-red-50 hover:text-red-600 dark:hover:bg-red-900/20 dark:hover:text-red-400"
+                    >
+                      <X className="h-4 w-4" />
+                    </button>
+                  </div>
+                  {result && (
+                    <p
+                      className={`flex items-center gap-1 text-xs ${
+                        result.met
+                          ? 'text-green-600 dark:text-green-400'
+                          : 'text-gray-500 dark:text-gray-400'
+                      }`}
+                    >
+                      {result.met ? <Check className="h-3 w-3" /> : <X className="h-3 w-3" />}
+                      {result.description}
+                    </p>
+                  )}
+                </div>
+              );
+            })}
+            <button
+              type="button"
+              onClick={() =>
+                updateRule(ruleIndex, {
+                  conditions: [...rule.conditions, emptyCondition('path_exists')],
+                })
+              }
+              className="ml-6 inline-flex items-center gap-1 text-xs font-medium text-blue-600 hover:underline dark:text-blue-400"
+            >
+              <Plus className="h-3 w-3" />
+              Add condition
+            </button>
+          </div>
+        );
+      })}
+      <div className="flex items-center gap-2">
+        <button
+          type="button"
+          onClick={addRule}
+          disabled={profiles.length === 0}
+          className="inline-flex items-center gap-1.5 rounded-lg px-2 py-1 text-sm font-medium text-blue-600 hover:bg-blue-50 disabled:opacity-50 dark:text-blue-400 dark:hover:bg-slate-800"
+        >
+          <Plus className="h-4 w-4" />
+          Add rule
+        </button>
+        <button
+          type="button"
+          onClick={() => onExplain()}
+          className="inline-flex items-center gap-1.5 rounded-lg px-2 py-1 text-sm font-medium text-gray-600 hover:bg-gray-100 dark:text-gray-300 dark:hover:bg-slate-800"
+        >
+          <RefreshCw className="h-4 w-4" />
+          Check now
+        </button>
+        {dirty && (
+          <button
+            type="button"
+            onClick={save}
+            className="ml-auto rounded-lg bg-blue-600 px-3 py-1 text-sm font-medium text-white hover:bg-blue-700"
+          >
+            Save rules
+          </button>
+        )}
+      </div>
+      {lastFiring && (
+        <p className="text-xs text-gray-500 dark:text-gray-400">
+          Last switched to {firingProfile?.name ?? 'a deleted profile'} by “{lastFiring.rule_name}”
+          {' '}on {new Date(lastFiring.at).toLocaleString()} because {lastFiring.reasons.join(' and ')}.
+        </p>
+      )}
+      {error && <p className="text-xs text-red-600 dark:text-red-400">{error}</p>}
+    </div>
+  );
+};
+
+export default SignalRuleList;
@@ -1,9 +1,10 @@
+import { convertFileSrc } from '@tauri-apps/api/core';
 import { useSortable } from '@dnd-kit/sortable';
 import { CSS } from '@dnd-kit/utilities';
 import { GripVertical, Trash2 } from 'lucide-react';
 
 interface SortableAppItemProps {
-  app: { name: string; path: string; icon?: string };
+  app: { name: string; path: string; icon?: string; kind?: string };
   id: string;
   onRemove: () => void;
 }
@@ -25,6 +26,7 @@ const SortableAppItem = ({ app, id, onRemove }: SortableAppItemProps) => {
     position: 'relative' as const,
   };
 
+  const isSpacer = app.kind === 'spacer' || app.kind === 'small_spacer';
   const initial = app.name.charAt(0).toUpperCase();
   const colors = [
     'bg-red-500', 'bg-orange-500', 'bg-amber-500', 
@@ -46,9 +48,11 @@ const SortableAppItem = ({ app, id, onRemove }: SortableAppItemProps) => {
         <GripVertical className="h-4 w-4 text-gray-400" />
       </div>
 
-      {app.icon ? (
+      {isSpacer ? (
+        <div className={`h-10 shrink-0 rounded-lg border-2 border-dashed border-gray-300 dark:border-slate-600 ${app.kind === 'small_spacer' ? 'w-5' : 'w-10'}`} />
+      ) : app.icon ? (
         <img
-          src={`data:image/png;base64,${app.icon}`}
+          src={convertFileSrc(app.icon, 'dsicon')}
           alt={app.name}
           className="h-10 w-10 shrink-0 rounded-lg shadow-sm"
         />
@@ -60,14 +64,16 @@ const SortableAppItem = ({ app, id, onRemove }: SortableAppItemProps) => {
 
       <div className="flex min-w-0 flex-1 flex-col">
         <span className="truncate font-medium text-gray-900 dark:text-white">{app.name}</span>
-        <span className="truncate text-xs text-gray-400 dark:text-slate-500">{app.path}</span>
+        {!isSpacer && (
+          <span className="truncate text-xs text-gray-400 dark:text-slate-500">{app.path}</span>
+        )}
       </div>
 
       <button
         type="button"
         onClick={onRemove}
         className="rounded-md p-2 opacity-0 hover:bg-red-50 hover:text-red-600 group-hover:opacity-100 dark:hover:bg-red-900/20 dark:hover:text-red-400 transition-all"
-        title="Remove app"
+        title={isSpacer ? 'Remove spacer' : 'Remove app'}
       >
         <Trash2 className="h-4 w-4" />
       </button>
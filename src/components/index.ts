@@ -1,3 +1,7 @@
 export { default as Sidebar } from './Sidebar';
 export { default as ConfirmDialog } from './ConfirmDialog';
 export { default as SortableAppItem } from './SortableAppItem';
+export { default as ShortcutField } from './ShortcutField';
+export { default as ScheduleList } from './ScheduleList';
+export { default as CalendarRuleList } from './CalendarRuleList';
+export { default as SignalRuleList } from './SignalRuleList';
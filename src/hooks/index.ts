@@ -1,3 +1,8 @@
 export { useProfiles } from './useProfiles';
 export { useDock } from './useDock';
 export { useSettings } from './useSettings';
+export { useCatalog } from './useCatalog';
+export { useSchedules } from './useSchedules';
+export { useCalendar } from './useCalendar';
+export { useSignalRules } from './useSignalRules';
+export { useTemporaryProfile } from './useTemporaryProfile';
@@ -0,0 +1,96 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { renderHook, act } from "@testing-library/react";
+import { waitFor } from "@testing-library/dom";
+import { invoke } from "@tauri-apps/api/core";
+import { useCalendar } from "./useCalendar";
+import type { CalendarRule } from "../types";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+const mockInvoke = vi.mocked(invoke);
+
+describe("useCalendar", () => {
+  const demos: CalendarRule = {
+    id: "c1",
+    pattern: "demo|presentation",
+    profile_id: "presenting",
+    enabled: true,
+  };
+  const workCalendar = "/Users/me/Calendars/Work.ics";
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_calendar_files") return [workCalendar];
+      if (cmd === "get_calendar_rules") return [demos];
+      return null;
+    });
+  });
+
+  it("loads calendar files and rules on mount", async () => {
+    const { result } = renderHook(() => useCalendar());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    expect(result.current.calendarFiles).toEqual([workCalendar]);
+    expect(result.current.calendarRules).toEqual([demos]);
+    expect(mockInvoke).toHaveBeenCalledWith("get_calendar_files");
+    expect(mockInvoke).toHaveBeenCalledWith("get_calendar_rules");
+  });
+
+  it("updateCalendarFiles keeps the files saved by the backend", async () => {
+    const { result } = renderHook(() => useCalendar());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "update_calendar_files") return [workCalendar];
+      return null;
+    });
+
+    await act(async () => {
+      await result.current.updateCalendarFiles([` ${workCalendar} `, workCalendar]);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("update_calendar_files", {
+      files: [` ${workCalendar} `, workCalendar],
+    });
+    expect(result.current.calendarFiles).toEqual([workCalendar]);
+  });
+
+  it("updateCalendarRules keeps the rules saved by the backend", async () => {
+    const focus: CalendarRule = {
+      id: "",
+      pattern: "focus",
+      profile_id: "writing",
+      enabled: true,
+    };
+    const saved = [demos, { ...focus, id: "c2" }];
+
+    const { result } = renderHook(() => useCalendar());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "update_calendar_rules") return saved;
+      return null;
+    });
+
+    await act(async () => {
+      await result.current.updateCalendarRules([demos, focus]);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("update_calendar_rules", {
+      rules: [demos, focus],
+    });
+    expect(result.current.calendarRules).toEqual(saved);
+  });
+});
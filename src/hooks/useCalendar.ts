@@ -0,0 +1,45 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { CalendarRule } from "../types";
+
+export function useCalendar() {
+  const [calendarFiles, setCalendarFiles] = useState<string[]>([]);
+  const [calendarRules, setCalendarRules] = useState<CalendarRule[]>([]);
+  const [loading, setLoading] = useState(true);
+
+  const refresh = useCallback(async () => {
+    try {
+      const [files, rules] = await Promise.all([
+        invoke<string[]>("get_calendar_files"),
+        invoke<CalendarRule[]>("get_calendar_rules"),
+      ]);
+      setCalendarFiles(files);
+      setCalendarRules(rules);
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const updateCalendarFiles = useCallback(async (files: string[]) => {
+    const saved = await invoke<string[]>("update_calendar_files", { files });
+    setCalendarFiles(saved);
+  }, []);
+
+  const updateCalendarRules = useCallback(async (rules: CalendarRule[]) => {
+    const saved = await invoke<CalendarRule[]>("update_calendar_rules", { rules });
+    setCalendarRules(saved);
+  }, []);
+
+  return {
+    calendarFiles,
+    calendarRules,
+    loading,
+    refresh,
+    updateCalendarFiles,
+    updateCalendarRules,
+  };
+}
@@ -0,0 +1,55 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { renderHook } from "@testing-library/react";
+import { waitFor } from "@testing-library/dom";
+import { invoke } from "@tauri-apps/api/core";
+import { useCatalog } from "./useCatalog";
+import type { CatalogApp } from "../types";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+const mockInvoke = vi.mocked(invoke);
+
+describe("useCatalog", () => {
+  const apps: CatalogApp[] = [
+    {
+      name: "Safari",
+      path: "/Applications/Safari.app",
+      bundle_id: "com.apple.Safari",
+      version: "18.0",
+      category: "public.app-category.productivity",
+      icon_file: null,
+    },
+  ];
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("listInstalledApps invokes command", async () => {
+    mockInvoke.mockResolvedValueOnce(apps);
+
+    const { result } = renderHook(() => useCatalog());
+
+    await waitFor(() => expect(result.current).not.toBeNull());
+
+    const fetched = await result.current.listInstalledApps();
+
+    expect(fetched).toEqual(apps);
+    expect(mockInvoke).toHaveBeenCalledWith("list_installed_apps");
+  });
+
+  it("searchApps invokes command with query", async () => {
+    mockInvoke.mockResolvedValueOnce(apps);
+
+    const { result } = renderHook(() => useCatalog());
+
+    await waitFor(() => expect(result.current).not.toBeNull());
+
+    const found = await result.current.searchApps("saf");
+
+    expect(found).toEqual(apps);
+    expect(mockInvoke).toHaveBeenCalledWith("search_apps", { query: "saf" });
+  });
+});
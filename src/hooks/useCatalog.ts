@@ -0,0 +1,15 @@
+import { useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { CatalogApp } from "../types";
+
+export function useCatalog() {
+  const listInstalledApps = useCallback(async () => {
+    return invoke<CatalogApp[]>("list_installed_apps");
+  }, []);
+
+  const searchApps = useCallback(async (query: string) => {
+    return invoke<CatalogApp[]>("search_apps", { query });
+  }, []);
+
+  return { listInstalledApps, searchApps };
+}
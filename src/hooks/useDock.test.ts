@@ -3,7 +3,7 @@ import { renderHook, act } from "@testing-library/react";
 import { waitFor } from "@testing-library/dom";
 import { invoke } from "@tauri-apps/api/core";
 import { useDock } from "./useDock";
-import type { AppEntry } from "../types";
+import type { AppEntry, DockLocks, SwitchPlan } from "../types";
 
 vi.mock("@tauri-apps/api/core", () => ({
   invoke: vi.fn(),
@@ -115,4 +115,70 @@ describe("useDock", () => {
     expect(available).toBe(true);
     expect(mockInvoke).toHaveBeenCalledWith("check_dockutil");
   });
+
+  it("getDockLocks invokes command", async () => {
+    const locks: DockLocks = {
+      contents: true,
+      size: false,
+      position: false,
+      magnify: true,
+    };
+    mockInvoke.mockResolvedValueOnce(locks);
+
+    const { result } = renderHook(() => useDock());
+
+    await waitFor(() => expect(result.current).not.toBeNull());
+
+    const fetched = await result.current.getDockLocks();
+
+    expect(fetched).toEqual(locks);
+    expect(mockInvoke).toHaveBeenCalledWith("get_dock_locks");
+  });
+
+  it("unlockDock invokes command", async () => {
+    mockInvoke.mockResolvedValueOnce(null);
+
+    const { result } = renderHook(() => useDock());
+
+    await waitFor(() => expect(result.current).not.toBeNull());
+
+    await result.current.unlockDock();
+
+    expect(mockInvoke).toHaveBeenCalledWith("unlock_dock");
+  });
+
+  it("getSwitchConfirmation invokes command with profile id", async () => {
+    const plan: SwitchPlan = {
+      profile_id: "p1",
+      profile_name: "Work",
+      added: ["Slack"],
+      removed: [],
+      kept: 2,
+      missing: [],
+      locks: { contents: false, size: false, position: false, magnify: false },
+      summary: "Adds: Slack\nKeeps 2 apps",
+    };
+    mockInvoke.mockResolvedValueOnce(plan);
+
+    const { result } = renderHook(() => useDock());
+
+    await waitFor(() => expect(result.current).not.toBeNull());
+
+    const fetched = await result.current.getSwitchConfirmation("p1");
+
+    expect(fetched).toEqual(plan);
+    expect(mockInvoke).toHaveBeenCalledWith("get_switch_confirmation", {
+      id: "p1",
+    });
+  });
+
+  it("getSwitchConfirmation returns null when confirmation is off", async () => {
+    mockInvoke.mockResolvedValueOnce(null);
+
+    const { result } = renderHook(() => useDock());
+
+    await waitFor(() => expect(result.current).not.toBeNull());
+
+    expect(await result.current.getSwitchConfirmation("p1")).toBeNull();
+  });
 });
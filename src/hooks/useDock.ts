@@ -1,6 +1,6 @@
 import { useCallback, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { AppEntry } from "../types";
+import type { AppEntry, DockLocks, SwitchPlan } from "../types";
 
 export function useDock() {
   const [loading, setLoading] = useState(false);
@@ -39,6 +39,18 @@ export function useDock() {
     return invoke<boolean>("check_dockutil");
   }, []);
 
+  const getDockLocks = useCallback(async () => {
+    return invoke<DockLocks>("get_dock_locks");
+  }, []);
+
+  const unlockDock = useCallback(async () => {
+    await invoke("unlock_dock");
+  }, []);
+
+  const getSwitchConfirmation = useCallback(async (id: string) => {
+    return invoke<SwitchPlan | null>("get_switch_confirmation", { id });
+  }, []);
+
   return {
     loading,
     getCurrentDockApps,
@@ -46,5 +58,8 @@ export function useDock() {
     addAppToProfile,
     removeAppFromProfile,
     checkDockutil,
+    getDockLocks,
+    unlockDock,
+    getSwitchConfirmation,
   };
 }
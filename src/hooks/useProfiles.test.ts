@@ -2,6 +2,7 @@ import { beforeEach, describe, expect, it, vi } from "vitest";
 import { renderHook, act } from "@testing-library/react";
 import { waitFor } from "@testing-library/dom";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { useProfiles } from "./useProfiles";
 import type { Profile } from "../types";
 
@@ -9,7 +10,12 @@ vi.mock("@tauri-apps/api/core", () => ({
   invoke: vi.fn(),
 }));
 
+vi.mock("@tauri-apps/api/event", () => ({
+  listen: vi.fn(async () => () => {}),
+}));
+
 const mockInvoke = vi.mocked(invoke);
+const mockListen = vi.mocked(listen);
 
 describe("useProfiles", () => {
   const profiles: Profile[] = [
@@ -149,6 +155,72 @@ describe("useProfiles", () => {
     expect(mockInvoke).toHaveBeenCalledWith("get_active_profile_id");
   });
 
+  it("applyNextProfile invokes command and refreshes", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return profiles;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      if (cmd === "apply_next_profile") return "profile-1";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => !result.current.loading);
+
+    let appliedId: string | null = null;
+    await act(async () => {
+      appliedId = await result.current.applyNextProfile();
+    });
+
+    expect(appliedId).toBe("profile-1");
+    expect(mockInvoke).toHaveBeenCalledWith("apply_next_profile");
+    expect(mockInvoke).toHaveBeenCalledWith("get_profiles");
+  });
+
+  it("applyPreviousProfile invokes command and refreshes", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return profiles;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      if (cmd === "apply_previous_profile") return "profile-1";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => !result.current.loading);
+
+    let appliedId: string | null = null;
+    await act(async () => {
+      appliedId = await result.current.applyPreviousProfile();
+    });
+
+    expect(appliedId).toBe("profile-1");
+    expect(mockInvoke).toHaveBeenCalledWith("apply_previous_profile");
+    expect(mockInvoke).toHaveBeenCalledWith("get_profiles");
+  });
+
+  it("toggleLastProfile invokes command and refreshes", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return profiles;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      if (cmd === "toggle_last_profile") return "profile-1";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => !result.current.loading);
+
+    let appliedId: string | null = null;
+    await act(async () => {
+      appliedId = await result.current.toggleLastProfile();
+    });
+
+    expect(appliedId).toBe("profile-1");
+    expect(mockInvoke).toHaveBeenCalledWith("toggle_last_profile");
+    expect(mockInvoke).toHaveBeenCalledWith("get_profiles");
+  });
+
   it("sets error when refresh fails", async () => {
     mockInvoke.mockImplementation(async (cmd: string) => {
       if (cmd === "get_profiles") throw new Error("boom");
@@ -162,4 +234,119 @@ describe("useProfiles", () => {
       expect(result.current.error).toContain("boom");
     });
   });
+
+  it("patches app icons streamed in via events", async () => {
+    const withApp: Profile[] = [
+      {
+        ...profiles[0],
+        apps: [{ name: "Safari", path: "/Applications/Safari.app" }],
+      },
+    ];
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return withApp;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => {
+      expect(result.current.profiles).toEqual(withApp);
+    });
+
+    const handler = mockListen.mock.calls.find(
+      ([event]) => event === "app-icon-loaded",
+    )?.[1];
+    expect(handler).toBeDefined();
+
+    act(() => {
+      handler?.({
+        event: "app-icon-loaded",
+        id: 1,
+        payload: {
+          profile_id: "profile-1",
+          path: "/Applications/Safari.app",
+          icon: "abc123",
+        },
+      });
+    });
+
+    expect(result.current.profiles[0].apps[0].icon).toBe("abc123");
+  });
+
+  it("renderProfilePreview invokes command with options", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return profiles;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      if (cmd === "render_profile_preview") return "iVBORw0KGgo=";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => !result.current.loading);
+
+    const preview = await result.current.renderProfilePreview("profile-1", {
+      orientation: "vertical",
+      tile_size: 48,
+    });
+
+    expect(preview).toBe("iVBORw0KGgo=");
+    expect(mockInvoke).toHaveBeenCalledWith("render_profile_preview", {
+      id: "profile-1",
+      options: { orientation: "vertical", tile_size: 48 },
+    });
+  });
+
+  it("exportProfile invokes command and returns preview path", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return profiles;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      if (cmd === "export_profile") return "/tmp/work.png";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => !result.current.loading);
+
+    const previewPath = await result.current.exportProfile(
+      "profile-1",
+      "/tmp/work.json",
+    );
+
+    expect(previewPath).toBe("/tmp/work.png");
+    expect(mockInvoke).toHaveBeenCalledWith("export_profile", {
+      id: "profile-1",
+      path: "/tmp/work.json",
+      options: undefined,
+    });
+  });
+
+  it("refreshes when profiles change in the backend", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_profiles") return profiles;
+      if (cmd === "get_active_profile_id") return "profile-1";
+      return null;
+    });
+
+    const { result } = renderHook(() => useProfiles());
+
+    await waitFor(() => {
+      expect(result.current.profiles).toEqual(profiles);
+    });
+
+    const handler = mockListen.mock.calls.find(
+      ([event]) => event === "profiles-changed",
+    )?.[1];
+    expect(handler).toBeDefined();
+
+    mockInvoke.mockClear();
+    await act(async () => {
+      handler?.({ event: "profiles-changed", id: 2, payload: null });
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("get_profiles");
+    expect(mockInvoke).toHaveBeenCalledWith("get_active_profile_id");
+  });
 });
@@ -1,6 +1,13 @@
 import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { Profile } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import type { PreviewOptions, Profile } from "../types";
+
+interface AppIconLoaded {
+  profile_id: string | null;
+  path: string;
+  icon: string;
+}
 
 export function useProfiles() {
   const [profiles, setProfiles] = useState<Profile[]>([]);
@@ -28,6 +35,37 @@ export function useProfiles() {
     refresh();
   }, [refresh]);
 
+  useEffect(() => {
+    const unlisten = listen<AppIconLoaded>("app-icon-loaded", ({ payload }) => {
+      if (!payload.profile_id) return;
+      setProfiles((current) =>
+        current.map((profile) =>
+          profile.id === payload.profile_id
+            ? {
+                ...profile,
+                apps: profile.apps.map((app) =>
+                  app.path === payload.path ? { ...app, icon: payload.icon } : app,
+                ),
+              }
+            : profile,
+        ),
+      );
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  useEffect(() => {
+    // Profiles can change from the tray, e.g. when the current Dock is saved or a profile is applied.
+    const unlisten = listen("profiles-changed", () => {
+      refresh();
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [refresh]);
+
   const createProfile = useCallback(
     async (name: string) => {
       const profile = await invoke<Profile>("create_profile", { name });
@@ -69,6 +107,38 @@ export function useProfiles() {
     [refresh],
   );
 
+  const applyNextProfile = useCallback(async () => {
+    const id = await invoke<string | null>("apply_next_profile");
+    await refresh();
+    return id;
+  }, [refresh]);
+
+  const applyPreviousProfile = useCallback(async () => {
+    const id = await invoke<string | null>("apply_previous_profile");
+    await refresh();
+    return id;
+  }, [refresh]);
+
+  const toggleLastProfile = useCallback(async () => {
+    const id = await invoke<string | null>("toggle_last_profile");
+    await refresh();
+    return id;
+  }, [refresh]);
+
+  const renderProfilePreview = useCallback(
+    async (id: string, options?: PreviewOptions) => {
+      return invoke<string>("render_profile_preview", { id, options });
+    },
+    [],
+  );
+
+  const exportProfile = useCallback(
+    async (id: string, path: string, options?: PreviewOptions) => {
+      return invoke<string>("export_profile", { id, path, options });
+    },
+    [],
+  );
+
   return {
     profiles,
     activeProfileId,
@@ -80,5 +150,10 @@ export function useProfiles() {
     deleteProfile,
     reorderProfiles,
     applyProfile,
+    applyNextProfile,
+    applyPreviousProfile,
+    toggleLastProfile,
+    renderProfilePreview,
+    exportProfile,
   };
 }
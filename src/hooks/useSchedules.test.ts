@@ -0,0 +1,75 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { renderHook, act } from "@testing-library/react";
+import { waitFor } from "@testing-library/dom";
+import { invoke } from "@tauri-apps/api/core";
+import { useSchedules } from "./useSchedules";
+import type { ScheduleRule } from "../types";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+const mockInvoke = vi.mocked(invoke);
+
+describe("useSchedules", () => {
+  const workdays: ScheduleRule = {
+    id: "s1",
+    profile_id: "work",
+    weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri"],
+    time: "09:00",
+    enabled: true,
+  };
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("loads schedules on mount", async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_schedules") return [workdays];
+      return null;
+    });
+
+    const { result } = renderHook(() => useSchedules());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    expect(result.current.schedules).toEqual([workdays]);
+    expect(mockInvoke).toHaveBeenCalledWith("get_schedules");
+  });
+
+  it("updateSchedules keeps the schedules saved by the backend", async () => {
+    const evening: ScheduleRule = {
+      id: "",
+      profile_id: "personal",
+      weekdays: [],
+      time: "18:00",
+      timezone: "Europe/Madrid",
+      enabled: true,
+    };
+    const saved = [workdays, { ...evening, id: "s2" }];
+
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_schedules") return [workdays];
+      if (cmd === "update_schedules") return saved;
+      return null;
+    });
+
+    const { result } = renderHook(() => useSchedules());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    await act(async () => {
+      await result.current.updateSchedules([workdays, evening]);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("update_schedules", {
+      schedules: [workdays, evening],
+    });
+    expect(result.current.schedules).toEqual(saved);
+  });
+});
@@ -0,0 +1,30 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { ScheduleRule } from "../types";
+
+export function useSchedules() {
+  const [schedules, setSchedules] = useState<ScheduleRule[]>([]);
+  const [loading, setLoading] = useState(true);
+
+  const refresh = useCallback(async () => {
+    try {
+      const fetched = await invoke<ScheduleRule[]>("get_schedules");
+      setSchedules(fetched);
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const updateSchedules = useCallback(async (newSchedules: ScheduleRule[]) => {
+    const saved = await invoke<ScheduleRule[]>("update_schedules", {
+      schedules: newSchedules,
+    });
+    setSchedules(saved);
+  }, []);
+
+  return { schedules, loading, refresh, updateSchedules };
+}
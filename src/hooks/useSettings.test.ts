@@ -20,6 +20,10 @@ describe("useSettings", () => {
     const settings: Settings = {
       launch_at_login: true,
       confirm_before_switch: true,
+      update_moved_app_paths: false,
+      app_roots: [],
+      tray_display: "icon",
+      tray_profile_order: "manual",
     };
 
     mockInvoke.mockImplementation(async (cmd: string) => {
@@ -41,6 +45,10 @@ describe("useSettings", () => {
     const settings: Settings = {
       launch_at_login: false,
       confirm_before_switch: false,
+      update_moved_app_paths: false,
+      app_roots: [],
+      tray_display: "icon",
+      tray_profile_order: "manual",
     };
 
     mockInvoke.mockImplementation(async (cmd: string) => {
@@ -57,6 +65,10 @@ describe("useSettings", () => {
     const nextSettings: Settings = {
       launch_at_login: true,
       confirm_before_switch: false,
+      update_moved_app_paths: false,
+      app_roots: [],
+      tray_display: "icon",
+      tray_profile_order: "manual",
     };
 
     await act(async () => {
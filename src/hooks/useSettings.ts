@@ -6,6 +6,10 @@ export function useSettings() {
   const [settings, setSettings] = useState<Settings>({
     launch_at_login: false,
     confirm_before_switch: false,
+    update_moved_app_paths: false,
+    app_roots: [],
+    tray_display: "icon",
+    tray_profile_order: "manual",
   });
   const [loading, setLoading] = useState(true);
 
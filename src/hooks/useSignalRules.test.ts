@@ -0,0 +1,117 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { renderHook, act } from "@testing-library/react";
+import { waitFor } from "@testing-library/dom";
+import { invoke } from "@tauri-apps/api/core";
+import { useSignalRules } from "./useSignalRules";
+import type { RuleEvaluation, SignalFiring, SignalRule } from "../types";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+const mockInvoke = vi.mocked(invoke);
+
+describe("useSignalRules", () => {
+  const office: SignalRule = {
+    id: "r1",
+    name: "Office",
+    profile_id: "work",
+    conditions: [{ kind: "path_exists", path: "/Volumes/Work", negate: false }],
+    priority: 0,
+    enabled: true,
+  };
+  const evaluation: RuleEvaluation = {
+    rule_id: "r1",
+    matched: true,
+    conditions: [{ met: true, description: "/Volumes/Work exists" }],
+  };
+  const firing: SignalFiring = {
+    rule_id: "r1",
+    rule_name: "Office",
+    profile_id: "work",
+    reasons: ["/Volumes/Work exists"],
+    at: "2026-03-02T09:00:00+00:00",
+  };
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_signal_rules") return [office];
+      if (cmd === "explain_signal_rules") return [evaluation];
+      if (cmd === "get_last_signal_firing") return firing;
+      return null;
+    });
+  });
+
+  it("loads rules with their explanation on mount", async () => {
+    const { result } = renderHook(() => useSignalRules());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    expect(result.current.signalRules).toEqual([office]);
+    expect(result.current.evaluations).toEqual([evaluation]);
+    expect(result.current.lastFiring).toEqual(firing);
+  });
+
+  it("updateSignalRules keeps the saved rules and explains them again", async () => {
+    const vpn: SignalRule = {
+      id: "",
+      name: "VPN",
+      profile_id: "remote",
+      conditions: [{ kind: "hostname", name: "studio-mac", negate: true }],
+      priority: 5,
+      enabled: true,
+    };
+    const saved = [office, { ...vpn, id: "r2" }];
+
+    const { result } = renderHook(() => useSignalRules());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "update_signal_rules") return saved;
+      if (cmd === "explain_signal_rules") return [evaluation];
+      if (cmd === "get_last_signal_firing") return null;
+      return null;
+    });
+
+    await act(async () => {
+      await result.current.updateSignalRules([office, vpn]);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("update_signal_rules", {
+      rules: [office, vpn],
+    });
+    expect(result.current.signalRules).toEqual(saved);
+    expect(result.current.lastFiring).toBeNull();
+  });
+
+  it("explain refreshes the evaluations", async () => {
+    const { result } = renderHook(() => useSignalRules());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    const unmounted: RuleEvaluation = {
+      rule_id: "r1",
+      matched: false,
+      conditions: [{ met: false, description: "/Volumes/Work does not exist" }],
+    };
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "explain_signal_rules") return [unmounted];
+      if (cmd === "get_last_signal_firing") return firing;
+      return null;
+    });
+
+    await act(async () => {
+      await result.current.explain();
+    });
+
+    expect(result.current.evaluations).toEqual([unmounted]);
+  });
+});
@@ -0,0 +1,44 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { RuleEvaluation, SignalFiring, SignalRule } from "../types";
+
+export function useSignalRules() {
+  const [signalRules, setSignalRules] = useState<SignalRule[]>([]);
+  const [evaluations, setEvaluations] = useState<RuleEvaluation[]>([]);
+  const [lastFiring, setLastFiring] = useState<SignalFiring | null>(null);
+  const [loading, setLoading] = useState(true);
+
+  const explain = useCallback(async () => {
+    const [explained, firing] = await Promise.all([
+      invoke<RuleEvaluation[]>("explain_signal_rules"),
+      invoke<SignalFiring | null>("get_last_signal_firing"),
+    ]);
+    setEvaluations(explained);
+    setLastFiring(firing);
+  }, []);
+
+  const refresh = useCallback(async () => {
+    try {
+      const fetched = await invoke<SignalRule[]>("get_signal_rules");
+      setSignalRules(fetched);
+      await explain();
+    } finally {
+      setLoading(false);
+    }
+  }, [explain]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const updateSignalRules = useCallback(
+    async (rules: SignalRule[]) => {
+      const saved = await invoke<SignalRule[]>("update_signal_rules", { rules });
+      setSignalRules(saved);
+      await explain();
+    },
+    [explain],
+  );
+
+  return { signalRules, evaluations, lastFiring, loading, refresh, explain, updateSignalRules };
+}
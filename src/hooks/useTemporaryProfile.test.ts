@@ -0,0 +1,95 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { renderHook, act } from "@testing-library/react";
+import { waitFor } from "@testing-library/dom";
+import { invoke } from "@tauri-apps/api/core";
+import { useTemporaryProfile } from "./useTemporaryProfile";
+import type { TemporarySession } from "../types";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+vi.mock("@tauri-apps/api/event", () => ({
+  listen: vi.fn(async () => () => {}),
+}));
+
+const mockInvoke = vi.mocked(invoke);
+
+describe("useTemporaryProfile", () => {
+  const presenting: TemporarySession = {
+    profile_id: "presenting",
+    ends_at: "2026-03-02T10:45:00+00:00",
+    previous_profile_id: "home",
+  };
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "get_temporary_session") return presenting;
+      return null;
+    });
+  });
+
+  it("loads the running session on mount", async () => {
+    const { result } = renderHook(() => useTemporaryProfile());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    expect(result.current.session).toEqual(presenting);
+    expect(mockInvoke).toHaveBeenCalledWith("get_temporary_session");
+  });
+
+  it("applyTemporarily passes the duration in seconds", async () => {
+    const { result } = renderHook(() => useTemporaryProfile());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    const focus = { ...presenting, profile_id: "focus" };
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "apply_profile_temporarily") return focus;
+      return null;
+    });
+
+    await act(async () => {
+      await result.current.applyTemporarily("focus", 45);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("apply_profile_temporarily", {
+      id: "focus",
+      duration: 2700,
+    });
+    expect(result.current.session).toEqual(focus);
+  });
+
+  it("extend and end update the session", async () => {
+    const { result } = renderHook(() => useTemporaryProfile());
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false);
+    });
+
+    const extended = { ...presenting, ends_at: "2026-03-02T11:00:00+00:00" };
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === "extend_temporary_profile") return extended;
+      return null;
+    });
+
+    await act(async () => {
+      await result.current.extend(15);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("extend_temporary_profile", { duration: 900 });
+    expect(result.current.session).toEqual(extended);
+
+    await act(async () => {
+      await result.current.end();
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith("end_temporary_profile");
+    expect(result.current.session).toBeNull();
+  });
+});
@@ -0,0 +1,53 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { TemporarySession } from "../types";
+
+export function useTemporaryProfile() {
+  const [session, setSession] = useState<TemporarySession | null>(null);
+  const [loading, setLoading] = useState(true);
+
+  const refresh = useCallback(async () => {
+    try {
+      setSession(await invoke<TemporarySession | null>("get_temporary_session"));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    // Sessions end on their own, or from the tray.
+    const unlisten = listen("profiles-changed", () => {
+      refresh();
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [refresh]);
+
+  const applyTemporarily = useCallback(async (id: string, minutes: number) => {
+    const started = await invoke<TemporarySession>("apply_profile_temporarily", {
+      id,
+      duration: minutes * 60,
+    });
+    setSession(started);
+  }, []);
+
+  const extend = useCallback(async (minutes: number) => {
+    const extended = await invoke<TemporarySession>("extend_temporary_profile", {
+      duration: minutes * 60,
+    });
+    setSession(extended);
+  }, []);
+
+  const end = useCallback(async () => {
+    await invoke("end_temporary_profile");
+    setSession(null);
+  }, []);
+
+  return { session, loading, refresh, applyTemporarily, extend, end };
+}
@@ -0,0 +1,13 @@
+export interface CalendarRule {
+  id: string;
+  pattern: string;
+  profile_id: string;
+  enabled: boolean;
+}
+
+export interface CalendarSession {
+  event_key: string;
+  rule_id: string;
+  profile_id: string;
+  previous_profile_id?: string;
+}
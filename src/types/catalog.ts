@@ -0,0 +1,8 @@
+export interface CatalogApp {
+  name: string;
+  path: string;
+  bundle_id: string | null;
+  version: string | null;
+  category: string | null;
+  icon_file: string | null;
+}
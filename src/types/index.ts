@@ -1,3 +1,24 @@
-export type { AppEntry, Profile } from './profile';
-export type { Settings } from './settings';
-export type { StoreData } from './store';
+export type { CalendarRule, CalendarSession } from './calendar';
+export type { CatalogApp } from './catalog';
+export type {
+  AddAppError,
+  AppEntry,
+  DockItemKind,
+  DockLocks,
+  PreviewOptions,
+  PreviewOrientation,
+  Profile,
+  SwitchPlan,
+} from './profile';
+export type { ScheduleRule, Weekday } from './schedule';
+export type { ProfileOrder, Settings, TrayDisplay } from './settings';
+export type {
+  ConditionResult,
+  RuleEvaluation,
+  Signal,
+  SignalCondition,
+  SignalFiring,
+  SignalRule,
+} from './signal';
+export type { ApplyResult, DirectorySession, StoreData } from './store';
+export type { TemporarySession } from './temporary';
@@ -1,14 +1,57 @@
+export type DockItemKind = 'app' | 'spacer' | 'small_spacer';
+
 export interface AppEntry {
   name: string;
   path: string;
   icon?: string;
   bundle_id?: string;
+  version?: string;
+  kind?: DockItemKind;
+}
+
+export interface AddAppError {
+  kind:
+    | 'invalid_bundle'
+    | 'profile_not_found'
+    | 'already_in_profile'
+    | 'lock_poisoned'
+    | 'store';
+  message: string;
+}
+
+export interface DockLocks {
+  contents: boolean;
+  size: boolean;
+  position: boolean;
+  magnify: boolean;
 }
 
 export interface Profile {
   id: string;
   name: string;
   apps: AppEntry[];
+  locks?: DockLocks;
+  symbol?: string;
+  shortcut?: string;
   created_at: string;
   updated_at: string;
 }
+
+export type PreviewOrientation = 'horizontal' | 'vertical';
+
+export interface PreviewOptions {
+  orientation?: PreviewOrientation;
+  tile_size?: number;
+  separator?: boolean;
+}
+
+export interface SwitchPlan {
+  profile_id: string;
+  profile_name: string;
+  added: string[];
+  removed: string[];
+  kept: number;
+  missing: string[];
+  locks: DockLocks;
+  summary: string;
+}
@@ -0,0 +1,10 @@
+export type Weekday = 'Mon' | 'Tue' | 'Wed' | 'Thu' | 'Fri' | 'Sat' | 'Sun';
+
+export interface ScheduleRule {
+  id: string;
+  profile_id: string;
+  weekdays: Weekday[];
+  time: string;
+  timezone?: string;
+  enabled: boolean;
+}
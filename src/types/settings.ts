@@ -1,4 +1,15 @@
+export type TrayDisplay = 'icon' | 'name' | 'profile_icon';
+
+export type ProfileOrder = 'manual' | 'recently_used';
+
 export interface Settings {
   launch_at_login: boolean;
   confirm_before_switch: boolean;
+  update_moved_app_paths: boolean;
+  app_roots: string[];
+  tray_display: TrayDisplay;
+  tray_profile_order: ProfileOrder;
+  next_profile_shortcut?: string;
+  previous_profile_shortcut?: string;
+  toggle_last_profile_shortcut?: string;
 }
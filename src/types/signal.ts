@@ -0,0 +1,34 @@
+export type Signal =
+  | { kind: 'path_exists'; path: string }
+  | { kind: 'env_flag'; file: string; key: string; value?: string }
+  | { kind: 'hostname'; name: string };
+
+export type SignalCondition = Signal & { negate: boolean };
+
+export interface SignalRule {
+  id: string;
+  name: string;
+  profile_id: string;
+  conditions: SignalCondition[];
+  priority: number;
+  enabled: boolean;
+}
+
+export interface ConditionResult {
+  met: boolean;
+  description: string;
+}
+
+export interface RuleEvaluation {
+  rule_id: string;
+  matched: boolean;
+  conditions: ConditionResult[];
+}
+
+export interface SignalFiring {
+  rule_id: string;
+  rule_name: string;
+  profile_id: string;
+  reasons: string[];
+  at: string;
+}
@@ -1,9 +1,40 @@
+import type { CalendarRule, CalendarSession } from './calendar';
 import type { Profile } from './profile';
+import type { ScheduleRule } from './schedule';
 import type { Settings } from './settings';
+import type { SignalFiring, SignalRule } from './signal';
+import type { TemporarySession } from './temporary';
+
+export interface ApplyResult {
+  profile_id: string;
+  profile_name: string;
+  at: string;
+  ok: boolean;
+  messages?: string[];
+}
+
+export interface DirectorySession {
+  root: string;
+  profile_id?: string;
+  previous_profile_id?: string;
+}
 
 export interface StoreData {
   profiles: Profile[];
   active_profile_id: string | null;
   settings: Settings;
   schema_version: number;
+  last_apply?: ApplyResult;
+  recent_profile_ids: string[];
+  schedules: ScheduleRule[];
+  automation_paused: boolean;
+  schedule_checked_at?: string;
+  calendar_files: string[];
+  calendar_rules: CalendarRule[];
+  calendar_session?: CalendarSession;
+  signal_rules: SignalRule[];
+  signal_rule_id?: string;
+  last_signal_firing?: SignalFiring;
+  directory_session?: DirectorySession;
+  temporary_session?: TemporarySession;
 }
@@ -0,0 +1,8 @@
+import type { AppEntry } from './profile';
+
+export interface TemporarySession {
+  profile_id: string;
+  ends_at: string;
+  previous_profile_id?: string;
+  previous_apps?: AppEntry[];
+}
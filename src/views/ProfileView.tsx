@@ -12,28 +12,71 @@ import {
   verticalListSortingStrategy,
   arrayMove,
 } from '@dnd-kit/sortable';
-import { Plus, Download, Play } from 'lucide-react';
-import type { Profile, AppEntry } from '../types/profile';
+import { Plus, Download, Play, Lock, LockOpen, Share, Space, Timer } from 'lucide-react';
+import type { Profile, AppEntry, DockItemKind, DockLocks } from '../types/profile';
+import type { TemporarySession } from '../types/temporary';
 import SortableAppItem from '../components/SortableAppItem';
+import ShortcutField from '../components/ShortcutField';
 
 interface ProfileViewProps {
   profile: Profile | null;
   activeProfileId: string | null;
+  dockLocked: boolean;
+  onUnlockDock: () => void;
   onApplyProfile: (id: string) => void;
+  temporarySession: TemporarySession | null;
+  onApplyTemporarily: (id: string, minutes: number) => void;
+  onExtendTemporary: (minutes: number) => void;
+  onEndTemporary: () => void;
   onSaveDock: (id: string) => void;
   onAddApp: (id: string) => void;
+  onAddSpacer: (profile: Profile, kind: DockItemKind) => void;
+  onExportProfile: (profile: Profile) => void;
+  previewSrc: string | null;
   onRemoveApp: (id: string, index: number) => void;
   onUpdateProfile: (profile: Profile) => void;
+  onSetShortcut: (profile: Profile, shortcut: string | undefined) => Promise<void>;
 }
 
+const LOCK_OPTIONS: { key: keyof DockLocks; label: string }[] = [
+  { key: 'contents', label: 'Apps' },
+  { key: 'size', label: 'Size' },
+  { key: 'position', label: 'Position' },
+  { key: 'magnify', label: 'Magnification' },
+];
+
+const TEMPORARY_MINUTES = [15, 30, 45, 60, 90, 120];
+const EXTEND_MINUTES = 15;
+
+const NO_LOCKS: DockLocks = {
+  contents: false,
+  size: false,
+  position: false,
+  magnify: false,
+};
+
+// Spacers have no path, so their position keeps them distinct for drag and drop.
+const itemId = (app: AppEntry, index: number) =>
+  app.path || `${app.kind ?? 'spacer'}-${index}`;
+
 const ProfileView: React.FC<ProfileViewProps> = ({
   profile,
   activeProfileId,
+  dockLocked,
+  onUnlockDock,
   onApplyProfile,
+  temporarySession,
+  onApplyTemporarily,
+  onExtendTemporary,
+  onEndTemporary,
   onSaveDock,
   onAddApp,
+  onAddSpacer,
+  onExportProfile,
+  previewSrc,
   onRemoveApp,
   onUpdateProfile,
+  onSetShortcut,
 }) => {
   const sensors = useSensors(
     useSensor(PointerSensor, {
@@ -58,12 +101,26 @@ const ProfileView: React.FC<ProfileViewProps> = ({
   }
 
   const isActive = activeProfileId === profile.id;
+  const temporaryUntil =
+    isActive && temporarySession?.profile_id === profile.id
+      ? new Date(temporarySession.ends_at).toLocaleTimeString([], {
+          hour: '2-digit',
+          minute: '2-digit',
+        })
+      : null;
+  const locks = profile.locks ?? NO_LOCKS;
+  const appCount = profile.apps.filter((app) => (app.kind ?? 'app') === 'app').length;
+
+  const toggleLock = (key: keyof DockLocks) => {
+    onUpdateProfile({ ...profile, locks: { ...locks, [key]: !locks[key] } });
+  };
 
   const handleDragEnd = (event: DragEndEvent) => {
     const { active, over } = event;
     if (over && active.id !== over.id) {
-      const oldIndex = profile.apps.findIndex((app: AppEntry) => app.path === active.id);
-      const newIndex = profile.apps.findIndex((app: AppEntry) => app.path === over.id);
+      const ids = profile.apps.map(itemId);
+      const oldIndex = ids.indexOf(String(active.id));
+      const newIndex = ids.indexOf(String(over.id));
       
       if (oldIndex !== -1 && newIndex !== -1) {
         const newApps = arrayMove(profile.apps, oldIndex, newIndex);
@@ -78,11 +135,33 @@ const ProfileView: React.FC<ProfileViewProps> = ({
         <div>
           <h1 className="text-2xl font-bold text-gray-900 dark:text-white">{profile.name}</h1>
           <p className="mt-1 text-sm text-gray-500 dark:text-gray-400">
-            {profile.apps.length === 0 ? 'No apps' : `${profile.apps.length} app${profile.apps.length === 1 ? '' : 's'}`}
+            {appCount === 0 ? 'No apps' : `${appCount} app${appCount === 1 ? '' : 's'}`}
           </p>
         </div>
         
         <div className="flex items-center gap-3">
+          {dockLocked && (
+            <button
+              type="button"
+              onClick={onUnlockDock}
+              title="The Dock is locked. Click to unlock it."
+              className="flex items-center gap-2 rounded-lg bg-amber-100 px-4 py-2 text-sm font-medium text-amber-800 hover:bg-amber-200 dark:bg-amber-900/30 dark:text-amber-300 dark:hover:bg-amber-900/50 transition-colors"
+            >
+              <Lock className="h-4 w-4" />
+              Unlock Dock
+            </button>
+          )}
+
+          <button
+            type="button"
+            onClick={() => onExportProfile(profile)}
+            title="Export this profile with a preview image"
+            className="flex items-center gap-2 rounded-lg border border-gray-300 bg-white px-4 py-2 text-sm font-medium text-gray-700 shadow-sm hover:bg-gray-50 dark:border-slate-600 dark:bg-slate-800 dark:text-gray-200 dark:hover:bg-slate-700 transition-colors"
+          >
+            <Share className="h-4 w-4" />
+            Export
+          </button>
+
           <button
             type="button"
             onClick={() => onSaveDock(profile.id)}
@@ -92,25 +171,75 @@ const ProfileView: React.FC<ProfileViewProps> = ({
             Save apps from Dock
           </button>
           
-          {isActive ? (
+          {isActive && temporaryUntil ? (
+            <>
+              <span className="flex items-center gap-2 rounded-lg bg-green-100 px-4 py-2 text-sm font-medium text-green-700 dark:bg-green-900/30 dark:text-green-400">
+                <Timer className="h-4 w-4" />
+                Active until {temporaryUntil}
+              </span>
+              <button
+                type="button"
+                onClick={() => onExtendTemporary(EXTEND_MINUTES)}
+                className="rounded-lg border border-gray-300 bg-white px-3 py-2 text-sm font-medium text-gray-700 shadow-sm hover:bg-gray-50 dark:border-slate-600 dark:bg-slate-800 dark:text-gray-200 dark:hover:bg-slate-700 transition-colors"
+              >
+                +{EXTEND_MINUTES} min
+              </button>
+              <button
+                type="button"
+                onClick={onEndTemporary}
+                className="rounded-lg border border-gray-300 bg-white px-3 py-2 text-sm font-medium text-gray-700 shadow-sm hover:bg-gray-50 dark:border-slate-600 dark:bg-slate-800 dark:text-gray-200 dark:hover:bg-slate-700 transition-colors"
+              >
+                End now
+              </button>
+            </>
+          ) : isActive ? (
             <span className="flex items-center gap-2 rounded-lg bg-green-100 px-4 py-2 text-sm font-medium text-green-700 dark:bg-green-900/30 dark:text-green-400">
               <span className="h-2 w-2 rounded-full bg-green-500" />
               Active
             </span>
           ) : (
-            <button
-              type="button"
-              onClick={() => onApplyProfile(profile.id)}
-              className="flex items-center gap-2 rounded-lg bg-blue-600 px-4 py-2 text-sm font-medium text-white shadow-sm hover:bg-blue-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-blue-600 transition-colors"
-            >
-              <Play className="h-4 w-4 fill-current" />
-              Apply to Dock
-            </button>
+            <>
+              <select
+                value=""
+                title="Apply this profile for a while, then go back to the current Dock"
+                onChange={(event) => {
+                  if (event.target.value) {
+                    onApplyTemporarily(profile.id, Number(event.target.value));
+                  }
+                }}
+                className="rounded-lg border border-gray-300 bg-white px-3 py-2 text-sm font-medium text-gray-700 shadow-sm hover:bg-gray-50 dark:border-slate-600 dark:bg-slate-800 dark:text-gray-200 dark:hover:bg-slate-700"
+              >
+                <option value="">Apply for…</option>
+                {TEMPORARY_MINUTES.map((minutes) => (
+                  <option key={minutes} value={minutes}>
+                    {minutes < 60 ? `${minutes} minutes` : `${minutes / 60} hour${minutes === 60 ? '' : 's'}`}
+                  </option>
+                ))}
+              </select>
+              <button
+                type="button"
+                onClick={() => onApplyProfile(profile.id)}
+                className="flex items-center gap-2 rounded-lg bg-blue-600 px-4 py-2 text-sm font-medium text-white shadow-sm hover:bg-blue-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-blue-600 transition-colors"
+              >
+                <Play className="h-4 w-4 fill-current" />
+                Apply to Dock
+              </button>
+            </>
           )}
         </div>
       </div>
 
       <div className="flex-1 overflow-y-auto p-8">
+        {previewSrc && profile.apps.length > 0 && (
+          <div className="mb-6 flex justify-center overflow-x-auto">
+            <img
+              src={previewSrc}
+              alt={`${profile.name} Dock preview`}
+              className="max-h-24"
+            />
+          </div>
+        )}
+
         {profile.apps.length === 0 ? (
           <div className="flex flex-col items-center justify-center rounded-xl border-2 border-dashed border-gray-200 bg-gray-50/50 py-12 text-center dark:border-slate-700 dark:bg-slate-800/20">
             <div className="mb-4 rounded-full bg-gray-100 p-3 dark:bg-slate-800">
@@ -129,13 +258,13 @@ const ProfileView: React.FC<ProfileViewProps> = ({
               onDragEnd={handleDragEnd}
             >
               <SortableContext
-                items={profile.apps.map((app: AppEntry) => app.path)}
+                items={profile.apps.map(itemId)}
                 strategy={verticalListSortingStrategy}
               >
                 {profile.apps.map((app: AppEntry, index: number) => (
                   <SortableAppItem
-                    key={app.path}
-                    id={app.path}
+                    key={itemId(app, index)}
+                    id={itemId(app, index)}
                     app={app}
                     onRemove={() => onRemoveApp(profile.id, index)}
                   />
@@ -153,6 +282,87 @@ const ProfileView: React.FC<ProfileViewProps> = ({
           <Plus className="h-4 w-4" />
           Add App
         </button>
+
+        <div className="mt-2 flex gap-2">
+          <button
+            type="button"
+            onClick={() => onAddSpacer(profile, 'spacer')}
+            className="flex flex-1 items-center justify-center gap-2 rounded-lg border border-dashed border-gray-300 px-4 py-2 text-sm text-gray-500 hover:border-gray-400 hover:text-gray-600 dark:border-slate-700 dark:text-gray-400 dark:hover:border-slate-600 dark:hover:text-gray-300 transition-colors"
+          >
+            <Space className="h-4 w-4" />
+            Add Spacer
+          </button>
+          <button
+            type="button"
+            onClick={() => onAddSpacer(profile, 'small_spacer')}
+            className="flex flex-1 items-center justify-center gap-2 rounded-lg border border-dashed border-gray-300 px-4 py-2 text-sm text-gray-500 hover:border-gray-400 hover:text-gray-600 dark:border-slate-700 dark:text-gray-400 dark:hover:border-slate-600 dark:hover:text-gray-300 transition-colors"
+          >
+            <Space className="h-3 w-3" />
+            Add Small Spacer
+          </button>
+        </div>
+
+        <div className="mt-8">
+          <h2 className="flex items-center gap-2 text-sm font-semibold text-gray-900 dark:text-white">
+            {locks.contents || locks.size || locks.position || locks.magnify ? (
+              <Lock className="h-4 w-4" />
+            ) : (
+              <LockOpen className="h-4 w-4" />
+            )}
+            Lockdown
+          </h2>
+          <p className="mt-1 text-sm text-gray-500 dark:text-gray-400">
+            Lock these Dock settings after applying this profile.
+          </p>
+          <div className="mt-3 flex flex-wrap gap-4">
+            {LOCK_OPTIONS.map(({ key, label }) => (
+              <label key={key} className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+                <input
+                  type="checkbox"
+                  checked={locks[key]}
+                  onChange={() => toggleLock(key)}
+                  className="h-4 w-4 rounded border-gray-300 text-blue-600 focus:ring-blue-600 dark:border-slate-600"
+                />
+                {label}
+              </label>
+            ))}
+          </div>
+        </div>
+
+        <div className="mt-8">
+          <h2 className="text-sm font-semibold text-gray-900 dark:text-white">Menu Bar Symbol</h2>
+          <p className="mt-1 text-sm text-gray-500 dark:text-gray-400">
+            Up to two letters or an emoji, shown in the menu bar when it displays the active profile symbol. Leave empty to use the profile's initials.
+          </p>
+          <input
+            key={profile.id}
+            type="text"
+            defaultValue={profile.symbol ?? ''}
+            maxLength={4}
+            placeholder="e.g. W or 🚀"
+            onBlur={(event) => {
+              const symbol = event.target.value.trim() || undefined;
+              if (symbol !== profile.symbol) {
+                onUpdateProfile({ ...profile, symbol });
+              }
+            }}
+            className="mt-3 w-24 rounded-lg border border-gray-300 bg-white px-3 py-1.5 text-sm text-gray-900 shadow-sm focus:border-blue-600 focus:outline-none focus:ring-1 focus:ring-blue-600 dark:border-slate-600 dark:bg-slate-800 dark:text-white"
+          />
+        </div>
+
+        <div className="mt-8">
+          <h2 className="text-sm font-semibold text-gray-900 dark:text-white">Keyboard Shortcut</h2>
+          <p className="mt-1 text-sm text-gray-500 dark:text-gray-400">
+            Switch to this profile from anywhere. Combine modifiers and a key, like Cmd+Alt+1. Leave empty to disable.
+          </p>
+          <div className="mt-3">
+            <ShortcutField
+              key={profile.id}
+              value={profile.shortcut}
+              onSave={(shortcut) => onSetShortcut(profile, shortcut)}
+            />
+          </div>
+        </div>
       </div>
     </div>
   );
@@ -1,16 +1,74 @@
 import React from 'react';
 import { ArrowLeft } from 'lucide-react';
-import type { Settings } from '../types/settings';
+import type {
+  CalendarRule,
+  Profile,
+  RuleEvaluation,
+  ScheduleRule,
+  SignalFiring,
+  SignalRule,
+} from '../types';
+import type { ProfileOrder, Settings, TrayDisplay } from '../types/settings';
+import CalendarRuleList from '../components/CalendarRuleList';
+import ScheduleList from '../components/ScheduleList';
+import ShortcutField from '../components/ShortcutField';
+import SignalRuleList from '../components/SignalRuleList';
+
+const TRAY_DISPLAY_OPTIONS: { value: TrayDisplay; label: string }[] = [
+  { value: 'icon', label: 'DockSwitcher icon' },
+  { value: 'name', label: 'Active profile name' },
+  { value: 'profile_icon', label: 'Active profile symbol' },
+];
+
+const PROFILE_ORDER_OPTIONS: { value: ProfileOrder; label: string }[] = [
+  { value: 'manual', label: 'Sidebar order' },
+  { value: 'recently_used', label: 'Recently used first' },
+];
+
+type ShortcutSetting =
+  | 'next_profile_shortcut'
+  | 'previous_profile_shortcut'
+  | 'toggle_last_profile_shortcut';
+
+const SHORTCUT_OPTIONS: { key: ShortcutSetting; label: string; description: string }[] = [
+  { key: 'next_profile_shortcut', label: 'Next profile', description: 'Switch to the profile below the active one' },
+  { key: 'previous_profile_shortcut', label: 'Previous profile', description: 'Switch to the profile above the active one' },
+  { key: 'toggle_last_profile_shortcut', label: 'Toggle last two', description: 'Switch back to the previously active profile' },
+];
 
 interface SettingsViewProps {
   settings: Settings;
-  onUpdateSettings: (newSettings: Settings) => void;
+  profiles: Profile[];
+  schedules: ScheduleRule[];
+  calendarFiles: string[];
+  calendarRules: CalendarRule[];
+  signalRules: SignalRule[];
+  signalEvaluations: RuleEvaluation[];
+  lastSignalFiring: SignalFiring | null;
+  onUpdateSettings: (newSettings: Settings) => Promise<void>;
+  onUpdateSchedules: (schedules: ScheduleRule[]) => Promise<void>;
+  onUpdateCalendarFiles: (files: string[]) => Promise<void>;
+  onUpdateCalendarRules: (rules: CalendarRule[]) => Promise<void>;
+  onUpdateSignalRules: (rules: SignalRule[]) => Promise<void>;
+  onExplainSignalRules: () => Promise<void>;
   onBack: () => void;
 }
 
 const SettingsView: React.FC<SettingsViewProps> = ({
   settings,
+  profiles,
+  schedules,
+  calendarFiles,
+  calendarRules,
+  signalRules,
+  signalEvaluations,
+  lastSignalFiring,
   onUpdateSettings,
+  onUpdateSchedules,
+  onUpdateCalendarFiles,
+  onUpdateCalendarRules,
+  onUpdateSignalRules,
+  onExplainSignalRules,
   onBack,
 }) => {
   const toggleLaunchAtLogin = () => {
@@ -27,6 +85,33 @@ const SettingsView: React.FC<SettingsViewProps> = ({
     });
   };
 
+  const toggleUpdateMovedAppPaths = () => {
+    onUpdateSettings({
+      ...settings,
+      update_moved_app_paths: !settings.update_moved_app_paths,
+    });
+  };
+
+  const changeTrayDisplay = (trayDisplay: TrayDisplay) => {
+    onUpdateSettings({
+      ...settings,
+      tray_display: trayDisplay,
+    });
+  };
+
+  const changeProfileOrder = (profileOrder: ProfileOrder) => {
+    onUpdateSettings({
+      ...settings,
+      tray_profile_order: profileOrder,
+    });
+  };
+
+  const changeShortcut = (key: ShortcutSetting, shortcut: string | undefined) =>
+    onUpdateSettings({
+      ...settings,
+      [key]: shortcut,
+    });
+
   return (
     <div className="flex h-full flex-col bg-gray-50 dark:bg-slate-900">
       <div data-tauri-drag-region className="border-b border-gray-200 bg-white p-4 dark:border-slate-800 dark:bg-slate-900">
@@ -84,6 +169,122 @@ const SettingsView: React.FC<SettingsViewProps> = ({
               />
             </button>
           </div>
+
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Update moved app paths</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">When an app is found at a new location while applying, save its new path to the profile</p>
+            </div>
+            <button
+              type="button"
+              onClick={toggleUpdateMovedAppPaths}
+              className={`relative inline-flex h-6 w-11 shrink-0 cursor-pointer rounded-full border-2 border-transparent transition-colors duration-200 ease-in-out focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-600 focus-visible:ring-offset-2 ${
+                settings.update_moved_app_paths ? 'bg-blue-600' : 'bg-gray-200 dark:bg-slate-700'
+              }`}
+            >
+              <span
+                className={`pointer-events-none inline-block h-5 w-5 transform rounded-full bg-white shadow ring-0 transition duration-200 ease-in-out ${
+                  settings.update_moved_app_paths ? 'translate-x-5' : 'translate-x-0'
+                }`}
+              />
+            </button>
+          </div>
+
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Menu bar display</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">What the menu bar shows for the active profile</p>
+            </div>
+            <select
+              value={settings.tray_display}
+              onChange={(event) => changeTrayDisplay(event.target.value as TrayDisplay)}
+              className="rounded-lg border border-gray-300 bg-white px-3 py-1.5 text-sm text-gray-900 shadow-sm focus:border-blue-600 focus:outline-none focus:ring-1 focus:ring-blue-600 dark:border-slate-600 dark:bg-slate-800 dark:text-white"
+            >
+              {TRAY_DISPLAY_OPTIONS.map(({ value, label }) => (
+                <option key={value} value={value}>
+                  {label}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          <div className="flex items-center justify-between">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Menu profile order</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">How profiles are listed in the menu bar menu</p>
+            </div>
+            <select
+              value={settings.tray_profile_order}
+              onChange={(event) => changeProfileOrder(event.target.value as ProfileOrder)}
+              className="rounded-lg border border-gray-300 bg-white px-3 py-1.5 text-sm text-gray-900 shadow-sm focus:border-blue-600 focus:outline-none focus:ring-1 focus:ring-blue-600 dark:border-slate-600 dark:bg-slate-800 dark:text-white"
+            >
+              {PROFILE_ORDER_OPTIONS.map(({ value, label }) => (
+                <option key={value} value={value}>
+                  {label}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          <div className="space-y-4 border-t border-gray-200 pt-6 dark:border-slate-800">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Keyboard shortcuts</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">Global shortcuts that work while DockSwitcher runs in the menu bar</p>
+            </div>
+            {SHORTCUT_OPTIONS.map(({ key, label, description }) => (
+              <div key={key} className="flex items-start justify-between gap-4">
+                <div className="space-y-0.5">
+                  <span className="text-sm font-medium text-gray-900 dark:text-white">{label}</span>
+                  <p className="text-sm text-gray-500 dark:text-gray-400">{description}</p>
+                </div>
+                <ShortcutField
+                  value={settings[key]}
+                  onSave={(shortcut) => changeShortcut(key, shortcut)}
+                />
+              </div>
+            ))}
+          </div>
+
+          <div className="space-y-4 border-t border-gray-200 pt-6 dark:border-slate-800">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Schedules</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">Apply a profile at a set time on the chosen days. Pause automation from the menu bar.</p>
+            </div>
+            <ScheduleList
+              schedules={schedules}
+              profiles={profiles}
+              onSave={onUpdateSchedules}
+            />
+          </div>
+
+          <div className="space-y-4 border-t border-gray-200 pt-6 dark:border-slate-800">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Calendar</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">Apply a profile while an event whose title matches a rule is running, then go back to the previous one. List one .ics file per line.</p>
+            </div>
+            <CalendarRuleList
+              files={calendarFiles}
+              rules={calendarRules}
+              profiles={profiles}
+              onSaveFiles={onUpdateCalendarFiles}
+              onSaveRules={onUpdateCalendarRules}
+            />
+          </div>
+
+          <div className="space-y-4 border-t border-gray-200 pt-6 dark:border-slate-800">
+            <div className="space-y-0.5">
+              <span className="text-base font-medium text-gray-900 dark:text-white">Rules</span>
+              <p className="text-sm text-gray-500 dark:text-gray-400">Apply a profile when all of a rule's conditions hold, such as a volume being mounted or a VPN marker file existing. The matching rule with the highest priority wins, and it only switches when it starts matching.</p>
+            </div>
+            <SignalRuleList
+              rules={signalRules}
+              evaluations={signalEvaluations}
+              lastFiring={lastSignalFiring}
+              profiles={profiles}
+              onSave={onUpdateSignalRules}
+              onExplain={onExplainSignalRules}
+            />
+          </div>
         </div>
       </div>
 